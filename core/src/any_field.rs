@@ -0,0 +1,151 @@
+//! Type-erased fields, which remember their name and type
+
+use crate::{derive::Invariant, Dynamic, Field, FieldDescriptor};
+
+use core::{
+    any::{self, TypeId},
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+/// A type-erased field of `P`, which remembers its name and type
+///
+/// Derived types produce these from their field enums, for example
+/// `Foo::field_of(FooField::Bar)`, and they can be turned back into a typed
+/// field with [`AnyField::downcast`].
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{Field, ProjectTo};
+///
+/// #[derive(Field)]
+/// struct Foo {
+///     bar: u32,
+///     tap: String,
+/// }
+///
+/// # fn main() {
+/// let foo = Foo { bar: 1, tap: "tap".to_string() };
+///
+/// let field = Foo::field_of("bar".parse().unwrap());
+/// assert_eq!(field.name(), "bar");
+/// assert!(field.downcast::<String>().is_none());
+///
+/// let bar = field.downcast::<u32>().unwrap();
+/// assert_eq!(*foo.project_to(bar), 1);
+/// # }
+/// # }
+/// ```
+pub struct AnyField<P> {
+    name:       &'static str,
+    descriptor: FieldDescriptor,
+    type_id:    TypeId,
+    type_name:  &'static str,
+    _parent:    Invariant<P>,
+}
+
+impl<P> Clone for AnyField<P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P> Copy for AnyField<P> {
+}
+
+impl<P> AnyField<P> {
+    /// Erase the type of `field`
+    pub fn new<F: Field<Parent = P>>(name: &'static str, field: F) -> Self
+    where
+        F::Type: 'static,
+    {
+        Self {
+            name,
+            descriptor: field.descriptor(),
+            type_id: TypeId::of::<F::Type>(),
+            type_name: any::type_name::<F::Type>(),
+            _parent: Invariant::INIT,
+        }
+    }
+
+    /// The name of the field
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Where the field lives inside of `P`
+    pub fn descriptor(&self) -> FieldDescriptor {
+        self.descriptor
+    }
+
+    /// The `TypeId` of the type of the field
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// The name of the type of the field, for diagnostics
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Check if the field has the type `T`
+    pub fn is<T: 'static>(&self) -> bool {
+        self.type_id == TypeId::of::<T>()
+    }
+
+    /// Recover a typed field, if the field has the type `T`
+    pub fn downcast<T: 'static>(&self) -> Option<Dynamic<P, T>> {
+        if self.is::<T>() {
+            // Safety
+            // * the descriptor was created from a `Field` of `P` with the
+            //      type `T`, so the offset points to a `T` inside of `P`
+            unsafe { Some(Dynamic::from_offset(self.descriptor.offset())) }
+        } else {
+            None
+        }
+    }
+}
+
+impl<P> fmt::Debug for AnyField<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AnyField")
+            .field("name", &self.name)
+            .field("type", &self.type_name)
+            .field("descriptor", &self.descriptor)
+            .finish()
+    }
+}
+
+impl<P> PartialEq for AnyField<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.descriptor == other.descriptor
+            && self.type_id == other.type_id
+    }
+}
+
+impl<P> Eq for AnyField<P> {
+}
+
+impl<P> Hash for AnyField<P> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.descriptor.hash(state);
+        self.type_id.hash(state);
+    }
+}
+
+/// The error returned when parsing the name of a field which doesn't exist
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownField;
+
+impl fmt::Display for UnknownField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("unknown field name")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnknownField {
+}
@@ -0,0 +1,124 @@
+//! Fields for the elements of arrays
+
+use crate::{
+    derive::{name_hash, FieldByName, Invariant},
+    AutoUnpin,
+    Field,
+    NamedField,
+};
+
+use core::{
+    iter::{self, Once},
+    str,
+};
+
+/// The element at index `I` of a `[T; N]`
+///
+/// `I < N` is checked at compile time when using [`ArrayField::NEW`]. `T` and
+/// `N` can be inferred when chaining, for example
+/// `Foo::fields().regs.chain(ArrayField::<_, _, 3>::NEW)`
+///
+/// The elements at indices `0` to `31` can also be named in
+/// [`chain_type!`](crate::chain_type), for example `Foo => regs.3`
+pub struct ArrayField<T, const N: usize, const I: usize>(Invariant<[T; N]>);
+
+impl<T, const N: usize, const I: usize> ArrayField<T, N, I> {
+    const IN_BOUNDS: () = assert!(I < N, "array index out of bounds");
+
+    /// Default initializer for the field
+    pub const NEW: Self = {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::IN_BOUNDS;
+        Self(Invariant::INIT)
+    };
+
+    /// The decimal digits of `I`, right-aligned and padded with `0` bytes
+    const DIGITS: [u8; 20] = {
+        let mut digits = [0; 20];
+        let mut index = I;
+        let mut at = digits.len();
+
+        loop {
+            at -= 1;
+            digits[at] = b'0' + (index % 10) as u8;
+            index /= 10;
+
+            if index == 0 {
+                break digits;
+            }
+        }
+    };
+
+    /// The name of the element, which is its index
+    const NAME: &'static str = {
+        let mut start = 0;
+
+        while Self::DIGITS[start] == 0 {
+            start += 1;
+        }
+
+        match str::from_utf8(Self::DIGITS.split_at(start).1) {
+            Ok(name) => name,
+            Err(_) => unreachable!(),
+        }
+    };
+}
+
+impl<T, const N: usize, const I: usize> Clone for ArrayField<T, N, I> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, const N: usize, const I: usize> Copy for ArrayField<T, N, I> {
+}
+
+unsafe impl<T, const N: usize, const I: usize> Field for ArrayField<T, N, I> {
+    type Parent = [T; N];
+    type Type = T;
+
+    #[inline]
+    unsafe fn project_raw(
+        &self,
+        ptr: *const Self::Parent,
+    ) -> *const Self::Type {
+        ptr.cast::<T>().add(I)
+    }
+
+    #[inline]
+    unsafe fn project_raw_mut(
+        &self,
+        ptr: *mut Self::Parent,
+    ) -> *mut Self::Type {
+        ptr.cast::<T>().add(I)
+    }
+}
+
+impl<T, const N: usize, const I: usize> AutoUnpin for ArrayField<T, N, I> {
+}
+
+impl<T, const N: usize, const I: usize> NamedField for ArrayField<T, N, I> {
+    type Name = Once<&'static str>;
+
+    #[inline]
+    fn name(&self) -> Self::Name {
+        iter::once(Self::NAME)
+    }
+}
+
+macro_rules! array_by_name {
+    ($($index:tt)*) => {$(
+        impl<T, const N: usize> FieldByName<{ name_hash(stringify!($index)) }>
+            for [T; N]
+        {
+            type Field = ArrayField<T, N, $index>;
+
+            const FIELD: Self::Field = ArrayField::NEW;
+        }
+    )*};
+}
+
+array_by_name! {
+    0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15
+    16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31
+}
@@ -0,0 +1,105 @@
+//! Atomic views of integer fields
+
+use crate::Field;
+
+use core::{mem, sync::atomic::*};
+
+/// A type which has an atomic counterpart
+///
+/// # Safety
+///
+/// `Atomic` must have the same size and bit validity as `Self`
+pub unsafe trait HasAtomic: Sized {
+    /// The atomic counterpart
+    type Atomic;
+}
+
+macro_rules! has_atomic {
+    ($($width:literal: $($type:ty => $atomic:ty),*;)*) => {$($(
+        #[cfg(target_has_atomic = $width)]
+        unsafe impl HasAtomic for $type {
+            type Atomic = $atomic;
+        }
+    )*)*};
+}
+
+has_atomic! {
+    "8": bool => AtomicBool, u8 => AtomicU8, i8 => AtomicI8;
+    "16": u16 => AtomicU16, i16 => AtomicI16;
+    "32": u32 => AtomicU32, i32 => AtomicI32;
+    "64": u64 => AtomicU64, i64 => AtomicI64;
+    "ptr": usize => AtomicUsize, isize => AtomicIsize;
+}
+
+/// Views an integer field as its atomic counterpart, for example a `u32`
+/// field as an `AtomicU32`
+///
+/// This isn't a `Field`, because writing through an atomic projected from a
+/// `&Parent` would mutate memory behind a shared reference. Instead it
+/// projects from `&mut Parent`, or from raw pointers for parents that live in
+/// shared memory.
+#[derive(Clone, Copy)]
+pub struct AtomicField<F> {
+    field: F,
+}
+
+impl<F: Field> AtomicField<F>
+where
+    F::Parent: Sized,
+    F::Type: HasAtomic,
+{
+    /// Create an atomic view of `field`, or `None` if the field may not be
+    /// aligned for its atomic counterpart
+    pub fn new(field: F) -> Option<Self> {
+        let align = mem::align_of::<<F::Type as HasAtomic>::Atomic>();
+
+        if mem::align_of::<F::Parent>().is_multiple_of(align)
+            && field.field_offset().is_multiple_of(align)
+        {
+            Some(Self {
+                field,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Create an atomic view of `field` without checking alignment
+    ///
+    /// # Safety
+    ///
+    /// The field must be aligned for its atomic counterpart in every parent
+    /// that is projected through this view
+    pub unsafe fn new_unchecked(field: F) -> Self {
+        Self {
+            field,
+        }
+    }
+
+    /// Get the wrapped field
+    pub fn field(self) -> F {
+        self.field
+    }
+
+    /// Project a unique reference to the parent to an atomic view of the field
+    pub fn project<'a>(
+        &self,
+        parent: &'a mut F::Parent,
+    ) -> &'a <F::Type as HasAtomic>::Atomic {
+        unsafe { &*self.project_raw(parent) }
+    }
+
+    /// Project a raw pointer from the parent to an atomic view of the field
+    ///
+    /// # Safety
+    ///
+    /// * `ptr` must point to a valid allocation of `Parent`
+    /// * while the projection is in use, every access to the field must be
+    ///     atomic
+    pub unsafe fn project_raw(
+        &self,
+        ptr: *mut F::Parent,
+    ) -> *const <F::Type as HasAtomic>::Atomic {
+        self.field.project_raw_mut(ptr).cast()
+    }
+}
@@ -0,0 +1,362 @@
+//! Grouping, ordering, and joining parents by one of their fields
+
+#[cfg(feature = "alloc")]
+use crate::FieldExt;
+use crate::{Field, ProjectTo};
+
+use core::cmp::Ordering;
+#[cfg(feature = "alloc")]
+use std::vec::Vec;
+#[cfg(feature = "std")]
+use std::{collections::HashMap, hash::Hash, vec};
+
+/// Group `parents` by the value of `field`, keeping the order of the parents
+/// within each group
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{group_by_field, Field};
+///
+/// #[derive(Field, Debug, PartialEq)]
+/// struct Order {
+///     customer: String,
+///     total:    u32,
+/// }
+///
+/// # fn main() {
+/// let orders = vec![
+///     Order { customer: "ada".to_string(), total: 10 },
+///     Order { customer: "bob".to_string(), total: 5 },
+///     Order { customer: "ada".to_string(), total: 7 },
+/// ];
+///
+/// let groups = group_by_field(orders, Order::FIELDS.customer);
+///
+/// assert_eq!(groups["ada"].len(), 2);
+/// assert_eq!(groups["bob"][0].total, 5);
+/// # }
+/// # }
+/// ```
+#[cfg(feature = "std")]
+pub fn group_by_field<F, I>(
+    parents: I,
+    field: F,
+) -> HashMap<F::Type, Vec<F::Parent>>
+where
+    F: Field,
+    F::Parent: Sized,
+    F::Type: Eq + Hash + Clone,
+    I: IntoIterator<Item = F::Parent>,
+{
+    let mut groups = HashMap::<_, Vec<_>>::new();
+
+    for parent in parents {
+        let key = parent.project_to(&field).clone();
+        groups.entry(key).or_default().push(parent);
+    }
+
+    groups
+}
+
+/// Pair up the parents of `left` and `right` whose keys are equal, like an
+/// inner join
+///
+/// This is a hash join, so it takes time linear in the number of parents
+/// and pairs. The pairs are in the order of `left`, and the pairs for one
+/// parent of `left` are in the order of `right`.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{join_by_field, Field};
+///
+/// #[derive(Field)]
+/// struct User {
+///     id:   u32,
+///     name: &'static str,
+/// }
+///
+/// #[derive(Field)]
+/// struct Order {
+///     user:  u32,
+///     total: u32,
+/// }
+///
+/// # fn main() {
+/// let users = [User { id: 1, name: "ada" }, User { id: 2, name: "bob" }];
+/// let orders = [
+///     Order { user: 2, total: 5 },
+///     Order { user: 1, total: 10 },
+///     Order { user: 2, total: 7 },
+/// ];
+///
+/// let user = User::FIELDS.id;
+/// let joined = join_by_field(&users, user, &orders, Order::FIELDS.user)
+///     .map(|(user, order)| (user.name, order.total))
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(joined, [("ada", 10), ("bob", 5), ("bob", 7)]);
+/// # }
+/// # }
+/// ```
+#[cfg(feature = "std")]
+pub fn join_by_field<'a, LF, RF>(
+    left: &'a [LF::Parent],
+    left_key: LF,
+    right: &'a [RF::Parent],
+    right_key: RF,
+) -> vec::IntoIter<(&'a LF::Parent, &'a RF::Parent)>
+where
+    LF: Field,
+    RF: Field<Type = LF::Type>,
+    LF::Parent: Sized,
+    RF::Parent: Sized,
+    LF::Type: Eq + Hash,
+{
+    let mut table = HashMap::<_, Vec<_>>::new();
+
+    for parent in right {
+        let key = parent.project_to(&right_key);
+        table.entry(key).or_default().push(parent);
+    }
+
+    let mut pairs = Vec::new();
+
+    for parent in left {
+        if let Some(matches) = table.get(parent.project_to(&left_key)) {
+            pairs.extend(matches.iter().map(|&other| (parent, other)));
+        }
+    }
+
+    pairs.into_iter()
+}
+
+/// Remove consecutive parents with equal values of `field`, keeping the
+/// first of each run
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{dedup_by_field, Field};
+///
+/// #[derive(Field)]
+/// struct Reading {
+///     sensor: u8,
+///     value:  i32,
+/// }
+///
+/// # fn main() {
+/// let mut readings = vec![
+///     Reading { sensor: 1, value: 10 },
+///     Reading { sensor: 1, value: 11 },
+///     Reading { sensor: 2, value: 12 },
+///     Reading { sensor: 1, value: 13 },
+/// ];
+///
+/// dedup_by_field(&mut readings, Reading::FIELDS.sensor);
+///
+/// let values = readings.iter().map(|r| r.value).collect::<Vec<_>>();
+/// assert_eq!(values, [10, 12, 13]);
+/// # }
+/// # }
+/// ```
+#[cfg(feature = "alloc")]
+pub fn dedup_by_field<F>(parents: &mut Vec<F::Parent>, field: F)
+where
+    F: Field,
+    F::Parent: Sized,
+    F::Type: PartialEq,
+{
+    parents.dedup_by(|a, b| (*a).field(&field) == (*b).field(&field))
+}
+
+/// Sort `parents` by the value of `field`, keeping the order of parents with
+/// equal values
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{binary_search_by_field, sort_by_field, Field};
+///
+/// #[derive(Field)]
+/// struct Route {
+///     prefix: u32,
+///     hop:    &'static str,
+/// }
+///
+/// # fn main() {
+/// let mut routes = vec![
+///     Route { prefix: 30, hop: "c" },
+///     Route { prefix: 10, hop: "a" },
+///     Route { prefix: 20, hop: "b" },
+/// ];
+///
+/// let prefix = Route::FIELDS.prefix;
+/// sort_by_field(&mut routes, prefix);
+///
+/// assert_eq!(binary_search_by_field(&routes, prefix, &20), Ok(1));
+/// assert_eq!(binary_search_by_field(&routes, prefix, &25), Err(2));
+/// # }
+/// # }
+/// ```
+#[cfg(feature = "alloc")]
+pub fn sort_by_field<F>(parents: &mut [F::Parent], field: F)
+where
+    F: Field,
+    F::Parent: Sized,
+    F::Type: Ord,
+{
+    parents.sort_by(|a, b| a.project_to(&field).cmp(b.project_to(&field)))
+}
+
+/// Binary search `parents`, which must be sorted by `field`, for `key`
+///
+/// This is `slice::binary_search` on the values of `field`: it returns the
+/// index of a parent whose field equals `key`, or the index where a parent
+/// with that value could be inserted to keep the slice sorted.
+pub fn binary_search_by_field<F>(
+    parents: &[F::Parent],
+    field: F,
+    key: &F::Type,
+) -> Result<usize, usize>
+where
+    F: Field,
+    F::Parent: Sized,
+    F::Type: Ord,
+{
+    parents.binary_search_by(|parent| parent.project_to(&field).cmp(key))
+}
+
+/// The parent with the greatest value of `field`, or the last one of them if
+/// there are several
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{max_by_field, max_by_field_by, Field};
+///
+/// #[derive(Field)]
+/// struct Player {
+///     name:  &'static str,
+///     level: u32,
+///     speed: f32,
+/// }
+///
+/// # fn main() {
+/// let players = [
+///     Player { name: "ada", level: 3, speed: 1.5 },
+///     Player { name: "bob", level: 7, speed: 0.5 },
+/// ];
+///
+/// let fields = Player::fields();
+/// let top = max_by_field(&players, fields.level).unwrap();
+/// let fastest = max_by_field_by(&players, fields.speed, f32::total_cmp);
+///
+/// assert_eq!(top.name, "bob");
+/// assert_eq!(fastest.unwrap().name, "ada");
+/// # }
+/// # }
+/// ```
+pub fn max_by_field<'a, F, I>(parents: I, field: F) -> Option<&'a F::Parent>
+where
+    F: Field,
+    F::Parent: 'a,
+    F::Type: 'a + Ord,
+    I: IntoIterator<Item = &'a F::Parent>,
+{
+    max_by_field_by(parents, field, Ord::cmp)
+}
+
+/// The parent with the least value of `field`, or the first one of them if
+/// there are several
+pub fn min_by_field<'a, F, I>(parents: I, field: F) -> Option<&'a F::Parent>
+where
+    F: Field,
+    F::Parent: 'a,
+    F::Type: 'a + Ord,
+    I: IntoIterator<Item = &'a F::Parent>,
+{
+    min_by_field_by(parents, field, Ord::cmp)
+}
+
+/// The parent with the greatest key computed from `field`, or the last one
+/// of them if there are several
+pub fn max_by_field_key<'a, F, I, K, G>(
+    parents: I,
+    field: F,
+    mut key: G,
+) -> Option<&'a F::Parent>
+where
+    F: Field,
+    F::Parent: 'a,
+    F::Type: 'a,
+    I: IntoIterator<Item = &'a F::Parent>,
+    K: Ord,
+    G: FnMut(&F::Type) -> K,
+{
+    parents
+        .into_iter()
+        .max_by_key(|&parent| key(parent.project_to(&field)))
+}
+
+/// The parent with the least key computed from `field`, or the first one of
+/// them if there are several
+pub fn min_by_field_key<'a, F, I, K, G>(
+    parents: I,
+    field: F,
+    mut key: G,
+) -> Option<&'a F::Parent>
+where
+    F: Field,
+    F::Parent: 'a,
+    F::Type: 'a,
+    I: IntoIterator<Item = &'a F::Parent>,
+    K: Ord,
+    G: FnMut(&F::Type) -> K,
+{
+    parents
+        .into_iter()
+        .min_by_key(|&parent| key(parent.project_to(&field)))
+}
+
+/// The parent with the greatest value of `field` according to `compare`, or
+/// the last one of them if there are several
+///
+/// This works for fields which are only `PartialOrd`, i.e. floats can be
+/// compared with `f32::total_cmp`.
+pub fn max_by_field_by<'a, F, I, C>(
+    parents: I,
+    field: F,
+    mut compare: C,
+) -> Option<&'a F::Parent>
+where
+    F: Field,
+    F::Parent: 'a,
+    F::Type: 'a,
+    I: IntoIterator<Item = &'a F::Parent>,
+    C: FnMut(&F::Type, &F::Type) -> Ordering,
+{
+    parents
+        .into_iter()
+        .max_by(|&a, &b| compare(a.project_to(&field), b.project_to(&field)))
+}
+
+/// The parent with the least value of `field` according to `compare`, or
+/// the first one of them if there are several
+pub fn min_by_field_by<'a, F, I, C>(
+    parents: I,
+    field: F,
+    mut compare: C,
+) -> Option<&'a F::Parent>
+where
+    F: Field,
+    F::Parent: 'a,
+    F::Type: 'a,
+    I: IntoIterator<Item = &'a F::Parent>,
+    C: FnMut(&F::Type, &F::Type) -> Ordering,
+{
+    parents
+        .into_iter()
+        .min_by(|&a, &b| compare(a.project_to(&field), b.project_to(&field)))
+}
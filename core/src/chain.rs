@@ -1,4 +1,7 @@
 use super::*;
+use crate::derive::PathRoot;
+
+use core::iter;
 
 /// Chain two fields together
 #[derive(Clone, Copy)]
@@ -40,3 +43,163 @@ unsafe impl<A: Field, B: Field<Parent = A::Type>> Field for Chain<A, B> {
         self.b.project_raw_mut(ptr)
     }
 }
+
+impl<A: Field, B: Field<Parent = A::Type>> AutoUnpin for Chain<A, B> {
+}
+
+impl<A: PathRoot, B> PathRoot for Chain<A, B> {
+    type Root = A::Root;
+}
+
+impl<A: NamedField, B: NamedField<Parent = A::Type>> NamedField
+    for Chain<A, B>
+{
+    type Name = iter::Chain<A::Name, B::Name>;
+
+    #[inline]
+    fn name(&self) -> Self::Name {
+        self.a.name().chain(self.b.name())
+    }
+}
+
+/// Convert a tuple of fields into the equivalent [`Chain`]
+///
+/// A tuple `(A, B, C)` is a path of fields, which first projects to `A`, then
+/// `B`, then `C`. Tuples of fields are also fields themselves, so a path can
+/// be passed straight to `project_to` without building a `Chain` first.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{At1, Field, IntoChain, ProjectTo};
+///
+/// #[derive(Field)]
+/// struct Foo {
+///     bar: Bar,
+/// }
+///
+/// #[derive(Field)]
+/// struct Bar {
+///     tap: (u8, u32),
+/// }
+///
+/// # fn main() {
+/// let foo = Foo { bar: Bar { tap: (1, 2) } };
+///
+/// let path = (Foo::FIELDS.bar, Bar::FIELDS.tap, At1::<(u8, u32)>::NEW);
+/// assert_eq!(*foo.project_to(path), 2);
+///
+/// let chain = path.into_chain();
+/// assert_eq!(*foo.project_to(chain), 2);
+/// # }
+/// # }
+/// ```
+pub trait IntoChain {
+    /// The `Chain` of all of the fields in order
+    type Chain;
+
+    /// Chain all of the fields in order
+    fn into_chain(self) -> Self::Chain;
+}
+
+impl<A> IntoChain for (A,) {
+    type Chain = A;
+
+    #[inline]
+    fn into_chain(self) -> Self::Chain {
+        self.0
+    }
+}
+
+unsafe impl<A: Field> Field for (A,) {
+    type Parent = A::Parent;
+    type Type = A::Type;
+
+    #[inline]
+    unsafe fn project_raw(
+        &self,
+        ptr: *const Self::Parent,
+    ) -> *const Self::Type {
+        self.0.project_raw(ptr)
+    }
+
+    #[inline]
+    unsafe fn project_raw_mut(
+        &self,
+        ptr: *mut Self::Parent,
+    ) -> *mut Self::Type {
+        self.0.project_raw_mut(ptr)
+    }
+}
+
+impl<A: Field> AutoUnpin for (A,) {
+}
+
+impl<A: PathRoot> PathRoot for (A,) {
+    type Root = A::Root;
+}
+
+macro_rules! tuple_paths {
+    ($(($first:ident $(, $next:ident: $prev:ident)+) => $last:ident)*) => {$(
+        impl<$first, $($next),+> IntoChain for ($first, $($next),+) {
+            type Chain = Chain<$first, <($($next,)+) as IntoChain>::Chain>;
+
+            #[inline]
+            #[allow(non_snake_case)]
+            fn into_chain(self) -> Self::Chain {
+                let ($first, $($next),+) = self;
+                Chain::new($first, ($($next,)+).into_chain())
+            }
+        }
+
+        unsafe impl<$first: Field, $($next: Field<Parent = $prev::Type>),+>
+            Field for ($first, $($next),+)
+        {
+            type Parent = $first::Parent;
+            type Type = $last::Type;
+
+            #[inline]
+            #[allow(non_snake_case)]
+            unsafe fn project_raw(
+                &self,
+                ptr: *const Self::Parent,
+            ) -> *const Self::Type {
+                let ($first, $($next),+) = self;
+                let ptr = $first.project_raw(ptr);
+                $(let ptr = $next.project_raw(ptr);)+
+                ptr
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            unsafe fn project_raw_mut(
+                &self,
+                ptr: *mut Self::Parent,
+            ) -> *mut Self::Type {
+                let ($first, $($next),+) = self;
+                let ptr = $first.project_raw_mut(ptr);
+                $(let ptr = $next.project_raw_mut(ptr);)+
+                ptr
+            }
+        }
+
+        impl<$first: Field, $($next: Field<Parent = $prev::Type>),+> AutoUnpin
+            for ($first, $($next),+)
+        {
+        }
+
+        impl<$first: PathRoot, $($next),+> PathRoot for ($first, $($next),+) {
+            type Root = $first::Root;
+        }
+    )*};
+}
+
+tuple_paths! {
+    (A, B: A) => B
+    (A, B: A, C: B) => C
+    (A, B: A, C: B, D: C) => D
+    (A, B: A, C: B, D: C, E: D) => E
+    (A, B: A, C: B, D: C, E: D, G: E) => G
+    (A, B: A, C: B, D: C, E: D, G: E, H: G) => H
+    (A, B: A, C: B, D: C, E: D, G: E, H: G, I: H) => I
+}
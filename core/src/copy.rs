@@ -0,0 +1,175 @@
+//! Copying single fields, or lists of fields, between parents, and copying
+//! a field of many parents into a buffer
+
+use crate::{Field, ProjectTo};
+
+use typsy::map::Map;
+
+/// Copy `field` from `from` into `to`
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{copy_field, Field};
+///
+/// #[derive(Field)]
+/// struct Camera {
+///     zoom: f32,
+///     name: String,
+/// }
+///
+/// # fn main() {
+/// let saved = Camera { zoom: 2.0, name: "saved".to_string() };
+/// let mut camera = Camera { zoom: 1.0, name: "main".to_string() };
+///
+/// copy_field(Camera::FIELDS.zoom, &saved, &mut camera);
+///
+/// assert_eq!(camera.zoom, 2.0);
+/// assert_eq!(camera.name, "main");
+/// # }
+/// # }
+/// ```
+#[inline]
+pub fn copy_field<F: Field + Copy>(
+    field: F,
+    from: &F::Parent,
+    to: &mut F::Parent,
+) where
+    F::Type: Copy,
+{
+    *to.project_to(field) = *from.project_to(field)
+}
+
+/// Clone `field` from `from` into `to`, reusing the resources of the old
+/// value in `to` where the type allows it
+#[inline]
+pub fn clone_field_into<F: Field + Copy>(
+    field: F,
+    from: &F::Parent,
+    to: &mut F::Parent,
+) where
+    F::Type: Clone,
+{
+    to.project_to(field).clone_from(from.project_to(field))
+}
+
+/// Copy `field` of every parent into the dense buffer `buffer`
+///
+/// This gathers a column out of a slice of structs, i.e. to upload it to a
+/// GPU buffer, or to hand it to a numeric library.
+///
+/// # Panics
+///
+/// If `parents` and `buffer` have different lengths
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{copy_field_to_slice, Field};
+///
+/// #[derive(Field)]
+/// struct Particle {
+///     position: [f32; 3],
+///     mass:     f32,
+/// }
+///
+/// # fn main() {
+/// let particles = [
+///     Particle { position: [0.0; 3], mass: 1.0 },
+///     Particle { position: [1.0; 3], mass: 2.0 },
+/// ];
+///
+/// let mut masses = [0.0; 2];
+/// copy_field_to_slice(&particles, Particle::FIELDS.mass, &mut masses);
+///
+/// assert_eq!(masses, [1.0, 2.0]);
+/// # }
+/// # }
+/// ```
+pub fn copy_field_to_slice<F: Field>(
+    parents: &[F::Parent],
+    field: F,
+    buffer: &mut [F::Type],
+) where
+    F::Parent: Sized,
+    F::Type: Copy,
+{
+    assert_eq!(
+        parents.len(),
+        buffer.len(),
+        "the buffer must have one element for each parent"
+    );
+
+    for (parent, slot) in parents.iter().zip(buffer) {
+        *slot = *parent.project_to(&field);
+    }
+}
+
+/// Clone every field in the list `fields` from `from` into `to`
+///
+/// The list is built like the lists for `ProjectAll`, so a snapshot of a
+/// few fields can be restored in one call.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{clone_fields_into, Field};
+/// use typsy::convert::Convert;
+///
+/// #[derive(Field, Clone)]
+/// struct Document {
+///     text:   String,
+///     cursor: usize,
+///     path:   String,
+/// }
+///
+/// # fn main() {
+/// let mut doc = Document {
+///     text:   "hello".to_string(),
+///     cursor: 5,
+///     path:   "a.txt".to_string(),
+/// };
+/// let undo = doc.clone();
+///
+/// doc.text.push_str(" world");
+/// doc.cursor = 11;
+/// doc.path = "b.txt".to_string();
+///
+/// let fields = Document::fields();
+/// let restore = (fields.text, fields.cursor).into_hlist();
+/// clone_fields_into(restore, &undo, &mut doc);
+///
+/// assert_eq!(doc.text, "hello");
+/// assert_eq!(doc.cursor, 5);
+/// assert_eq!(doc.path, "b.txt");
+/// # }
+/// # }
+/// ```
+pub fn clone_fields_into<'a, P: ?Sized, L>(
+    fields: L,
+    from: &'a P,
+    to: &'a mut P,
+) where
+    L: Map<CloneInto<'a, P>>,
+{
+    fields.map(CloneInto {
+        from,
+        to,
+    });
+}
+
+/// Clones each field of a list from one parent into another, see
+/// [`clone_fields_into`]
+pub struct CloneInto<'a, P: ?Sized> {
+    from: &'a P,
+    to:   &'a mut P,
+}
+
+typsy::call! {
+    fn['a, F: Field + Copy](&mut self: CloneInto<'a, F::Parent>, field: F) -> ()
+    where(
+        F::Type: Clone,
+    ){
+        clone_field_into(field, self.from, self.to)
+    }
+}
@@ -0,0 +1,114 @@
+//! Projecting through the dependents of self-referential types
+
+use crate::{Field, ProjectTo};
+
+/// A self-referential type whose dependent can be borrowed for `'a`, like the
+/// types built with `self_cell` or `ouroboros`
+///
+/// The dependent is the part which borrows from the owner, so its fields
+/// can be projected to like the fields of any other parent, with
+/// [`project_dependent`](Self::project_dependent). Those crates generate a
+/// borrow method instead of implementing a trait, so this is implemented
+/// with [`borrow_dependent!`](crate::borrow_dependent), which forwards to
+/// that method.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{BorrowDependent, Field};
+/// use self_cell::self_cell;
+///
+/// #[derive(Field)]
+/// struct Header<'a> {
+///     name:  &'a str,
+///     value: &'a str,
+/// }
+///
+/// fn parse(line: &str) -> Header<'_> {
+///     let (name, value) = line.split_once(": ").unwrap();
+///     Header { name, value }
+/// }
+///
+/// self_cell!(
+///     struct OwnedHeader {
+///         owner: String,
+///
+///         #[covariant]
+///         dependent: Header,
+///     }
+/// );
+///
+/// gfp_core::borrow_dependent! {
+///     impl['a] OwnedHeader => Header<'a> { borrow_dependent }
+/// }
+///
+/// # fn main() {
+/// let header = OwnedHeader::new("Host: example.com".to_string(), |line| {
+///     parse(line)
+/// });
+///
+/// let value = header.project_dependent(Header::FIELDS.value);
+/// assert_eq!(*value, "example.com");
+/// # }
+/// # }
+/// ```
+pub trait BorrowDependent<'a> {
+    /// The dependent, with the lifetime of the borrow of `Self`
+    type Dependent: ?Sized + 'a;
+
+    /// Borrow the dependent
+    fn dependent(&'a self) -> &'a Self::Dependent;
+
+    /// Borrow the dependent, and project to `field` of it
+    #[inline]
+    fn project_dependent<F>(&'a self, field: F) -> &'a F::Type
+    where
+        F: Field<Parent = Self::Dependent>,
+    {
+        self.dependent().project_to(field)
+    }
+}
+
+/// Implement [`BorrowDependent`] for a self-referential type, through the
+/// method which borrows its dependent
+///
+/// The lifetime of the borrow is listed first in square brackets, followed
+/// by any other generic parameters, then the type, its dependent for that
+/// lifetime, and the name of the method, i.e. `borrow_dependent` for
+/// `self_cell`, or `borrow_` and the name of the field for `ouroboros`.
+///
+/// ```rust
+/// # mod __ {
+/// # use gfp_core::BorrowDependent;
+/// # struct Parsed<'a, T>(&'a [T]);
+/// # struct Document<T: 'static>(Parsed<'static, T>);
+/// # impl<T> Document<T> {
+/// #     fn borrow_parsed(&self) -> &Parsed<'_, T> { &self.0 }
+/// # }
+/// gfp_core::borrow_dependent! {
+///     impl['a, T: 'a] Document<T> => Parsed<'a, T> { borrow_parsed }
+/// }
+///
+/// # fn main() {
+/// let document = Document(Parsed(&[1, 2, 3]));
+/// assert_eq!(document.dependent().0, [1, 2, 3]);
+/// # }
+/// # }
+/// ```
+#[macro_export]
+macro_rules! borrow_dependent {
+    ($(
+        impl[$lt:lifetime $(, $($generics:tt)+)?] $owner:ty => $dependent:ty {
+            $borrow:ident
+        }
+    )*) => {$(
+        impl<$lt $(, $($generics)+)?> $crate::BorrowDependent<$lt> for $owner {
+            type Dependent = $dependent;
+
+            #[inline]
+            fn dependent(&$lt self) -> &$lt $dependent {
+                self.$borrow()
+            }
+        }
+    )*};
+}
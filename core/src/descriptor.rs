@@ -0,0 +1,354 @@
+use core::{alloc::Layout, fmt, mem, ops::Range};
+
+/// A type-erased description of where a field lives inside of its parent
+///
+/// A `FieldDescriptor` only *describes* a field, it doesn't grant access to
+/// it, so it is safe to construct. Manual `Field` impls can keep one in a
+/// `const` and delegate to it.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// use gfp_core::{Field, FieldDescriptor};
+///
+/// struct Foo {
+///     bar: u8,
+///     tap: u32,
+/// }
+///
+/// const FOO_TAP: FieldDescriptor =
+///     FieldDescriptor::new::<Foo, u32>(core::mem::offset_of!(Foo, tap));
+///
+/// struct FieldTap;
+///
+/// unsafe impl Field for FieldTap {
+///     type Parent = Foo;
+///     type Type = u32;
+///
+///     unsafe fn project_raw(&self, ptr: *const Foo) -> *const u32 {
+///         FOO_TAP.project_raw(ptr)
+///     }
+///
+///     unsafe fn project_raw_mut(&self, ptr: *mut Foo) -> *mut u32 {
+///         FOO_TAP.project_raw_mut(ptr)
+///     }
+/// }
+///
+/// assert_eq!(FieldTap.descriptor(), FOO_TAP);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FieldDescriptor {
+    offset: usize,
+    size:   usize,
+    align:  usize,
+}
+
+impl FieldDescriptor {
+    /// Describe a field of type `T` at `offset` bytes from the start of `P`
+    ///
+    /// # Panics
+    ///
+    /// If a `T` at `offset` doesn't fit inside of a `P`
+    pub const fn new<P, T>(offset: usize) -> Self {
+        let size = mem::size_of::<T>();
+
+        assert!(
+            offset <= mem::size_of::<P>()
+                && size <= mem::size_of::<P>() - offset,
+            "field does not fit inside of its parent"
+        );
+
+        Self {
+            offset,
+            size,
+            align: mem::align_of::<T>(),
+        }
+    }
+
+    /// Describe a field of type `T` at `offset` bytes from the start of `P`,
+    /// or return an error if it can't be a field of `P`
+    ///
+    /// This is for offsets that come from elsewhere, like `memoffset` or a C
+    /// header, so they are validated instead of trusted.
+    ///
+    /// ```rust
+    /// use gfp_core::{FieldDescriptor, OffsetError};
+    ///
+    /// #[repr(C)]
+    /// struct Foo {
+    ///     bar: u8,
+    ///     tap: u32,
+    /// }
+    ///
+    /// let tap = FieldDescriptor::from_offset_checked::<Foo, u32>(4).unwrap();
+    /// assert_eq!(usize::from(tap), 4);
+    ///
+    /// assert!(matches!(
+    ///     FieldDescriptor::from_offset_checked::<Foo, u32>(2),
+    ///     Err(OffsetError::Misaligned { .. })
+    /// ));
+    /// assert!(matches!(
+    ///     FieldDescriptor::from_offset_checked::<Foo, u32>(8),
+    ///     Err(OffsetError::OutOfBounds { .. })
+    /// ));
+    /// ```
+    pub fn from_offset_checked<P, T>(
+        offset: usize,
+    ) -> Result<Self, OffsetError> {
+        Self::from_layouts(Layout::new::<P>(), offset, Layout::new::<T>())
+    }
+
+    /// Describe a field with the layout `field` at `offset` bytes from the
+    /// start of a parent with the layout `parent`, or return an error if it
+    /// can't be a field of the parent
+    pub fn from_layouts(
+        parent: Layout,
+        offset: usize,
+        field: Layout,
+    ) -> Result<Self, OffsetError> {
+        if offset > parent.size() || field.size() > parent.size() - offset {
+            return Err(OffsetError::OutOfBounds {
+                offset,
+                size: field.size(),
+                parent_size: parent.size(),
+            })
+        }
+
+        // the field must be aligned in every instance of the parent, not just
+        // relative to its start
+        if !offset.is_multiple_of(field.align())
+            || parent.align() < field.align()
+        {
+            return Err(OffsetError::Misaligned {
+                offset,
+                align: field.align(),
+            })
+        }
+
+        Ok(Self {
+            offset,
+            size: field.size(),
+            align: field.align(),
+        })
+    }
+
+    /// Describe the field of type `T` at `field`, inside of the `P` at
+    /// `parent`
+    ///
+    /// This takes the offset from a projected pointer instead of from
+    /// `offset_of!`, so the two can be checked against each other, which
+    /// `#[derive(Field)]` does at compile time.
+    ///
+    /// # Safety
+    ///
+    /// `field` must point into the same allocation as `parent`, at or after
+    /// `parent`
+    ///
+    /// # Panics
+    ///
+    /// If a `T` at that offset doesn't fit inside of a `P`
+    pub const unsafe fn from_pointers<P, T>(
+        parent: *const P,
+        field: *const T,
+    ) -> Self {
+        let offset = (field as *const u8).offset_from(parent as *const u8);
+
+        assert!(offset >= 0, "field does not fit inside of its parent");
+
+        Self::new::<P, T>(offset as usize)
+    }
+
+    /// Describe a field which covers the entirety of its parent
+    pub const fn identity<T>() -> Self {
+        Self::new::<T, T>(0)
+    }
+
+    /// The offset in bytes from the start of the parent
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The size in bytes of the field
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The alignment in bytes of the field
+    pub const fn align(&self) -> usize {
+        self.align
+    }
+
+    /// The layout of the field
+    pub fn layout(&self) -> Layout {
+        // Safety
+        // * `size` and `align` were taken from a type or a `Layout`
+        unsafe { Layout::from_size_align_unchecked(self.size, self.align) }
+    }
+
+    /// Return range of offsets covered by the field
+    pub const fn range(&self) -> Range<usize> {
+        self.offset..self.offset + self.size
+    }
+
+    /// Describe the field `inner` of the field described by `self`, this is
+    /// the `const` equivalent of [`Field::chain`](crate::Field::chain)
+    pub const fn chain(&self, inner: Self) -> Self {
+        assert!(
+            inner.offset + inner.size <= self.size,
+            "field does not fit inside of its parent"
+        );
+
+        Self {
+            offset: self.offset + inner.offset,
+            size:   inner.size,
+            align:  inner.align,
+        }
+    }
+
+    /// Project a raw pointer from `P` to the described field
+    ///
+    /// # Safety
+    ///
+    /// * `self` must describe a field of type `T` inside of `P`
+    /// * `ptr` must point to a valid allocation of `P`
+    /// * the projection is not safe to write to
+    pub const unsafe fn project_raw<P, T>(&self, ptr: *const P) -> *const T {
+        add_offset(ptr.cast(), self.offset).cast()
+    }
+
+    /// Project a mutable raw pointer from `P` to the described field
+    ///
+    /// # Safety
+    ///
+    /// * `self` must describe a field of type `T` inside of `P`
+    /// * `ptr` must point to a valid allocation of `P`
+    pub const unsafe fn project_raw_mut<P, T>(&self, ptr: *mut P) -> *mut T {
+        add_offset(ptr as *const u8, self.offset) as *mut T
+    }
+}
+
+impl From<FieldDescriptor> for usize {
+    fn from(descriptor: FieldDescriptor) -> Self {
+        descriptor.offset
+    }
+}
+
+/// The error returned when an offset can't describe a field of a parent, see
+/// [`FieldDescriptor::from_offset_checked`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OffsetError {
+    /// The field would extend past the end of the parent
+    OutOfBounds {
+        /// The offset of the field
+        offset:      usize,
+        /// The size of the field
+        size:        usize,
+        /// The size of the parent
+        parent_size: usize,
+    },
+    /// The field wouldn't be aligned
+    Misaligned {
+        /// The offset of the field
+        offset: usize,
+        /// The alignment of the field
+        align:  usize,
+    },
+}
+
+impl fmt::Display for OffsetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            OffsetError::OutOfBounds {
+                offset,
+                size,
+                parent_size,
+            } => {
+                write!(
+                    f,
+                    "a field of {} bytes at offset {} doesn't fit inside of a \
+                     parent of {} bytes",
+                    size, offset, parent_size
+                )
+            },
+            OffsetError::Misaligned {
+                offset,
+                align,
+            } => {
+                write!(
+                    f,
+                    "offset {} isn't aligned to {} bytes in every parent",
+                    offset, align
+                )
+            },
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OffsetError {
+}
+
+// Dev Note: the offset-based projections share these byte pointer helpers,
+// so the pointer arithmetic, and the casts around it, are written in one
+// place. They are inlined, so they don't reduce the code generated per
+// `Field` instantiation. Calling a single non-inlined `(ptr, offset)`
+// function from every `project_raw` instead makes the code larger, because
+// each field still needs its own `project_raw`, which then also sets up a
+// call.
+
+/// Offset `ptr` by `offset` bytes
+///
+/// # Safety
+///
+/// The same as [`pointer::add`]
+#[inline]
+pub(crate) const unsafe fn add_offset(
+    ptr: *const u8,
+    offset: usize,
+) -> *const u8 {
+    ptr.add(offset)
+}
+
+/// Offset `ptr` by `-offset` bytes
+///
+/// # Safety
+///
+/// The same as [`pointer::sub`]
+#[inline]
+pub(crate) const unsafe fn sub_offset(
+    ptr: *const u8,
+    offset: usize,
+) -> *const u8 {
+    ptr.sub(offset)
+}
+
+/// Offset `ptr` by `offset` bytes using wrapping arithmetic
+#[inline]
+pub(crate) const fn wrapping_add_offset(
+    ptr: *const u8,
+    offset: usize,
+) -> *const u8 {
+    ptr.wrapping_add(offset)
+}
+
+/// Offset `ptr` by `-offset` bytes using wrapping arithmetic
+#[inline]
+pub(crate) const fn wrapping_sub_offset(
+    ptr: *const u8,
+    offset: usize,
+) -> *const u8 {
+    ptr.wrapping_sub(offset)
+}
+
+/// The distance in bytes from `parent` to `field`
+///
+/// # Safety
+///
+/// The same as [`pointer::offset_from`], and `field` must not come before
+/// `parent`
+#[inline]
+pub(crate) unsafe fn offset_between(
+    parent: *const u8,
+    field: *const u8,
+) -> usize {
+    field.offset_from(parent) as usize
+}
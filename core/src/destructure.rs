@@ -0,0 +1,319 @@
+//! Moving fields out of an owned value one at a time
+
+use crate::{path, CompleteFieldLookup, Field};
+
+use core::{mem::ManuallyDrop, ptr};
+#[cfg(feature = "alloc")]
+use std::boxed::Box;
+
+/// An owned `P` which fields can be moved out of, and which drops the fields
+/// that weren't moved out when it is dropped
+///
+/// This is a partial move, like `let Foo { bar, .. } = foo;`, which works
+/// through projections, so fields can be moved out conditionally or one at a
+/// time. The fields are found through [`CompleteFieldLookup`], which is
+/// derived for structs, and the remaining fields are dropped in declaration
+/// order. Like a partial move, this doesn't run the `Drop` impl of `P` itself.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{Destructure, Field};
+///
+/// #[derive(Field)]
+/// struct Request {
+///     method: String,
+///     body:   Vec<u8>,
+///     trace:  String,
+/// }
+///
+/// # fn main() {
+/// let request = Request {
+///     method: "GET".to_string(),
+///     body:   vec![1, 2, 3],
+///     trace:  "abc".to_string(),
+/// };
+///
+/// let mut parts = Destructure::new(request);
+/// let body = parts.take(Request::FIELDS.body);
+/// let method = parts.take(Request::FIELDS.method);
+/// assert!(parts.is_taken(Request::FIELDS.body));
+/// // drops `trace`
+/// drop(parts);
+///
+/// assert_eq!(method, "GET");
+/// assert_eq!(body, [1, 2, 3]);
+/// # }
+/// # }
+/// ```
+///
+/// Only parents whose lookup lists every field can be destructured, so the
+/// fields left out by `#[field(only(...))]` can't be leaked.
+///
+/// ```compile_fail
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{Destructure, Field};
+///
+/// #[derive(Field)]
+/// #[field(only(name))]
+/// struct User {
+///     name:     String,
+///     password: String,
+/// }
+///
+/// # fn main() {
+/// let user = User {
+///     name:     "ferris".to_string(),
+///     password: "hunter2".to_string(),
+/// };
+///
+/// let mut parts = Destructure::new(user);
+/// let name = parts.take(User::FIELDS.name);
+/// # }
+/// # }
+/// ```
+pub struct Destructure<P: CompleteFieldLookup> {
+    value: ManuallyDrop<P>,
+    taken: u64,
+}
+
+impl<P: CompleteFieldLookup> Destructure<P> {
+    /// Start moving fields out of `value`
+    ///
+    /// # Panics
+    ///
+    /// If `P` has more than 64 fields
+    pub fn new(value: P) -> Self {
+        assert!(
+            P::field_names().len() <= 64,
+            "only types with at most 64 fields can be destructured"
+        );
+
+        Self {
+            value: ManuallyDrop::new(value),
+            taken: 0,
+        }
+    }
+
+    /// Move `field` out of the value
+    ///
+    /// # Panics
+    ///
+    /// * if `field` isn't one of the fields of `P`, i.e. if it is a chain of
+    ///     fields
+    /// * if `field` was already moved out
+    pub fn take<F: Field<Parent = P>>(&mut self, field: F) -> F::Type
+    where
+        F::Type: 'static,
+    {
+        // Safety
+        // * the value is initialized, except for the fields marked as taken
+        unsafe { take_field(&*self.value, &mut self.taken, field) }
+    }
+
+    /// Check if `field` was moved out
+    pub fn is_taken<F: Field<Parent = P>>(&self, field: F) -> bool
+    where
+        F::Type: 'static,
+    {
+        is_field_taken(self.taken, field)
+    }
+}
+
+impl<P: CompleteFieldLookup> Drop for Destructure<P> {
+    fn drop(&mut self) {
+        // Safety
+        // * the value is initialized, except for the fields marked as taken
+        unsafe { drop_remaining(&mut *self.value, self.taken) }
+    }
+}
+
+/// A boxed `P` which fields can be moved out of in place, see [`Destructure`]
+///
+/// Only the fields that are moved out are copied out of the allocation, so
+/// this works for parents that are too large to move onto the stack. When it
+/// is dropped, the fields that weren't moved out are dropped in place, then
+/// the allocation is freed.
+#[cfg(feature = "alloc")]
+pub struct DestructureBox<P: CompleteFieldLookup> {
+    value: Box<ManuallyDrop<P>>,
+    taken: u64,
+}
+
+#[cfg(feature = "alloc")]
+impl<P: CompleteFieldLookup> DestructureBox<P> {
+    /// Start moving fields out of `value`
+    ///
+    /// # Panics
+    ///
+    /// If `P` has more than 64 fields
+    pub fn new(value: Box<P>) -> Self {
+        assert!(
+            P::field_names().len() <= 64,
+            "only types with at most 64 fields can be destructured"
+        );
+
+        // Safety
+        // * `ManuallyDrop<P>` has the same layout as `P`
+        let value = unsafe { Box::from_raw(Box::into_raw(value) as *mut _) };
+
+        Self {
+            value,
+            taken: 0,
+        }
+    }
+
+    /// Move `field` out of the value
+    ///
+    /// # Panics
+    ///
+    /// * if `field` isn't one of the fields of `P`, i.e. if it is a chain of
+    ///     fields
+    /// * if `field` was already moved out
+    pub fn take<F: Field<Parent = P>>(&mut self, field: F) -> F::Type
+    where
+        F::Type: 'static,
+    {
+        // Safety
+        // * the value is initialized, except for the fields marked as taken
+        unsafe { take_field(&**self.value, &mut self.taken, field) }
+    }
+
+    /// Check if `field` was moved out
+    pub fn is_taken<F: Field<Parent = P>>(&self, field: F) -> bool
+    where
+        F::Type: 'static,
+    {
+        is_field_taken(self.taken, field)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<P: CompleteFieldLookup> Drop for DestructureBox<P> {
+    fn drop(&mut self) {
+        // Safety
+        // * the value is initialized, except for the fields marked as taken
+        // * the box is freed afterwards, without dropping `P`
+        unsafe { drop_remaining(&mut **self.value, self.taken) }
+    }
+}
+
+/// Moving a field out of a `Box` without moving the rest of the parent
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{ExtractField, Field};
+///
+/// #[derive(Field)]
+/// struct Frame {
+///     header: String,
+///     pixels: [u8; 1 << 16],
+/// }
+///
+/// # fn main() {
+/// let frame = Box::new(Frame {
+///     header: "frame 1".to_string(),
+///     pixels: [0; 1 << 16],
+/// });
+///
+/// // the pixels are dropped in place, and the box is freed
+/// let (header, _) = frame.extract(Frame::FIELDS.header);
+/// assert_eq!(header, "frame 1");
+/// # }
+/// # }
+/// ```
+#[cfg(feature = "alloc")]
+pub trait ExtractField<P: CompleteFieldLookup> {
+    /// Move `field` out, and get the rest of the parent to move more fields
+    /// out of, or to drop
+    ///
+    /// # Panics
+    ///
+    /// If `field` isn't one of the fields of `P`, i.e. if it is a chain of
+    /// fields
+    fn extract<F: Field<Parent = P>>(
+        self,
+        field: F,
+    ) -> (F::Type, DestructureBox<P>)
+    where
+        F::Type: 'static;
+}
+
+#[cfg(feature = "alloc")]
+impl<P: CompleteFieldLookup> ExtractField<P> for Box<P> {
+    fn extract<F: Field<Parent = P>>(
+        self,
+        field: F,
+    ) -> (F::Type, DestructureBox<P>)
+    where
+        F::Type: 'static,
+    {
+        let mut rest = DestructureBox::new(self);
+        let value = rest.take(field);
+        (value, rest)
+    }
+}
+
+/// Move `field` out of `parent`, and mark it as taken
+///
+/// # Safety
+///
+/// `parent` must be initialized, except for the fields marked in `taken`
+unsafe fn take_field<F: Field>(
+    parent: *const F::Parent,
+    taken: &mut u64,
+    field: F,
+) -> F::Type
+where
+    F::Parent: CompleteFieldLookup + Sized,
+    F::Type: 'static,
+{
+    let descriptor = field.descriptor();
+    let index = path::field_index::<F::Parent, F::Type>(descriptor, *taken)
+        .expect("only the fields of the parent can be moved out");
+
+    assert!(*taken & 1 << index == 0, "the field was already moved out");
+    *taken |= 1 << index;
+
+    // Safety
+    // * the field is initialized, and it is marked as taken, so it won't
+    //      be read or dropped again
+    ptr::read(field.project_raw(parent))
+}
+
+fn is_field_taken<F: Field>(taken: u64, field: F) -> bool
+where
+    F::Parent: CompleteFieldLookup + Sized,
+    F::Type: 'static,
+{
+    let descriptor = field.descriptor();
+
+    match path::field_index::<F::Parent, F::Type>(descriptor, taken) {
+        Some(index) => taken & 1 << index != 0,
+        None => false,
+    }
+}
+
+/// Drop the fields of `parent` which aren't marked in `taken`, in declaration
+/// order
+///
+/// # Safety
+///
+/// `parent` must be initialized, except for the fields marked in `taken`
+unsafe fn drop_remaining<P: CompleteFieldLookup>(parent: *mut P, taken: u64) {
+    let parent = parent as *mut u8;
+
+    for (index, name) in P::field_names().iter().enumerate() {
+        if taken & 1 << index != 0 {
+            continue
+        }
+
+        if let Some(info) = P::lookup_field(name) {
+            // Safety
+            // * the field wasn't moved out, so it is still initialized
+            info.drop_in_place(parent)
+        }
+    }
+}
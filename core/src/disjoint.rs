@@ -0,0 +1,92 @@
+//! Compile-time proofs that two fields of a parent never overlap
+
+use crate::{
+    derive::{PathRoot, Siblings},
+    Field,
+};
+
+/// A proof that the fields `Self` and `F` of the same parent never overlap
+///
+/// `#[derive(Field)]` proves this for every pair of distinct fields of a
+/// struct, but not a union, and it carries over to every path through those
+/// fields, i.e. `Foo::FIELDS.a.chain(Bar::FIELDS.x)` is disjoint from
+/// `Foo::FIELDS.b`, and from every path which starts at `Foo::FIELDS.b`. Two
+/// paths through the same field of the parent aren't proven disjoint, even
+/// if they split up further down.
+///
+/// This makes it possible to hand out unique references to two fields at
+/// once without checking their offsets at runtime.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{Disjoint, Field};
+///
+/// fn both_mut<A, B>(
+///     parent: &mut A::Parent,
+///     a: A,
+///     b: B,
+/// ) -> (&mut A::Type, &mut B::Type)
+/// where
+///     A: Disjoint<B>,
+///     B: Field<Parent = A::Parent>,
+/// {
+///     let parent = parent as *mut A::Parent;
+///
+///     // Safety
+///     // * `A: Disjoint<B>`, so the fields don't alias
+///     unsafe {
+///         let a = &mut *a.project_raw_mut(parent);
+///         let b = &mut *b.project_raw_mut(parent);
+///         (a, b)
+///     }
+/// }
+///
+/// #[derive(Field)]
+/// struct Player {
+///     name:  String,
+///     stats: Stats,
+/// }
+///
+/// #[derive(Field)]
+/// struct Stats {
+///     health: u32,
+///     armor:  u32,
+/// }
+///
+/// # fn main() {
+/// let mut player = Player {
+///     name:  "ferris".to_string(),
+///     stats: Stats { health: 10, armor: 3 },
+/// };
+///
+/// let health = Player::FIELDS.stats.chain(Stats::FIELDS.health);
+/// let (name, health) = both_mut(&mut player, Player::FIELDS.name, health);
+/// name.push('!');
+/// *health += 1;
+///
+/// assert_eq!(player.name, "ferris!");
+/// assert_eq!(player.stats.health, 11);
+/// # }
+/// # }
+/// ```
+///
+/// # Safety
+///
+/// There must be no byte which is in both the field `Self` and the field `F`
+/// of any parent. This trait can't be implemented directly, the derive
+/// implements it through the hidden traits which back it.
+pub unsafe trait Disjoint<F: Field<Parent = Self::Parent>>: Field {
+}
+
+// Safety
+// * `PathRoot::Root` is the field of the parent that contains every field
+//      along the path, and `Siblings` is only implemented for distinct
+//      fields of a struct, so the paths are inside of disjoint fields
+unsafe impl<A, B> Disjoint<B> for A
+where
+    A: Field + PathRoot,
+    B: Field<Parent = A::Parent> + PathRoot,
+    A::Root: Siblings<B::Root>,
+{
+}
@@ -1,4 +1,4 @@
-use crate::Field;
+use crate::{descriptor::add_offset, Field};
 
 /// A runtime offset based `Field`. This is a more efficient version
 /// of `dyn Field<Parent = P, Type = T, Name = N>`.
@@ -48,14 +48,14 @@ unsafe impl<P, T> Field for Dynamic<P, T> {
         &self,
         ptr: *const Self::Parent,
     ) -> *const Self::Type {
-        ptr.cast::<u8>().add(self.offset).cast()
+        add_offset(ptr.cast(), self.offset).cast()
     }
 
     unsafe fn project_raw_mut(
         &self,
         ptr: *mut Self::Parent,
     ) -> *mut Self::Type {
-        ptr.cast::<u8>().add(self.offset).cast()
+        add_offset(ptr as *const u8, self.offset) as *mut Self::Type
     }
 
     fn field_offset(&self) -> usize {
@@ -0,0 +1,193 @@
+//! Field access for the components of entity-component systems
+//!
+//! Engines implement [`ComponentFields`] by giving access to whole
+//! components, then single fields of a component can be read and updated
+//! generically, either with a typed `Field`, or with an [`AnyField`] which
+//! was chosen at run-time, i.e. from an editor or a script.
+//!
+//! [`World`] is a minimal store which implements it, for tests and small
+//! tools.
+
+use crate::{AnyField, Field, ProjectTo};
+
+use core::mem;
+
+/// A store of components which can be accessed by field
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{
+///     ecs::{ComponentFields, World},
+///     Field,
+/// };
+///
+/// #[derive(Field)]
+/// struct Position {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// # fn main() {
+/// let mut world = World::new();
+/// let entity = world.spawn();
+/// world.insert(entity, Position { x: 1.0, y: 2.0 });
+///
+/// *world.get_field_mut(entity, Position::FIELDS.x).unwrap() += 1.0;
+/// assert_eq!(world.get_field(entity, Position::FIELDS.x), Some(&2.0));
+///
+/// let y = Position::field_of("y".parse().unwrap());
+/// assert_eq!(world.get_any_field::<_, f32>(entity, y), Some(&2.0));
+/// # }
+/// # }
+/// ```
+pub trait ComponentFields {
+    /// The handle of an entity
+    type Entity: Copy;
+
+    /// Get the component `C` of `entity`, if it has one
+    fn component<C: 'static>(&self, entity: Self::Entity) -> Option<&C>;
+
+    /// Mutably get the component `C` of `entity`, if it has one
+    fn component_mut<C: 'static>(
+        &mut self,
+        entity: Self::Entity,
+    ) -> Option<&mut C>;
+
+    /// Get the field `field` of the component `C` of `entity`
+    fn get_field<C: 'static, F: Field<Parent = C>>(
+        &self,
+        entity: Self::Entity,
+        field: F,
+    ) -> Option<&F::Type> {
+        self.component::<C>(entity)
+            .map(|component| component.project_to(field))
+    }
+
+    /// Mutably get the field `field` of the component `C` of `entity`
+    fn get_field_mut<C: 'static, F: Field<Parent = C>>(
+        &mut self,
+        entity: Self::Entity,
+        field: F,
+    ) -> Option<&mut F::Type> {
+        self.component_mut::<C>(entity)
+            .map(|component| component.project_to(field))
+    }
+
+    /// Replace the field `field` of the component `C` of `entity`, and
+    /// return the old value, or give back `value` if there is no component
+    fn replace_field<C: 'static, F: Field<Parent = C>>(
+        &mut self,
+        entity: Self::Entity,
+        field: F,
+        value: F::Type,
+    ) -> Result<F::Type, F::Type> {
+        match self.get_field_mut(entity, field) {
+            Some(old) => Ok(mem::replace(old, value)),
+            None => Err(value),
+        }
+    }
+
+    /// Get a field which was chosen at run-time, if it has the type `T`
+    fn get_any_field<C: 'static, T: 'static>(
+        &self,
+        entity: Self::Entity,
+        field: AnyField<C>,
+    ) -> Option<&T> {
+        let field = field.downcast::<T>()?;
+        self.get_field(entity, field)
+    }
+
+    /// Mutably get a field which was chosen at run-time, if it has the type
+    /// `T`
+    fn get_any_field_mut<C: 'static, T: 'static>(
+        &mut self,
+        entity: Self::Entity,
+        field: AnyField<C>,
+    ) -> Option<&mut T> {
+        let field = field.downcast::<T>()?;
+        self.get_field_mut(entity, field)
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub use self::world::{Entity, World};
+
+#[cfg(feature = "alloc")]
+mod world {
+    use super::ComponentFields;
+
+    use core::any::{Any, TypeId};
+    use std::{boxed::Box, collections::BTreeMap};
+
+    /// An entity in a [`World`]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct Entity(u64);
+
+    /// A minimal store of components, which maps each component type to the
+    /// entities that have it
+    #[derive(Default)]
+    pub struct World {
+        next:       u64,
+        components: BTreeMap<TypeId, Box<dyn Any>>,
+    }
+
+    impl World {
+        /// Create an empty world
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Create a new entity without any components
+        pub fn spawn(&mut self) -> Entity {
+            let entity = Entity(self.next);
+            self.next += 1;
+            entity
+        }
+
+        /// Add the component `component` to `entity`, and return the old
+        /// component of the same type if there was one
+        pub fn insert<C: 'static>(
+            &mut self,
+            entity: Entity,
+            component: C,
+        ) -> Option<C> {
+            self.components
+                .entry(TypeId::of::<C>())
+                .or_insert_with(|| Box::new(BTreeMap::<Entity, C>::new()))
+                .downcast_mut::<BTreeMap<Entity, C>>()?
+                .insert(entity, component)
+        }
+
+        /// Remove the component `C` from `entity`, and return it if there
+        /// was one
+        pub fn remove<C: 'static>(&mut self, entity: Entity) -> Option<C> {
+            self.storage_mut::<C>()?.remove(&entity)
+        }
+
+        fn storage<C: 'static>(&self) -> Option<&BTreeMap<Entity, C>> {
+            self.components.get(&TypeId::of::<C>())?.downcast_ref()
+        }
+
+        fn storage_mut<C: 'static>(
+            &mut self,
+        ) -> Option<&mut BTreeMap<Entity, C>> {
+            self.components.get_mut(&TypeId::of::<C>())?.downcast_mut()
+        }
+    }
+
+    impl ComponentFields for World {
+        type Entity = Entity;
+
+        fn component<C: 'static>(&self, entity: Entity) -> Option<&C> {
+            self.storage::<C>()?.get(&entity)
+        }
+
+        fn component_mut<C: 'static>(
+            &mut self,
+            entity: Entity,
+        ) -> Option<&mut C> {
+            self.storage_mut::<C>()?.get_mut(&entity)
+        }
+    }
+}
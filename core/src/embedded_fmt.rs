@@ -0,0 +1,248 @@
+//! `defmt` and `ufmt` formatting for paths and errors
+//!
+//! These mirror the `Display` impls, so field-level information can be
+//! logged on embedded targets without `core::fmt`.
+
+use crate::{
+    path::MAX_DEPTH,
+    FieldPath,
+    OffsetError,
+    PathError,
+    ResolveError,
+    UnknownField,
+};
+#[cfg(feature = "alloc")]
+use crate::OverlapError;
+
+#[cfg(feature = "defmt")]
+mod defmt_impls {
+    use super::*;
+
+    use defmt::{Format, Formatter};
+
+    impl<P> Format for FieldPath<P> {
+        fn format(&self, f: Formatter) {
+            for (i, name) in self.segments().iter().enumerate() {
+                if i != 0 {
+                    defmt::write!(f, ".");
+                }
+
+                defmt::write!(f, "{=str}", name);
+            }
+        }
+    }
+
+    impl Format for PathError {
+        fn format(&self, f: Formatter) {
+            if self.is_too_deep() {
+                return defmt::write!(
+                    f,
+                    "path is deeper than the maximum of {=usize} segments",
+                    MAX_DEPTH
+                )
+            }
+
+            if self.is_invalid_pointer() {
+                return defmt::write!(
+                    f,
+                    "JSON pointers to fields must start with `/`"
+                )
+            }
+
+            defmt::write!(
+                f,
+                "`{=str}` has no field named by segment {=usize} of the path",
+                self.parent(),
+                self.segment()
+            )
+        }
+    }
+
+    impl Format for ResolveError {
+        fn format(&self, f: Formatter) {
+            match self {
+                ResolveError::UnknownType => defmt::write!(f, "unknown type"),
+                ResolveError::NoFields => {
+                    defmt::write!(f, "the path has no fields")
+                },
+                ResolveError::Path(err) => err.format(f),
+            }
+        }
+    }
+
+    impl Format for OffsetError {
+        fn format(&self, f: Formatter) {
+            match *self {
+                OffsetError::OutOfBounds {
+                    offset,
+                    size,
+                    parent_size,
+                } => {
+                    defmt::write!(
+                        f,
+                        "a field of {=usize} bytes at offset {=usize} doesn't \
+                         fit inside of a parent of {=usize} bytes",
+                        size,
+                        offset,
+                        parent_size
+                    )
+                },
+                OffsetError::Misaligned {
+                    offset,
+                    align,
+                } => {
+                    defmt::write!(
+                        f,
+                        "offset {=usize} isn't aligned to {=usize} bytes in \
+                         every parent",
+                        offset,
+                        align
+                    )
+                },
+            }
+        }
+    }
+
+    impl Format for UnknownField {
+        fn format(&self, f: Formatter) {
+            defmt::write!(f, "unknown field name")
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    impl Format for OverlapError {
+        fn format(&self, f: Formatter) {
+            let (first, second) = self.fields();
+            defmt::write!(f, "`{=str}` overlaps `{=str}`", second, first)
+        }
+    }
+}
+
+#[cfg(feature = "ufmt")]
+mod ufmt_impls {
+    use super::*;
+
+    use ufmt::{uDisplay, uWrite, uwrite, Formatter};
+
+    impl<P> uDisplay for FieldPath<P> {
+        fn fmt<W: uWrite + ?Sized>(
+            &self,
+            f: &mut Formatter<'_, W>,
+        ) -> Result<(), W::Error> {
+            for (i, name) in self.segments().iter().enumerate() {
+                if i != 0 {
+                    f.write_str(".")?;
+                }
+
+                f.write_str(name)?;
+            }
+
+            Ok(())
+        }
+    }
+
+    impl uDisplay for PathError {
+        fn fmt<W: uWrite + ?Sized>(
+            &self,
+            f: &mut Formatter<'_, W>,
+        ) -> Result<(), W::Error> {
+            if self.is_too_deep() {
+                return uwrite!(
+                    f,
+                    "path is deeper than the maximum of {} segments",
+                    MAX_DEPTH
+                )
+            }
+
+            if self.is_invalid_pointer() {
+                return f
+                    .write_str("JSON pointers to fields must start with `/`")
+            }
+
+            #[cfg(feature = "alloc")]
+            return uwrite!(
+                f,
+                "`{}` has no field named `{}` (segment {} of the path)",
+                self.parent(),
+                self.name(),
+                self.segment()
+            );
+
+            #[cfg(not(feature = "alloc"))]
+            return uwrite!(
+                f,
+                "`{}` has no field named by segment {} of the path",
+                self.parent(),
+                self.segment()
+            );
+        }
+    }
+
+    impl uDisplay for ResolveError {
+        fn fmt<W: uWrite + ?Sized>(
+            &self,
+            f: &mut Formatter<'_, W>,
+        ) -> Result<(), W::Error> {
+            match self {
+                ResolveError::UnknownType => f.write_str("unknown type"),
+                ResolveError::NoFields => f.write_str("the path has no fields"),
+                ResolveError::Path(err) => err.fmt(f),
+            }
+        }
+    }
+
+    impl uDisplay for OffsetError {
+        fn fmt<W: uWrite + ?Sized>(
+            &self,
+            f: &mut Formatter<'_, W>,
+        ) -> Result<(), W::Error> {
+            match *self {
+                OffsetError::OutOfBounds {
+                    offset,
+                    size,
+                    parent_size,
+                } => {
+                    uwrite!(
+                        f,
+                        "a field of {} bytes at offset {} doesn't fit inside \
+                         of a parent of {} bytes",
+                        size,
+                        offset,
+                        parent_size
+                    )
+                },
+                OffsetError::Misaligned {
+                    offset,
+                    align,
+                } => {
+                    uwrite!(
+                        f,
+                        "offset {} isn't aligned to {} bytes in every parent",
+                        offset,
+                        align
+                    )
+                },
+            }
+        }
+    }
+
+    impl uDisplay for UnknownField {
+        fn fmt<W: uWrite + ?Sized>(
+            &self,
+            f: &mut Formatter<'_, W>,
+        ) -> Result<(), W::Error> {
+            f.write_str("unknown field name")
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    impl uDisplay for OverlapError {
+        fn fmt<W: uWrite + ?Sized>(
+            &self,
+            f: &mut Formatter<'_, W>,
+        ) -> Result<(), W::Error> {
+            let (first, second) = self.fields();
+            uwrite!(f, "`{}` overlaps `{}`", second, first)
+        }
+    }
+}
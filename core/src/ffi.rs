@@ -0,0 +1,144 @@
+//! Export the layouts of derived types to C
+//!
+//! This is meant to be called from a build script, or a small generator
+//! binary, so C code can share the layouts of `#[repr(C)]` types without
+//! keeping the offsets in sync by hand.
+//!
+//! ```rust
+//! #![feature(raw_ref_op)]
+//! # mod __ {
+//! use gfp_core::{ffi::CHeader, Field, HasStructInfo};
+//!
+//! #[derive(Field)]
+//! #[repr(C)]
+//! struct Packet {
+//!     kind: u8,
+//!     len:  u32,
+//! }
+//!
+//! # fn main() {
+//! let header = CHeader::new().defines(&Packet::STRUCT_INFO).finish();
+//!
+//! assert_eq!(
+//!     header,
+//!     "#define PACKET_SIZE 8\n\
+//!      #define PACKET_ALIGN 4\n\
+//!      #define PACKET_KIND_OFFSET 0\n\
+//!      #define PACKET_KIND_SIZE 1\n\
+//!      #define PACKET_LEN_OFFSET 4\n\
+//!      #define PACKET_LEN_SIZE 4\n"
+//! );
+//! # }
+//! # }
+//! ```
+
+use crate::StructInfo;
+
+use core::fmt::Write;
+use std::string::String;
+
+/// Renders the layouts of types as a C header snippet
+#[derive(Debug, Clone, Default)]
+pub struct CHeader {
+    out:        String,
+    table_type: bool,
+}
+
+/// The C declaration of the entries in the tables from [`CHeader::table`]
+const TABLE_TYPE: &str = "struct gfp_field {
+    const char *name;
+    size_t offset;
+    size_t size;
+};
+";
+
+impl CHeader {
+    /// Create an empty header
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `#define`s for the size and alignment of the type, and the offset
+    /// and size of each field, i.e. `#define FOO_BAR_OFFSET 8` for `Foo::bar`
+    pub fn defines(mut self, info: &StructInfo) -> Self {
+        let prefix = to_upper_snake_case(info.name());
+
+        // writing to a `String` can't fail
+        let _ = writeln!(self.out, "#define {}_SIZE {}", prefix, info.size());
+        let _ = writeln!(self.out, "#define {}_ALIGN {}", prefix, info.align());
+
+        for (name, descriptor) in info.fields() {
+            let name = to_upper_snake_case(name);
+
+            let _ = writeln!(
+                self.out,
+                "#define {}_{}_OFFSET {}",
+                prefix,
+                name,
+                descriptor.offset()
+            );
+            let _ = writeln!(
+                self.out,
+                "#define {}_{}_SIZE {}",
+                prefix,
+                name,
+                descriptor.size()
+            );
+        }
+
+        self
+    }
+
+    /// Add a static table with the name, offset, and size of each field,
+    /// named i.e. `foo_fields` for `Foo`
+    ///
+    /// The declaration of `struct gfp_field` is added before the first table,
+    /// and the header must be included after `<stddef.h>`.
+    pub fn table(mut self, info: &StructInfo) -> Self {
+        if !self.table_type {
+            self.table_type = true;
+            self.out.push_str(TABLE_TYPE);
+        }
+
+        let _ = writeln!(
+            self.out,
+            "static const struct gfp_field {}_fields[] = {{",
+            to_upper_snake_case(info.name()).to_lowercase()
+        );
+
+        for (name, descriptor) in info.fields() {
+            let _ = writeln!(
+                self.out,
+                "    {{ \"{}\", {}, {} }},",
+                name,
+                descriptor.offset(),
+                descriptor.size()
+            );
+        }
+
+        self.out.push_str("};\n");
+        self
+    }
+
+    /// Get the rendered header
+    pub fn finish(self) -> String {
+        self.out
+    }
+}
+
+/// `FooBar` and `foo_bar` both become `FOO_BAR`
+fn to_upper_snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    let mut prev_lower = false;
+
+    for c in name.chars() {
+        if c.is_uppercase() && prev_lower {
+            snake.push('_');
+        }
+
+        prev_lower = c.is_lowercase() || c.is_numeric();
+        snake.extend(c.to_uppercase());
+    }
+
+    snake
+}
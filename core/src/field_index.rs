@@ -0,0 +1,431 @@
+//! Lookup tables of parents, keyed by one of their fields
+
+use crate::{Field, ProjectTo};
+
+use core::{borrow::Borrow, fmt, ops::RangeBounds};
+use std::collections::{btree_map, BTreeMap};
+#[cfg(feature = "std")]
+use core::hash::Hash;
+#[cfg(feature = "std")]
+use std::collections::{hash_map, HashMap};
+
+/// Collect `parents` into a map keyed by the value of `field`
+///
+/// If several parents have the same value, the last one is kept. Use
+/// [`position_index_by`] to index a slice without moving the parents.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{index_by, Field};
+///
+/// #[derive(Field)]
+/// struct User {
+///     id:   u32,
+///     name: String,
+/// }
+///
+/// # fn main() {
+/// let users = vec![
+///     User { id: 7, name: "ada".to_string() },
+///     User { id: 3, name: "bob".to_string() },
+/// ];
+///
+/// let users = index_by(users, User::FIELDS.id);
+/// assert_eq!(users[&3].name, "bob");
+/// # }
+/// # }
+/// ```
+#[cfg(feature = "std")]
+pub fn index_by<F, I>(parents: I, field: F) -> HashMap<F::Type, F::Parent>
+where
+    F: Field,
+    F::Parent: Sized,
+    F::Type: Eq + Hash + Clone,
+    I: IntoIterator<Item = F::Parent>,
+{
+    parents
+        .into_iter()
+        .map(|parent| (parent.project_to(&field).clone(), parent))
+        .collect()
+}
+
+/// Map the value of `field` of each parent to its index in `parents`
+///
+/// If several parents have the same value, the last index is kept.
+#[cfg(feature = "std")]
+pub fn position_index_by<F>(
+    parents: &[F::Parent],
+    field: F,
+) -> HashMap<F::Type, usize>
+where
+    F: Field,
+    F::Parent: Sized,
+    F::Type: Eq + Hash + Clone,
+{
+    parents
+        .iter()
+        .enumerate()
+        .map(|(index, parent)| (parent.project_to(&field).clone(), index))
+        .collect()
+}
+
+/// A set of parents which can be looked up by the value of `field`
+///
+/// The parents are only handed out by shared reference, or through
+/// [`modify`](FieldIndex::modify), so the index can't go out of sync with the
+/// fields of the parents.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{Field, FieldIndex};
+///
+/// #[derive(Field)]
+/// struct Session {
+///     token: String,
+///     user:  u32,
+/// }
+///
+/// # fn main() {
+/// let mut sessions = FieldIndex::new(Session::FIELDS.token);
+///
+/// sessions.insert(Session { token: "a1".to_string(), user: 7 });
+/// sessions.insert(Session { token: "b2".to_string(), user: 3 });
+///
+/// assert_eq!(sessions.get("b2").unwrap().user, 3);
+///
+/// sessions.modify("a1", |session| session.token = "c3".to_string());
+/// assert!(sessions.get("a1").is_none());
+/// assert_eq!(sessions.get("c3").unwrap().user, 7);
+/// # }
+/// # }
+/// ```
+#[cfg(feature = "std")]
+pub struct FieldIndex<F: Field>
+where
+    F::Parent: Sized,
+{
+    field: F,
+    map:   HashMap<F::Type, F::Parent>,
+}
+
+#[cfg(feature = "std")]
+impl<F: Field> FieldIndex<F>
+where
+    F::Parent: Sized,
+    F::Type: Eq + Hash + Clone,
+{
+    /// Create an empty index, keyed by `field`
+    pub fn new(field: F) -> Self {
+        Self {
+            field,
+            map: HashMap::new(),
+        }
+    }
+
+    /// The number of parents in the index
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Check if there are no parents in the index
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Add `parent` to the index, and get back the parent with the same key
+    /// which it replaced
+    pub fn insert(&mut self, parent: F::Parent) -> Option<F::Parent> {
+        let key = parent.project_to(&self.field).clone();
+        self.map.insert(key, parent)
+    }
+
+    /// Get the parent whose field is equal to `key`
+    pub fn get<Q>(&self, key: &Q) -> Option<&F::Parent>
+    where
+        F::Type: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        self.map.get(key)
+    }
+
+    /// Check if there is a parent whose field is equal to `key`
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        F::Type: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        self.map.contains_key(key)
+    }
+
+    /// Remove the parent whose field is equal to `key`
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<F::Parent>
+    where
+        F::Type: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        self.map.remove(key)
+    }
+
+    /// Modify the parent whose field is equal to `key`, and move it to its
+    /// new key afterwards
+    ///
+    /// Returns the parent that was replaced at the new key, if there was one,
+    /// or `None` if there is no parent with the field `key`.
+    pub fn modify<Q, G>(&mut self, key: &Q, f: G) -> Option<F::Parent>
+    where
+        F::Type: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+        G: FnOnce(&mut F::Parent),
+    {
+        let mut parent = self.map.remove(key)?;
+        f(&mut parent);
+        self.insert(parent)
+    }
+
+    /// Iterate over the parents, in an arbitrary order
+    pub fn values(&self) -> hash_map::Values<'_, F::Type, F::Parent> {
+        self.map.values()
+    }
+
+    /// Take the parents out of the index
+    pub fn into_map(self) -> HashMap<F::Type, F::Parent> {
+        self.map
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F: Field> Extend<F::Parent> for FieldIndex<F>
+where
+    F::Parent: Sized,
+    F::Type: Eq + Hash + Clone,
+{
+    fn extend<I: IntoIterator<Item = F::Parent>>(&mut self, parents: I) {
+        for parent in parents {
+            self.insert(parent);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F: Field> fmt::Debug for FieldIndex<F>
+where
+    F::Parent: Sized + fmt::Debug,
+    F::Type: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.map.iter()).finish()
+    }
+}
+
+/// Collect `parents` into a map ordered by the value of `field`
+///
+/// If several parents have the same value, the last one is kept.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{ordered_index_by, Field};
+///
+/// #[derive(Field)]
+/// struct Event {
+///     time: u64,
+///     name: &'static str,
+/// }
+///
+/// # fn main() {
+/// let events = vec![
+///     Event { time: 30, name: "stop" },
+///     Event { time: 10, name: "start" },
+///     Event { time: 20, name: "pause" },
+/// ];
+///
+/// let events = ordered_index_by(events, Event::FIELDS.time);
+///
+/// let names = events.range(..25).map(|(_, e)| e.name).collect::<Vec<_>>();
+/// assert_eq!(names, ["start", "pause"]);
+/// # }
+/// # }
+/// ```
+pub fn ordered_index_by<F, I>(
+    parents: I,
+    field: F,
+) -> BTreeMap<F::Type, F::Parent>
+where
+    F: Field,
+    F::Parent: Sized,
+    F::Type: Ord + Clone,
+    I: IntoIterator<Item = F::Parent>,
+{
+    parents
+        .into_iter()
+        .map(|parent| (parent.project_to(&field).clone(), parent))
+        .collect()
+}
+
+/// A set of parents ordered by the value of `field`, which can be looked up
+/// by the value or by a range of values
+///
+/// This is the ordered version of [`FieldIndex`], and it works the same way.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{Field, OrderedFieldIndex};
+///
+/// #[derive(Field)]
+/// struct Lease {
+///     expires: u64,
+///     client:  &'static str,
+/// }
+///
+/// # fn main() {
+/// let mut leases = OrderedFieldIndex::new(Lease::FIELDS.expires);
+///
+/// leases.insert(Lease { expires: 300, client: "b" });
+/// leases.insert(Lease { expires: 100, client: "a" });
+/// leases.insert(Lease { expires: 900, client: "c" });
+///
+/// let expired = leases.range(..=300).map(|lease| lease.client);
+/// assert_eq!(expired.collect::<Vec<_>>(), ["a", "b"]);
+/// assert_eq!(leases.last().unwrap().client, "c");
+/// # }
+/// # }
+/// ```
+pub struct OrderedFieldIndex<F: Field>
+where
+    F::Parent: Sized,
+{
+    field: F,
+    map:   BTreeMap<F::Type, F::Parent>,
+}
+
+impl<F: Field> OrderedFieldIndex<F>
+where
+    F::Parent: Sized,
+    F::Type: Ord + Clone,
+{
+    /// Create an empty index, ordered by `field`
+    pub fn new(field: F) -> Self {
+        Self {
+            field,
+            map: BTreeMap::new(),
+        }
+    }
+
+    /// The number of parents in the index
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Check if there are no parents in the index
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Add `parent` to the index, and get back the parent with the same key
+    /// which it replaced
+    pub fn insert(&mut self, parent: F::Parent) -> Option<F::Parent> {
+        let key = parent.project_to(&self.field).clone();
+        self.map.insert(key, parent)
+    }
+
+    /// Get the parent whose field is equal to `key`
+    pub fn get<Q>(&self, key: &Q) -> Option<&F::Parent>
+    where
+        F::Type: Borrow<Q>,
+        Q: ?Sized + Ord,
+    {
+        self.map.get(key)
+    }
+
+    /// Check if there is a parent whose field is equal to `key`
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        F::Type: Borrow<Q>,
+        Q: ?Sized + Ord,
+    {
+        self.map.contains_key(key)
+    }
+
+    /// Remove the parent whose field is equal to `key`
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<F::Parent>
+    where
+        F::Type: Borrow<Q>,
+        Q: ?Sized + Ord,
+    {
+        self.map.remove(key)
+    }
+
+    /// Modify the parent whose field is equal to `key`, and move it to its
+    /// new key afterwards
+    ///
+    /// Returns the parent that was replaced at the new key, if there was one,
+    /// or `None` if there is no parent with the field `key`.
+    pub fn modify<Q, G>(&mut self, key: &Q, f: G) -> Option<F::Parent>
+    where
+        F::Type: Borrow<Q>,
+        Q: ?Sized + Ord,
+        G: FnOnce(&mut F::Parent),
+    {
+        let mut parent = self.map.remove(key)?;
+        f(&mut parent);
+        self.insert(parent)
+    }
+
+    /// Iterate over the parents whose fields are in `range`, in order
+    pub fn range<Q, R>(
+        &self,
+        range: R,
+    ) -> impl DoubleEndedIterator<Item = &F::Parent> + '_
+    where
+        F::Type: Borrow<Q>,
+        Q: ?Sized + Ord,
+        R: RangeBounds<Q>,
+    {
+        self.map.range(range).map(|(_, parent)| parent)
+    }
+
+    /// The parent with the least value of the field
+    pub fn first(&self) -> Option<&F::Parent> {
+        self.map.values().next()
+    }
+
+    /// The parent with the greatest value of the field
+    pub fn last(&self) -> Option<&F::Parent> {
+        self.map.values().next_back()
+    }
+
+    /// Iterate over the parents, in order
+    pub fn values(&self) -> btree_map::Values<'_, F::Type, F::Parent> {
+        self.map.values()
+    }
+
+    /// Take the parents out of the index
+    pub fn into_map(self) -> BTreeMap<F::Type, F::Parent> {
+        self.map
+    }
+}
+
+impl<F: Field> Extend<F::Parent> for OrderedFieldIndex<F>
+where
+    F::Parent: Sized,
+    F::Type: Ord + Clone,
+{
+    fn extend<I: IntoIterator<Item = F::Parent>>(&mut self, parents: I) {
+        for parent in parents {
+            self.insert(parent);
+        }
+    }
+}
+
+impl<F: Field> fmt::Debug for OrderedFieldIndex<F>
+where
+    F::Parent: Sized + fmt::Debug,
+    F::Type: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.map.iter()).finish()
+    }
+}
@@ -0,0 +1,177 @@
+//! A map keyed by the fields of a parent type
+
+use crate::{FieldPath, NamedField};
+
+use core::{any::TypeId, fmt};
+use std::collections::{btree_map, BTreeMap};
+
+/// A field is identified by where it lives, its type, and its name
+type Key<P> = (usize, TypeId, FieldPath<P>);
+
+/// A map from the fields of `P` to values of type `V`
+///
+/// This attaches per-field data, like validators, formatters, or UI labels,
+/// to fields without using strings as keys. Fields are identified by their
+/// offset, type, and path, so a chain of fields is a different key than
+/// another path to the same offset.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{Field, FieldMap};
+///
+/// #[derive(Field)]
+/// struct User {
+///     name: String,
+///     age:  u8,
+/// }
+///
+/// # fn main() {
+/// let mut labels = FieldMap::new();
+/// labels.insert(User::FIELDS.name, "Full name");
+/// labels.insert(User::FIELDS.age, "Age");
+///
+/// assert_eq!(labels.get(User::FIELDS.age), Some(&"Age"));
+/// assert_eq!(labels.len(), 2);
+/// # }
+/// # }
+/// ```
+pub struct FieldMap<P, V> {
+    map: BTreeMap<Key<P>, V>,
+}
+
+impl<P, V> FieldMap<P, V> {
+    /// Create an empty map
+    pub fn new() -> Self {
+        Self {
+            map: BTreeMap::new(),
+        }
+    }
+
+    /// The number of fields in the map
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Check if there are no fields in the map
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Remove all fields from the map
+    pub fn clear(&mut self) {
+        self.map.clear()
+    }
+
+    /// Iterate over the paths of the fields and their values, ordered by
+    /// offset
+    pub fn iter(&self) -> FieldMapIter<'_, P, V> {
+        FieldMapIter {
+            iter: self.map.iter(),
+        }
+    }
+
+    fn key<F: NamedField<Parent = P>>(field: F) -> Key<P>
+    where
+        F::Type: 'static,
+    {
+        (
+            field.field_offset(),
+            TypeId::of::<F::Type>(),
+            FieldPath::of(field),
+        )
+    }
+
+    /// Set the value for `field`, and return the old value if there was one
+    pub fn insert<F: NamedField<Parent = P>>(
+        &mut self,
+        field: F,
+        value: V,
+    ) -> Option<V>
+    where
+        F::Type: 'static,
+    {
+        self.map.insert(Self::key(field), value)
+    }
+
+    /// Get the value for `field`
+    pub fn get<F: NamedField<Parent = P>>(&self, field: F) -> Option<&V>
+    where
+        F::Type: 'static,
+    {
+        self.map.get(&Self::key(field))
+    }
+
+    /// Mutably get the value for `field`
+    pub fn get_mut<F: NamedField<Parent = P>>(
+        &mut self,
+        field: F,
+    ) -> Option<&mut V>
+    where
+        F::Type: 'static,
+    {
+        self.map.get_mut(&Self::key(field))
+    }
+
+    /// Check if there is a value for `field`
+    pub fn contains<F: NamedField<Parent = P>>(&self, field: F) -> bool
+    where
+        F::Type: 'static,
+    {
+        self.map.contains_key(&Self::key(field))
+    }
+
+    /// Remove the value for `field`, and return it if there was one
+    pub fn remove<F: NamedField<Parent = P>>(&mut self, field: F) -> Option<V>
+    where
+        F::Type: 'static,
+    {
+        self.map.remove(&Self::key(field))
+    }
+}
+
+impl<P, V> Default for FieldMap<P, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P, V: Clone> Clone for FieldMap<P, V> {
+    fn clone(&self) -> Self {
+        Self {
+            map: self.map.clone(),
+        }
+    }
+}
+
+impl<P, V: fmt::Debug> fmt::Debug for FieldMap<P, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, P, V> IntoIterator for &'a FieldMap<P, V> {
+    type IntoIter = FieldMapIter<'a, P, V>;
+    type Item = (&'a FieldPath<P>, &'a V);
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the entries of a [`FieldMap`]
+pub struct FieldMapIter<'a, P, V> {
+    iter: btree_map::Iter<'a, Key<P>, V>,
+}
+
+impl<'a, P, V> Iterator for FieldMapIter<'a, P, V> {
+    type Item = (&'a FieldPath<P>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|((_, _, path), value)| (path, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
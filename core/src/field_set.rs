@@ -0,0 +1,273 @@
+//! Sets of type-erased fields which are assembled at run-time
+
+use crate::{descriptor::add_offset, project::is_overlapping, AnyField};
+
+use core::{
+    any::{Any, TypeId},
+    fmt,
+    marker::PhantomData,
+};
+use std::vec::Vec;
+
+/// Builds a [`DynFieldSet`] out of fields chosen at run-time
+///
+/// The fields are only checked for overlap once the set is built, so they
+/// can be added in any order, for example as they are read from a config
+/// file.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{Field, FieldSetBuilder};
+///
+/// #[derive(Field)]
+/// struct Foo {
+///     bar: u32,
+///     tap: String,
+/// }
+///
+/// # fn main() {
+/// let mut foo = Foo { bar: 0, tap: String::new() };
+///
+/// let set = ["tap", "bar"]
+///     .iter()
+///     .fold(FieldSetBuilder::new(), |builder, name| {
+///         builder.with(Foo::field_of(name.parse().unwrap()))
+///     })
+///     .build()
+///     .unwrap();
+///
+/// let mut fields = set.project_mut(&mut foo).into_iter();
+/// let tap = fields.next().unwrap().downcast::<String>().ok().unwrap();
+/// let bar = fields.next().unwrap().downcast::<u32>().ok().unwrap();
+///
+/// tap.push_str("tap");
+/// *bar = 10;
+///
+/// assert_eq!(foo.bar, 10);
+/// assert_eq!(foo.tap, "tap");
+/// # }
+/// # }
+/// ```
+pub struct FieldSetBuilder<P> {
+    fields: Vec<AnyField<P>>,
+}
+
+impl<P> FieldSetBuilder<P> {
+    /// Create an empty builder
+    pub fn new() -> Self {
+        Self {
+            fields: Vec::new(),
+        }
+    }
+
+    /// Add a field to the set
+    pub fn with(mut self, field: AnyField<P>) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    /// Add a field to the set
+    pub fn push(&mut self, field: AnyField<P>) {
+        self.fields.push(field)
+    }
+
+    /// Check that none of the fields overlap, and build the set
+    pub fn build(self) -> Result<DynFieldSet<P>, OverlapError> {
+        for (i, a) in self.fields.iter().enumerate() {
+            for b in &self.fields[..i] {
+                let a_range = a.descriptor().range();
+                let b_range = b.descriptor().range();
+
+                if is_overlapping(a_range, b_range) {
+                    return Err(OverlapError {
+                        first:  b.name(),
+                        second: a.name(),
+                    })
+                }
+            }
+        }
+
+        Ok(DynFieldSet {
+            fields: self.fields,
+        })
+    }
+}
+
+impl<P> Default for FieldSetBuilder<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P> Extend<AnyField<P>> for FieldSetBuilder<P> {
+    fn extend<I: IntoIterator<Item = AnyField<P>>>(&mut self, iter: I) {
+        self.fields.extend(iter)
+    }
+}
+
+/// A set of disjoint fields of `P`, which can all be projected to at once
+///
+/// Created by [`FieldSetBuilder::build`]
+pub struct DynFieldSet<P> {
+    fields: Vec<AnyField<P>>,
+}
+
+impl<P> DynFieldSet<P> {
+    /// The fields in the set, in the order that they were added
+    pub fn fields(&self) -> &[AnyField<P>] {
+        &self.fields
+    }
+
+    /// Project to all of the fields in the set, in the order that they were
+    /// added
+    pub fn project<'a>(&self, parent: &'a P) -> Vec<AnyFieldRef<'a>> {
+        let ptr = parent as *const P as *const u8;
+
+        self.fields
+            .iter()
+            .map(|field| {
+                AnyFieldRef {
+                    // Safety
+                    // * the descriptor was created from a field of `P`, so
+                    //      the offset is inside of `parent`
+                    ptr:     unsafe {
+                        add_offset(ptr, field.descriptor().offset())
+                    },
+                    name:    field.name(),
+                    type_id: field.type_id(),
+                    _mark:   PhantomData,
+                }
+            })
+            .collect()
+    }
+
+    /// Mutably project to all of the fields in the set, in the order that
+    /// they were added
+    pub fn project_mut<'a>(&self, parent: &'a mut P) -> Vec<AnyFieldMut<'a>> {
+        let ptr = parent as *mut P as *const u8;
+
+        self.fields
+            .iter()
+            .map(|field| {
+                AnyFieldMut {
+                    // Safety
+                    // * the descriptor was created from a field of `P`, so
+                    //      the offset is inside of `parent`
+                    // * the fields were checked to be disjoint when the set
+                    //      was built, so no two projections alias
+                    ptr:     unsafe {
+                        add_offset(ptr, field.descriptor().offset()) as *mut u8
+                    },
+                    name:    field.name(),
+                    type_id: field.type_id(),
+                    _mark:   PhantomData,
+                }
+            })
+            .collect()
+    }
+}
+
+impl<P> Clone for DynFieldSet<P> {
+    fn clone(&self) -> Self {
+        Self {
+            fields: self.fields.clone(),
+        }
+    }
+}
+
+impl<P> fmt::Debug for DynFieldSet<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(&self.fields).finish()
+    }
+}
+
+/// A shared reference to a type-erased field, created by
+/// [`DynFieldSet::project`]
+pub struct AnyFieldRef<'a> {
+    ptr:     *const u8,
+    name:    &'static str,
+    type_id: TypeId,
+    _mark:   PhantomData<&'a dyn Any>,
+}
+
+impl<'a> AnyFieldRef<'a> {
+    /// The name of the field
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Check if the field has the type `T`
+    pub fn is<T: 'static>(&self) -> bool {
+        self.type_id == TypeId::of::<T>()
+    }
+
+    /// Get a reference to the field, if it has the type `T`
+    pub fn downcast<T: 'static>(self) -> Result<&'a T, Self> {
+        if self.is::<T>() {
+            // Safety
+            // * `ptr` points to a field of type `T`, which is borrowed for
+            //      `'a`
+            unsafe { Ok(&*(self.ptr as *const T)) }
+        } else {
+            Err(self)
+        }
+    }
+}
+
+/// A mutable reference to a type-erased field, created by
+/// [`DynFieldSet::project_mut`]
+pub struct AnyFieldMut<'a> {
+    ptr:     *mut u8,
+    name:    &'static str,
+    type_id: TypeId,
+    _mark:   PhantomData<&'a mut dyn Any>,
+}
+
+impl<'a> AnyFieldMut<'a> {
+    /// The name of the field
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Check if the field has the type `T`
+    pub fn is<T: 'static>(&self) -> bool {
+        self.type_id == TypeId::of::<T>()
+    }
+
+    /// Get a mutable reference to the field, if it has the type `T`
+    pub fn downcast<T: 'static>(self) -> Result<&'a mut T, Self> {
+        if self.is::<T>() {
+            // Safety
+            // * `ptr` points to a field of type `T`, which is uniquely
+            //      borrowed for `'a`
+            unsafe { Ok(&mut *(self.ptr as *mut T)) }
+        } else {
+            Err(self)
+        }
+    }
+}
+
+/// The error returned when two fields of a [`FieldSetBuilder`] overlap
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OverlapError {
+    first:  &'static str,
+    second: &'static str,
+}
+
+impl OverlapError {
+    /// The names of the overlapping fields, in the order they were added
+    pub fn fields(&self) -> (&'static str, &'static str) {
+        (self.first, self.second)
+    }
+}
+
+impl fmt::Display for OverlapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`{}` overlaps `{}`", self.second, self.first)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OverlapError {
+}
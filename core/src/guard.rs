@@ -0,0 +1,116 @@
+//! Support for projecting through guard types from other crates
+
+/// A guard which can be narrowed down to a part of the value that it guards
+///
+/// Implement this for a guard type (a custom `RefCell`-like borrow, an arena
+/// handle, ...) and then use [`projectable_guard!`](crate::projectable_guard)
+/// to get a `ProjectTo` impl for it, which maps the guard to any of the
+/// fields of its target.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use core::cell::RefMut;
+/// use gfp_core::{Field, ProjectTo, ProjectableGuard};
+///
+/// struct Guard<'a, T>(RefMut<'a, T>);
+///
+/// unsafe impl<'a, P, T: 'a> ProjectableGuard<T> for Guard<'a, P> {
+///     type Target = P;
+///     type Mapped = Guard<'a, T>;
+///
+///     unsafe fn map_raw(
+///         self,
+///         project: impl FnOnce(*mut P) -> *mut T,
+///     ) -> Guard<'a, T> {
+///         Guard(RefMut::map(self.0, |parent| &mut *project(parent)))
+///     }
+/// }
+///
+/// gfp_core::projectable_guard! { impl['a, P] Guard<'a, P> }
+///
+/// #[derive(Field)]
+/// struct Foo {
+///     bar: u32,
+///     tap: u64,
+/// }
+///
+/// # fn main() {
+/// let foo = core::cell::RefCell::new(Foo { bar: 0, tap: 0 });
+///
+/// let mut tap = Guard(foo.borrow_mut()).project_to(Foo::fields().tap);
+/// *tap.0 = 10;
+/// drop(tap);
+///
+/// assert_eq!(foo.borrow().tap, 10);
+/// # }
+/// # }
+/// ```
+///
+/// # Safety
+///
+/// * `map_raw` must pass `project` a pointer to the guarded value, and the
+///   mapped guard must hand out the same kind of access to the pointer
+///   returned from `project` that `self` did to the guarded value
+/// * the guarded value must stay borrowed for as long as the mapped guard
+///   is alive
+pub unsafe trait ProjectableGuard<T>: Sized {
+    /// The type of value guarded by `Self`
+    type Target;
+
+    /// The guard of the narrowed down value
+    type Mapped;
+
+    /// Narrow down the guard to the pointer returned from `project`
+    ///
+    /// # Safety
+    ///
+    /// `project` must return a pointer to a part of the value it was given,
+    /// which lives at least as long as the value
+    unsafe fn map_raw(
+        self,
+        project: impl FnOnce(*mut Self::Target) -> *mut T,
+    ) -> Self::Mapped;
+}
+
+/// Implement `ProjectTo` for a type which implements [`ProjectableGuard`]
+///
+/// The generic parameters of the guard are listed in square brackets before
+/// the guard type, i.e. `projectable_guard! { impl['a, T] Guard<'a, T> }`,
+/// and are left out for guards without any, i.e.
+/// `projectable_guard! { impl ConfigGuard }`
+#[macro_export]
+macro_rules! projectable_guard {
+    () => {};
+    (impl[$($generics:tt)+] $guard:ty $(, $($rest:tt)*)?) => {
+        $crate::projectable_guard! { @impl [$($generics)+,] $guard }
+        $crate::projectable_guard! { $($($rest)*)? }
+    };
+    (impl $guard:ty $(, $($rest:tt)*)?) => {
+        $crate::projectable_guard! { @impl [] $guard }
+        $crate::projectable_guard! { $($($rest)*)? }
+    };
+    (@impl [$($generics:tt)*] $guard:ty) => {
+        impl<$($generics)* __F: $crate::Field> $crate::ProjectTo<__F>
+            for $guard
+        where
+            $guard: $crate::ProjectableGuard<
+                __F::Type,
+                Target = __F::Parent,
+            >,
+        {
+            type Projection = <$guard as $crate::ProjectableGuard<
+                __F::Type,
+            >>::Mapped;
+
+            #[inline]
+            fn project_to(self, field: __F) -> Self::Projection {
+                unsafe {
+                    $crate::ProjectableGuard::map_raw(self, move |ptr| {
+                        $crate::Field::project_raw_mut(&field, ptr)
+                    })
+                }
+            }
+        }
+    };
+}
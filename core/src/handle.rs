@@ -0,0 +1,174 @@
+//! Handles to a field of shared data, which don't borrow the parent
+
+use crate::Field;
+
+use core::{fmt, ops::Deref, ptr::NonNull};
+use std::sync::Arc;
+
+/// A shared handle to a `T` inside of an `Arc<P>`, which keeps the `Arc`
+/// alive
+///
+/// This is what [`ArcProjectExt::project_owned`] gives. Unlike the
+/// projection that `project_to` gives for an `Arc`, it doesn't borrow
+/// anything, so it is `'static` whenever `T` and `P` are, and `Send` and
+/// `Sync` whenever `T` is `Sync` and `Arc<P>` is `Send` and `Sync`. That
+/// makes it easy to capture in spawned tasks and callbacks.
+///
+/// [`erase`](Self::erase) forgets the type of a `Send` and `Sync` parent, so
+/// the same type of handle, `OwnedFieldHandle<T>`, can point into different
+/// parents.
+///
+/// A handle into a parent which isn't `Sync`, like one containing an `Rc`,
+/// can't be sent to another thread, even if the field itself could be.
+///
+/// ```compile_fail
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{ArcProjectExt, Field};
+/// use std::{rc::Rc, sync::Arc, thread};
+///
+/// #[derive(Field)]
+/// struct Cache {
+///     hits:  u32,
+///     owner: Rc<str>,
+/// }
+///
+/// # fn main() {
+/// let cache = Arc::new(Cache {
+///     hits:  3,
+///     owner: Rc::from("main"),
+/// });
+///
+/// let hits = cache.project_owned(Cache::FIELDS.hits);
+/// thread::spawn(move || *hits);
+/// # }
+/// # }
+/// ```
+pub struct OwnedFieldHandle<T: ?Sized, P: ?Sized = dyn Send + Sync> {
+    field: NonNull<T>,
+    owner: Arc<P>,
+}
+
+unsafe impl<T: ?Sized + Sync, P: ?Sized> Send for OwnedFieldHandle<T, P> where
+    Arc<P>: Send
+{
+}
+unsafe impl<T: ?Sized + Sync, P: ?Sized> Sync for OwnedFieldHandle<T, P> where
+    Arc<P>: Sync
+{
+}
+
+impl<T: ?Sized, P: Send + Sync + 'static> OwnedFieldHandle<T, P> {
+    /// Forget the type of the parent, so handles into different parents
+    /// have the same type
+    pub fn erase(self) -> OwnedFieldHandle<T> {
+        OwnedFieldHandle {
+            field: self.field,
+            owner: self.owner,
+        }
+    }
+}
+
+impl<T: ?Sized, P: ?Sized> OwnedFieldHandle<T, P> {
+    /// Narrow the handle down to `field` of the current target
+    pub fn project<F>(self, field: F) -> OwnedFieldHandle<F::Type, P>
+    where
+        F: Field<Parent = T>,
+    {
+        // Safety
+        // * the current target is valid for as long as the `Arc` is alive
+        let field = unsafe { field.project_raw(self.field.as_ptr()) };
+
+        OwnedFieldHandle {
+            // Safety
+            // * projecting from a valid pointer never gives a null pointer
+            field: unsafe { NonNull::new_unchecked(field as *mut F::Type) },
+            owner: self.owner,
+        }
+    }
+}
+
+impl<T: ?Sized, P: ?Sized> Deref for OwnedFieldHandle<T, P> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // Safety
+        // * the `Arc` keeps the field alive, and only gives shared access
+        unsafe { self.field.as_ref() }
+    }
+}
+
+impl<T: ?Sized, P: ?Sized> Clone for OwnedFieldHandle<T, P> {
+    fn clone(&self) -> Self {
+        Self {
+            field: self.field,
+            owner: self.owner.clone(),
+        }
+    }
+}
+
+impl<T: ?Sized + fmt::Debug, P: ?Sized> fmt::Debug for OwnedFieldHandle<T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        T::fmt(self, f)
+    }
+}
+
+/// Projection methods for `Arc<P>`, which give `'static` handles to fields
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{ArcProjectExt, Field, OwnedFieldHandle};
+/// use std::{sync::Arc, thread};
+///
+/// #[derive(Field)]
+/// struct Server {
+///     name:   String,
+///     config: Config,
+/// }
+///
+/// #[derive(Field)]
+/// struct Config {
+///     port: u16,
+/// }
+///
+/// fn spawn_listener(port: OwnedFieldHandle<u16>) -> thread::JoinHandle<u16> {
+///     thread::spawn(move || *port)
+/// }
+///
+/// # fn main() {
+/// let server = Arc::new(Server {
+///     name:   "api".to_string(),
+///     config: Config { port: 8080 },
+/// });
+///
+/// let port = Server::FIELDS.config.chain(Config::FIELDS.port);
+/// let port = server.project_owned(port).erase();
+/// assert_eq!(spawn_listener(port).join().unwrap(), 8080);
+/// # }
+/// # }
+/// ```
+pub trait ArcProjectExt<P> {
+    /// Project to `field`, and get a handle which keeps the `Arc` alive
+    fn project_owned<F>(self, field: F) -> OwnedFieldHandle<F::Type, P>
+    where
+        F: Field<Parent = P>;
+}
+
+impl<P> ArcProjectExt<P> for Arc<P> {
+    fn project_owned<F>(self, field: F) -> OwnedFieldHandle<F::Type, P>
+    where
+        F: Field<Parent = P>,
+    {
+        // Safety
+        // * the `Arc` points to a valid `P`
+        let field = unsafe { field.project_raw(&*self) };
+
+        OwnedFieldHandle {
+            // Safety
+            // * projecting from a reference never gives a null pointer
+            field: unsafe { NonNull::new_unchecked(field as *mut F::Type) },
+            owner: self,
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Fallible projections to the elements of slices
+
+use crate::TryProjectTo;
+
+/// The element at a run-time index of a slice
+///
+/// Slices are unsized, so `Index` can't be a `Field`, instead projections
+/// through it are fallible and return `None` when the index is out of bounds.
+///
+/// ```rust
+/// use gfp_core::{Index, TryProjectTo};
+///
+/// let values = [1, 2, 3];
+/// let slice: &[i32] = &values;
+///
+/// assert_eq!(slice.try_project_to(Index(1)), Some(&2));
+/// assert_eq!(slice.try_project_to(Index(3)), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Index(pub usize);
+
+impl Index {
+    /// Project a raw pointer from a slice to the element at this index,
+    /// without checking bounds
+    ///
+    /// # Safety
+    ///
+    /// * `ptr` must point to a valid allocation of `[T]`
+    /// * the index must be in bounds of the slice
+    /// * the projection is not safe to write to
+    pub unsafe fn project_raw<T>(&self, ptr: *const [T]) -> *const T {
+        ptr.cast::<T>().add(self.0)
+    }
+
+    /// Project a mutable raw pointer from a slice to the element at this
+    /// index, without checking bounds
+    ///
+    /// # Safety
+    ///
+    /// * `ptr` must point to a valid allocation of `[T]`
+    /// * the index must be in bounds of the slice
+    pub unsafe fn project_raw_mut<T>(&self, ptr: *mut [T]) -> *mut T {
+        ptr.cast::<T>().add(self.0)
+    }
+}
+
+impl<'a, T> TryProjectTo<Index> for &'a [T] {
+    type Projection = &'a T;
+
+    #[inline]
+    fn try_project_to(self, index: Index) -> Option<Self::Projection> {
+        self.get(index.0)
+    }
+}
+
+impl<'a, T> TryProjectTo<Index> for &'a mut [T] {
+    type Projection = &'a mut T;
+
+    #[inline]
+    fn try_project_to(self, index: Index) -> Option<Self::Projection> {
+        self.get_mut(index.0)
+    }
+}
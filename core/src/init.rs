@@ -0,0 +1,226 @@
+//! Initializing a value field by field, when the order isn't known statically
+
+use crate::{path, CompleteFieldLookup, Field};
+
+use core::{
+    fmt,
+    mem::{self, MaybeUninit},
+    ptr,
+};
+
+/// A `MaybeUninit<P>` which records which of its fields were written
+///
+/// This is for values which are assembled from parts that arrive in any
+/// order, i.e. from the messages of a protocol, so which fields are
+/// initialized is only known at run-time. Once every field was written,
+/// [`try_assume_init`](InitTracker::try_assume_init) gives back the `P`.
+/// When the tracker is dropped before that, only the written fields are
+/// dropped.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{Field, InitTracker};
+///
+/// #[derive(Field, Debug)]
+/// struct Handshake {
+///     version: u8,
+///     name:    String,
+/// }
+///
+/// # fn main() {
+/// let mut tracker = InitTracker::<Handshake>::new();
+///
+/// tracker.write(Handshake::FIELDS.name, "client".to_string());
+/// assert_eq!(tracker.missing().collect::<Vec<_>>(), ["version"]);
+///
+/// let mut tracker = tracker.try_assume_init().unwrap_err();
+/// tracker.write(Handshake::FIELDS.version, 2);
+///
+/// let handshake = tracker.try_assume_init().unwrap();
+/// assert_eq!(handshake.version, 2);
+/// # }
+/// # }
+/// ```
+pub struct InitTracker<P: CompleteFieldLookup> {
+    value: MaybeUninit<P>,
+    init:  u64,
+}
+
+impl<P: CompleteFieldLookup> InitTracker<P> {
+    /// Create a tracker where none of the fields are initialized
+    ///
+    /// # Panics
+    ///
+    /// If `P` has more than 64 fields
+    pub fn new() -> Self {
+        assert!(
+            P::field_names().len() <= 64,
+            "only types with at most 64 fields can be tracked"
+        );
+
+        Self {
+            value: MaybeUninit::uninit(),
+            init:  0,
+        }
+    }
+
+    /// Write `value` to `field`, and drop the old value if it was already
+    /// initialized
+    ///
+    /// # Panics
+    ///
+    /// If `field` isn't one of the fields of `P`, i.e. if it is a chain of
+    /// fields
+    pub fn write<F: Field<Parent = P>>(
+        &mut self,
+        field: F,
+        value: F::Type,
+    ) -> &mut F::Type
+    where
+        F::Type: 'static,
+    {
+        let descriptor = field.descriptor();
+        let index = path::field_index::<P, F::Type>(descriptor, self.init)
+            .expect("only the fields of the parent can be written");
+        let is_init = self.init & 1 << index != 0;
+        self.init |= 1 << index;
+
+        // Safety
+        // * `self.value` is a valid allocation of `P`, and the old value is
+        //      only read if it was initialized
+        unsafe {
+            let slot = field.project_raw_mut(self.value.as_mut_ptr());
+
+            if is_init {
+                // the new value is in place before the old one is dropped,
+                // in case dropping it panics
+                drop(ptr::replace(slot, value));
+            } else {
+                ptr::write(slot, value);
+            }
+
+            &mut *slot
+        }
+    }
+
+    /// Check if `field` was written
+    pub fn is_init<F: Field<Parent = P>>(&self, field: F) -> bool
+    where
+        F::Type: 'static,
+    {
+        self.init_index(&field).is_some()
+    }
+
+    /// Get `field`, if it was written
+    pub fn get<F: Field<Parent = P>>(&self, field: F) -> Option<&F::Type>
+    where
+        F::Type: 'static,
+    {
+        self.init_index(&field)?;
+
+        // Safety
+        // * the field was initialized
+        unsafe { Some(&*field.project_raw(self.value.as_ptr())) }
+    }
+
+    /// Mutably get `field`, if it was written
+    pub fn get_mut<F: Field<Parent = P>>(
+        &mut self,
+        field: F,
+    ) -> Option<&mut F::Type>
+    where
+        F::Type: 'static,
+    {
+        self.init_index(&field)?;
+
+        // Safety
+        // * the field was initialized
+        unsafe { Some(&mut *field.project_raw_mut(self.value.as_mut_ptr())) }
+    }
+
+    /// The names of the fields which weren't written yet, in declaration
+    /// order
+    pub fn missing(&self) -> impl Iterator<Item = &'static str> + '_ {
+        P::field_names()
+            .iter()
+            .enumerate()
+            .filter(move |&(index, _)| self.init & 1 << index == 0)
+            .map(|(_, &name)| name)
+    }
+
+    /// Check if every field was written
+    pub fn is_complete(&self) -> bool {
+        self.missing().next().is_none()
+    }
+
+    /// Get the value if every field was written, or give back the tracker
+    pub fn try_assume_init(self) -> Result<P, Self> {
+        if !self.is_complete() {
+            return Err(self)
+        }
+
+        // Safety
+        // * every field was written, and `CompleteFieldLookup` guarantees
+        //      that those are all of the fields of `P`
+        // * the tracker is forgotten, so the fields aren't dropped
+        let value = unsafe { ptr::read(self.value.as_ptr()) };
+        mem::forget(self);
+        Ok(value)
+    }
+
+    /// The index of `field` among the fields of `P`, if it was written
+    fn init_index<F: Field<Parent = P>>(&self, field: &F) -> Option<u32>
+    where
+        F::Type: 'static,
+    {
+        let descriptor = field.descriptor();
+        let index = path::field_index::<P, F::Type>(descriptor, !self.init)?;
+
+        if self.init & 1 << index != 0 {
+            Some(index)
+        } else {
+            None
+        }
+    }
+}
+
+impl<P: CompleteFieldLookup> Default for InitTracker<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P: CompleteFieldLookup> Drop for InitTracker<P> {
+    fn drop(&mut self) {
+        let parent = self.value.as_mut_ptr() as *mut u8;
+
+        for (index, name) in P::field_names().iter().enumerate() {
+            if self.init & 1 << index == 0 {
+                continue
+            }
+
+            if let Some(info) = P::lookup_field(name) {
+                // Safety
+                // * the field was written, so it is initialized
+                unsafe { info.drop_in_place(parent) }
+            }
+        }
+    }
+}
+
+impl<P: CompleteFieldLookup> fmt::Debug for InitTracker<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("InitTracker")
+            .field("missing", &Missing(self))
+            .finish()
+    }
+}
+
+struct Missing<'a, P: CompleteFieldLookup>(&'a InitTracker<P>);
+
+impl<P: CompleteFieldLookup> fmt::Debug for Missing<'_, P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.0.missing()).finish()
+    }
+}
@@ -0,0 +1,354 @@
+//! Helpers for intrusive collections, which go from a node to its link and
+//! back again
+
+use crate::{Field, ProjectTo};
+
+use core::{iter::FusedIterator, marker::PhantomData, pin::Pin, ptr::NonNull};
+
+/// Goes from a node of an intrusive collection to its link field `F`, and
+/// from a link back to its node, like the adapters of `intrusive-collections`
+///
+/// The link is found by projecting to `F`, and the node by inverse projecting
+/// from it, so the offset of the link never has to be computed by hand, and
+/// a node can have several links, each with its own adapter. The pinned
+/// methods project like `Pin` does, so a [`PinToPin`](crate::PinToPin) link
+/// stays pinned.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use core::{cell::Cell, ptr::NonNull};
+/// use gfp_core::{Adapter, Field};
+///
+/// #[derive(Default)]
+/// struct Link {
+///     next: Cell<Option<NonNull<Link>>>,
+/// }
+///
+/// #[derive(Field)]
+/// struct Job {
+///     id:   u32,
+///     link: Link,
+/// }
+///
+/// # fn main() {
+/// let adapter = Adapter::new(Job::FIELDS.link);
+/// let jobs = [1, 2, 3].map(|id| Job { id, link: Link::default() });
+///
+/// // the links are projected from pointers to whole jobs, so they can be
+/// // inverse projected back to them
+/// let links = jobs
+///     .iter()
+///     .map(|job| unsafe { adapter.link_ptr(NonNull::from(job)) })
+///     .collect::<Vec<_>>();
+///
+/// // link each job to the one before it
+/// for (job, prev) in jobs[1..].iter().zip(&links) {
+///     adapter.link(job).next.set(Some(*prev));
+/// }
+///
+/// let mut ids = Vec::new();
+/// let mut link = Some(links[2]);
+///
+/// while let Some(ptr) = link {
+///     // Safety: every link is the link of a job in `jobs`
+///     let job = unsafe { adapter.value(ptr) };
+///     ids.push(job.id);
+///     link = job.link.next.get();
+/// }
+///
+/// assert_eq!(ids, [3, 2, 1]);
+/// # }
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Adapter<F> {
+    field: F,
+}
+
+impl<F: Field> Adapter<F> {
+    /// Create an adapter for nodes linked through `field`
+    pub const fn new(field: F) -> Self {
+        Self {
+            field,
+        }
+    }
+
+    /// The link field
+    pub fn field(&self) -> &F {
+        &self.field
+    }
+
+    /// Get the link of `node`
+    #[inline]
+    pub fn link<'a>(&self, node: &'a F::Parent) -> &'a F::Type {
+        // Safety
+        // * `node` is a valid reference
+        unsafe { &*self.field.project_raw(node) }
+    }
+
+    /// Get the link of `node` mutably
+    #[inline]
+    pub fn link_mut<'a>(&self, node: &'a mut F::Parent) -> &'a mut F::Type {
+        // Safety
+        // * `node` is a valid, unique reference
+        unsafe { &mut *self.field.project_raw_mut(node) }
+    }
+
+    /// Get the link of a pinned `node`, which is pinned if `F` is a
+    /// [`PinToPin`](crate::PinToPin) field
+    #[inline]
+    pub fn link_pin<'a>(
+        &self,
+        node: Pin<&'a F::Parent>,
+    ) -> <Pin<&'a F::Parent> as ProjectTo<F>>::Projection
+    where
+        F: Copy,
+        Pin<&'a F::Parent>: ProjectTo<F>,
+    {
+        node.project_to(self.field)
+    }
+
+    /// Get the link of a pinned `node` mutably, which is pinned if `F` is a
+    /// [`PinToPin`](crate::PinToPin) field
+    #[inline]
+    pub fn link_pin_mut<'a>(
+        &self,
+        node: Pin<&'a mut F::Parent>,
+    ) -> <Pin<&'a mut F::Parent> as ProjectTo<F>>::Projection
+    where
+        F: Copy,
+        Pin<&'a mut F::Parent>: ProjectTo<F>,
+    {
+        node.project_to(self.field)
+    }
+
+    /// Get a pointer to the link of the node behind `node`
+    ///
+    /// # Safety
+    ///
+    /// `node` must point to a valid allocation of the node, see
+    /// [`Field::project_raw`]
+    #[inline]
+    pub unsafe fn link_ptr(
+        &self,
+        node: NonNull<F::Parent>,
+    ) -> NonNull<F::Type> {
+        NonNull::new_unchecked(self.field.project_raw_mut(node.as_ptr()))
+    }
+
+    /// Get a pointer to the node which contains the link behind `link`, like
+    /// `container_of` in C
+    ///
+    /// # Safety
+    ///
+    /// `link` must point to the link of a node, and have provenance over the
+    /// whole node, see [`Field::inverse_project_raw`]
+    #[inline]
+    pub unsafe fn value_ptr(&self, link: NonNull<F::Type>) -> NonNull<F::Parent>
+    where
+        F::Parent: Sized,
+    {
+        NonNull::new_unchecked(
+            self.field.inverse_project_raw_mut(link.as_ptr()),
+        )
+    }
+
+    /// Get the node which contains the link behind `link`
+    ///
+    /// # Safety
+    ///
+    /// * the same as [`value_ptr`](Self::value_ptr)
+    /// * the node must be valid, and must not be mutated for `'a`, except
+    ///   through shared mutability
+    #[inline]
+    pub unsafe fn value<'a>(&self, link: NonNull<F::Type>) -> &'a F::Parent
+    where
+        F::Parent: Sized,
+    {
+        &*self.value_ptr(link).as_ptr()
+    }
+
+    /// Get the node which contains the link behind `link` mutably
+    ///
+    /// # Safety
+    ///
+    /// * the same as [`value_ptr`](Self::value_ptr)
+    /// * the node must be valid, and must not be accessed through any other
+    ///   pointer for `'a`
+    #[inline]
+    pub unsafe fn value_mut<'a>(
+        &self,
+        link: NonNull<F::Type>,
+    ) -> &'a mut F::Parent
+    where
+        F::Parent: Sized,
+    {
+        &mut *self.value_ptr(link).as_ptr()
+    }
+
+    /// Get the pinned node which contains the link behind `link`
+    ///
+    /// # Safety
+    ///
+    /// * the same as [`value`](Self::value)
+    /// * the node must be pinned
+    #[inline]
+    pub unsafe fn value_pin<'a>(
+        &self,
+        link: NonNull<F::Type>,
+    ) -> Pin<&'a F::Parent>
+    where
+        F::Parent: Sized,
+    {
+        Pin::new_unchecked(self.value(link))
+    }
+
+    /// Get the pinned node which contains the link behind `link` mutably
+    ///
+    /// # Safety
+    ///
+    /// * the same as [`value_mut`](Self::value_mut)
+    /// * the node must be pinned
+    #[inline]
+    pub unsafe fn value_pin_mut<'a>(
+        &self,
+        link: NonNull<F::Type>,
+    ) -> Pin<&'a mut F::Parent>
+    where
+        F::Parent: Sized,
+    {
+        Pin::new_unchecked(self.value_mut(link))
+    }
+}
+
+/// An iterator over a chain of nodes, which follows the `next` field `F` of
+/// each node to the next one
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use core::ptr::NonNull;
+/// use gfp_core::{Field, NodeIter};
+///
+/// #[derive(Field)]
+/// struct Node {
+///     value: u32,
+///     next:  Option<NonNull<Node>>,
+/// }
+///
+/// # fn main() {
+/// let mut last = Node { value: 2, next: None };
+/// let first = Node { value: 1, next: Some(NonNull::from(&mut last)) };
+///
+/// // Safety: both nodes are valid, and aren't mutated while they're
+/// // iterated over
+/// let first = Some(NonNull::from(&first));
+/// let nodes = unsafe { NodeIter::new(first, Node::FIELDS.next) };
+/// assert!(nodes.map(|node| node.value).eq([1, 2]));
+/// # }
+/// # }
+/// ```
+pub struct NodeIter<'a, F: Field> {
+    next:  Option<NonNull<F::Parent>>,
+    field: F,
+    _ref:  PhantomData<&'a F::Parent>,
+}
+
+impl<'a, F> NodeIter<'a, F>
+where
+    F: Field<Type = Option<NonNull<<F as Field>::Parent>>>,
+{
+    /// Iterate over the chain of nodes which starts at `first`
+    ///
+    /// # Safety
+    ///
+    /// Every node in the chain must be valid for `'a`, and must not be
+    /// mutated for `'a`, except through shared mutability
+    pub unsafe fn new(first: Option<NonNull<F::Parent>>, field: F) -> Self {
+        Self {
+            next: first,
+            field,
+            _ref: PhantomData,
+        }
+    }
+}
+
+impl<'a, F> Iterator for NodeIter<'a, F>
+where
+    F: Field<Type = Option<NonNull<<F as Field>::Parent>>>,
+{
+    type Item = &'a F::Parent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.next?;
+
+        // Safety
+        // * the node is valid for `'a`, and isn't mutated, see `new`
+        unsafe {
+            self.next = *self.field.project_raw(node.as_ptr());
+            Some(&*node.as_ptr())
+        }
+    }
+}
+
+impl<'a, F> FusedIterator for NodeIter<'a, F> where
+    F: Field<Type = Option<NonNull<<F as Field>::Parent>>>
+{
+}
+
+/// An iterator over a chain of nodes, which follows the `next` field `F` of
+/// each node to the next one, and gives unique references to them
+///
+/// The `next` field of each node is read before the node is yielded, so the
+/// chain can be relinked while it is iterated over, and it continues with
+/// the next node of the original chain.
+pub struct NodeIterMut<'a, F: Field> {
+    next:  Option<NonNull<F::Parent>>,
+    field: F,
+    _ref:  PhantomData<&'a mut F::Parent>,
+}
+
+impl<'a, F> NodeIterMut<'a, F>
+where
+    F: Field<Type = Option<NonNull<<F as Field>::Parent>>>,
+{
+    /// Iterate over the chain of nodes which starts at `first`
+    ///
+    /// # Safety
+    ///
+    /// * every node in the chain must be valid for `'a`, and must not be
+    ///   accessed through any other pointer for `'a`
+    /// * the chain must not have a cycle, so no node is yielded twice
+    pub unsafe fn new(first: Option<NonNull<F::Parent>>, field: F) -> Self {
+        Self {
+            next: first,
+            field,
+            _ref: PhantomData,
+        }
+    }
+}
+
+impl<'a, F> Iterator for NodeIterMut<'a, F>
+where
+    F: Field<Type = Option<NonNull<<F as Field>::Parent>>>,
+{
+    type Item = &'a mut F::Parent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.next?;
+
+        // Safety
+        // * the node is valid for `'a`, it isn't accessed through another
+        //      pointer, and it is only yielded once, see `new`
+        unsafe {
+            self.next = *self.field.project_raw(node.as_ptr());
+            Some(&mut *node.as_ptr())
+        }
+    }
+}
+
+impl<'a, F> FusedIterator for NodeIterMut<'a, F> where
+    F: Field<Type = Option<NonNull<<F as Field>::Parent>>>
+{
+}
@@ -0,0 +1,405 @@
+//! Projections over every element of a slice or an iterator
+
+use crate::{Field, ProjectAll, ProjectTo};
+
+use core::{iter::Sum, slice};
+#[cfg(feature = "alloc")]
+use std::vec::Vec;
+use typsy::map::{Map, Mapped};
+
+/// An iterator over a field of every element of a slice
+///
+/// Created by [`project_iter`]
+pub struct ProjectIter<'a, F: Field>
+where
+    F::Parent: Sized,
+{
+    iter:  slice::Iter<'a, F::Parent>,
+    field: F,
+}
+
+/// An iterator over a field of every element of a mutable slice
+///
+/// Created by [`project_iter_mut`]
+pub struct ProjectIterMut<'a, F: Field>
+where
+    F::Parent: Sized,
+{
+    iter:  slice::IterMut<'a, F::Parent>,
+    field: F,
+}
+
+/// Iterate over `field` of every element of `slice`
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{project_iter, Field};
+///
+/// #[derive(Field)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// # fn main() {
+/// let points = [Point { x: 1, y: 2 }, Point { x: 3, y: 4 }];
+///
+/// let xs: i32 = project_iter(&points, Point::fields().x).sum();
+/// assert_eq!(xs, 4);
+/// # }
+/// # }
+/// ```
+pub fn project_iter<F: Field>(
+    slice: &[F::Parent],
+    field: F,
+) -> ProjectIter<'_, F>
+where
+    F::Parent: Sized,
+{
+    ProjectIter {
+        iter: slice.iter(),
+        field,
+    }
+}
+
+/// Iterate mutably over `field` of every element of `slice`
+pub fn project_iter_mut<F: Field>(
+    slice: &mut [F::Parent],
+    field: F,
+) -> ProjectIterMut<'_, F>
+where
+    F::Parent: Sized,
+{
+    ProjectIterMut {
+        iter: slice.iter_mut(),
+        field,
+    }
+}
+
+/// Clone `field` out of every parent into `buffer`
+pub fn extend_field<'a, F, I, E>(buffer: &mut E, parents: I, field: F)
+where
+    F: Field,
+    F::Parent: 'a,
+    F::Type: 'a + Clone,
+    I: IntoIterator<Item = &'a F::Parent>,
+    E: Extend<F::Type>,
+{
+    buffer.extend(
+        parents
+            .into_iter()
+            .map(|parent| parent.project_to(&field).clone()),
+    )
+}
+
+/// Clone `field` out of every parent into a `Vec`
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{collect_field, Field};
+///
+/// #[derive(Field)]
+/// struct User {
+///     id:   u32,
+///     name: String,
+/// }
+///
+/// # fn main() {
+/// let users = vec![
+///     User { id: 1, name: "a".to_string() },
+///     User { id: 2, name: "b".to_string() },
+/// ];
+///
+/// assert_eq!(collect_field(&users, User::fields().name), ["a", "b"]);
+/// # }
+/// # }
+/// ```
+#[cfg(feature = "alloc")]
+pub fn collect_field<'a, F, I>(parents: I, field: F) -> Vec<F::Type>
+where
+    F: Field,
+    F::Parent: 'a,
+    F::Type: 'a + Clone,
+    I: IntoIterator<Item = &'a F::Parent>,
+{
+    let mut buffer = Vec::new();
+    extend_field(&mut buffer, parents, field);
+    buffer
+}
+
+/// Fold `field` of every parent into an accumulator, starting from `init`
+pub fn fold_field<'a, F, I, B, G>(parents: I, field: F, init: B, mut f: G) -> B
+where
+    F: Field,
+    F::Parent: 'a,
+    F::Type: 'a,
+    I: IntoIterator<Item = &'a F::Parent>,
+    G: FnMut(B, &'a F::Type) -> B,
+{
+    parents
+        .into_iter()
+        .fold(init, |acc, parent| f(acc, parent.project_to(&field)))
+}
+
+/// Sum `field` of every parent
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{fold_field, mean_field, sum_field, Field};
+///
+/// #[derive(Field)]
+/// struct Request {
+///     bytes:   u32,
+///     latency: f32,
+/// }
+///
+/// # fn main() {
+/// let requests = [
+///     Request { bytes: 100, latency: 0.25 },
+///     Request { bytes: 300, latency: 0.75 },
+/// ];
+///
+/// let fields = Request::fields();
+/// let total: u32 = sum_field(&requests, fields.bytes);
+/// let largest = fold_field(&requests, fields.bytes, 0, |a, &b| a.max(b));
+///
+/// assert_eq!(total, 400);
+/// assert_eq!(largest, 300);
+/// assert_eq!(mean_field(&requests, fields.latency), Some(0.5));
+/// # }
+/// # }
+/// ```
+pub fn sum_field<'a, F, I, S>(parents: I, field: F) -> S
+where
+    F: Field,
+    F::Parent: 'a,
+    F::Type: 'a,
+    I: IntoIterator<Item = &'a F::Parent>,
+    S: Sum<&'a F::Type>,
+{
+    parents
+        .into_iter()
+        .map(|parent| parent.project_to(&field))
+        .sum()
+}
+
+/// The arithmetic mean of `field` of every parent, or `None` if there are no
+/// parents
+///
+/// The values are converted to `f64` before they are added up, so this works
+/// for the float types and the integer types up to 32 bits.
+pub fn mean_field<'a, F, I>(parents: I, field: F) -> Option<f64>
+where
+    F: Field,
+    F::Parent: 'a,
+    F::Type: 'a + Copy + Into<f64>,
+    I: IntoIterator<Item = &'a F::Parent>,
+{
+    let (sum, count) = fold_field(parents, field, (0.0, 0), |(sum, n), &x| {
+        (sum + x.into(), n + 1)
+    });
+
+    if count == 0 {
+        None
+    } else {
+        Some(sum / count as f64)
+    }
+}
+
+impl<'a, F: Field> Iterator for ProjectIter<'a, F>
+where
+    F::Parent: Sized,
+    F::Type: 'a,
+{
+    type Item = &'a F::Type;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let parent = self.iter.next()?;
+        Some(parent.project_to(&self.field))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, F: Field> DoubleEndedIterator for ProjectIter<'a, F>
+where
+    F::Parent: Sized,
+    F::Type: 'a,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let parent = self.iter.next_back()?;
+        Some(parent.project_to(&self.field))
+    }
+}
+
+impl<'a, F: Field> ExactSizeIterator for ProjectIter<'a, F>
+where
+    F::Parent: Sized,
+    F::Type: 'a,
+{
+}
+
+impl<'a, F: Field> Iterator for ProjectIterMut<'a, F>
+where
+    F::Parent: Sized,
+    F::Type: 'a,
+{
+    type Item = &'a mut F::Type;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let parent = self.iter.next()?;
+        Some(parent.project_to(&self.field))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, F: Field> DoubleEndedIterator for ProjectIterMut<'a, F>
+where
+    F::Parent: Sized,
+    F::Type: 'a,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let parent = self.iter.next_back()?;
+        Some(parent.project_to(&self.field))
+    }
+}
+
+impl<'a, F: Field> ExactSizeIterator for ProjectIterMut<'a, F>
+where
+    F::Parent: Sized,
+    F::Type: 'a,
+{
+}
+
+/// Projections of every parent of an iterator to a list of fields, which is
+/// like a `SELECT` over in-memory data
+///
+/// The list of fields is built like the lists for `ProjectAll`, and each
+/// parent is mapped to the list of its fields.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{Field, SelectExt};
+/// use typsy::convert::Convert;
+///
+/// #[derive(Field)]
+/// struct Row {
+///     id:    u32,
+///     name:  String,
+///     score: f32,
+/// }
+///
+/// # fn main() {
+/// let rows = vec![
+///     Row { id: 1, name: "a".to_string(), score: 0.5 },
+///     Row { id: 2, name: "b".to_string(), score: 0.9 },
+/// ];
+///
+/// let fields = Row::fields();
+/// let columns = (fields.id, fields.name).into_hlist();
+///
+/// for typsy::hlist_pat!(id, name) in rows.iter().select(columns) {
+///     println!("{}: {}", id, name);
+/// }
+///
+/// let names = rows
+///     .iter()
+///     .select_cloned((fields.name,).into_hlist())
+///     .map(|typsy::hlist_pat!(name)| name)
+///     .collect::<Vec<String>>();
+/// assert_eq!(names, ["a", "b"]);
+/// # }
+/// # }
+/// ```
+pub trait SelectExt: Iterator + Sized {
+    /// Map each parent to references to the fields in `fields`
+    fn select<L: Copy>(self, fields: L) -> Select<Self, L> {
+        Select {
+            iter: self,
+            fields,
+        }
+    }
+
+    /// Map each parent to clones of the fields in `fields`
+    fn select_cloned<L: Copy>(self, fields: L) -> SelectCloned<Self, L> {
+        SelectCloned {
+            iter: self,
+            fields,
+        }
+    }
+}
+
+impl<I: Iterator> SelectExt for I {
+}
+
+/// An iterator over references to a list of fields of every parent
+///
+/// Created by [`SelectExt::select`]
+#[derive(Clone)]
+pub struct Select<I, L> {
+    iter:   I,
+    fields: L,
+}
+
+/// An iterator over clones of a list of fields of every parent
+///
+/// Created by [`SelectExt::select_cloned`]
+#[derive(Clone)]
+pub struct SelectCloned<I, L> {
+    iter:   I,
+    fields: L,
+}
+
+/// Clones each value of a list of references, see
+/// [`SelectExt::select_cloned`]
+pub struct CloneValue;
+
+typsy::call! {
+    fn['a, T: 'a + Clone](&mut self: CloneValue, value: &'a T) -> T {
+        value.clone()
+    }
+}
+
+impl<'a, P: 'a, I, L: Copy> Iterator for Select<I, L>
+where
+    I: Iterator<Item = &'a P>,
+    &'a P: ProjectAll<P, L>,
+{
+    type Item = <&'a P as ProjectAll<P, L>>::Projection;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let parent = self.iter.next()?;
+        Some(parent.project_all(self.fields))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, P: 'a, I, L: Copy> Iterator for SelectCloned<I, L>
+where
+    I: Iterator<Item = &'a P>,
+    &'a P: ProjectAll<P, L>,
+    <&'a P as ProjectAll<P, L>>::Projection: Map<CloneValue>,
+{
+    type Item = Mapped<<&'a P as ProjectAll<P, L>>::Projection, CloneValue>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let parent = self.iter.next()?;
+        Some(parent.project_all(self.fields).map(CloneValue))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
@@ -0,0 +1,203 @@
+//! Applying JSON Patches (RFC 6902) to the fields of a parent
+
+use crate::{path, serde::FieldValue, FieldLookup, PathError};
+
+use core::fmt;
+use serde::de::DeserializeSeed;
+use serde_json::Value;
+use std::string::{String, ToString};
+
+/// Apply the operations of a JSON Patch (RFC 6902) to the fields of
+/// `parent`, in order
+///
+/// The path of each operation is a JSON Pointer, which is resolved like
+/// [`FieldPath::from_json_pointer`](crate::FieldPath::from_json_pointer),
+/// and the operation is applied if the type of the field allows it:
+///
+/// * `add` and `replace` write the value to the field, which works for the
+///   fields which [`deserialize_into`](crate::serde::deserialize_into) can
+///   replace, and a field whose type is also derived is updated field by
+///   field from an object. A struct always has all of its fields, so `add` replaces
+///   the field like `replace` does.
+/// * `remove` sets an `Option` field to `None`, other fields can't be
+///   removed
+///
+/// The other operations aren't supported.
+///
+/// # Errors
+///
+/// If an operation is malformed, or can't be applied, the error has its
+/// index. The operations are applied one at a time, so the operations
+/// before it are already applied, and the ones after it aren't.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{json_patch::apply_json_patch, Field};
+/// use serde_json::Value;
+///
+/// #[derive(Field)]
+/// struct Listing {
+///     title: String,
+///     price: Price,
+/// }
+///
+/// #[derive(Field)]
+/// struct Price {
+///     cents:    u64,
+///     discount: Option<u8>,
+/// }
+///
+/// # fn main() {
+/// let mut listing = Listing {
+///     title: "lamp".to_string(),
+///     price: Price { cents: 2500, discount: Some(10) },
+/// };
+///
+/// let patch = serde_json::from_str::<Vec<Value>>(
+///     r#"[
+///         { "op": "replace", "path": "/price/cents", "value": 2000 },
+///         { "op": "remove", "path": "/price/discount" }
+///     ]"#,
+/// );
+/// apply_json_patch(&mut listing, &patch.unwrap()).unwrap();
+///
+/// assert_eq!(listing.price.cents, 2000);
+/// assert_eq!(listing.price.discount, None);
+/// # }
+/// # }
+/// ```
+pub fn apply_json_patch<P: FieldLookup>(
+    parent: &mut P,
+    patch: &[Value],
+) -> Result<(), JsonPatchError> {
+    let parent = parent as *mut P as *mut u8;
+
+    for (index, op) in patch.iter().enumerate() {
+        // Safety
+        // * `parent` is a unique pointer to a `P`
+        unsafe { apply::<P>(parent, op) }.map_err(|kind| {
+            JsonPatchError {
+                op: index,
+                kind,
+            }
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Apply a single operation to the `P` at `parent`
+///
+/// # Safety
+///
+/// `parent` must be a unique pointer to a `P`
+unsafe fn apply<P: FieldLookup>(
+    parent: *mut u8,
+    op: &Value,
+) -> Result<(), JsonPatchErrorKind> {
+    let name = op.get("op").and_then(Value::as_str);
+    let pointer = op.get("path").and_then(Value::as_str);
+
+    let (name, pointer) = match (name, pointer) {
+        (Some(name), Some(pointer)) => (name, pointer),
+        _ => return Err(JsonPatchErrorKind::Malformed),
+    };
+
+    let resolve = || {
+        let resolved = path::resolve_json_pointer::<P>(pointer)
+            .map_err(JsonPatchErrorKind::Path)?;
+
+        Ok(FieldValue {
+            field: resolved.field,
+            // Safety
+            // * the descriptor was resolved from the fields of `P`, so it
+            //      points to a field inside of `parent`
+            ptr:   resolved.descriptor.project_raw_mut(parent),
+        })
+    };
+
+    match name {
+        "add" | "replace" => {
+            let value = op.get("value").ok_or(JsonPatchErrorKind::Malformed)?;
+
+            resolve()?
+                .deserialize(value)
+                .map_err(JsonPatchErrorKind::Value)
+        },
+        "remove" => {
+            // only an `Option` can be set to null
+            resolve()?
+                .deserialize(Value::Null)
+                .map_err(|_| JsonPatchErrorKind::NotRemovable)
+        },
+        _ => Err(JsonPatchErrorKind::Unsupported(name.to_string())),
+    }
+}
+
+/// The error returned from [`apply_json_patch`]
+#[derive(Debug)]
+pub struct JsonPatchError {
+    op:   usize,
+    kind: JsonPatchErrorKind,
+}
+
+impl JsonPatchError {
+    /// The index of the operation which failed
+    pub fn op(&self) -> usize {
+        self.op
+    }
+
+    /// Why the operation failed
+    pub fn kind(&self) -> &JsonPatchErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for JsonPatchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "operation {} of the patch failed: {}",
+            self.op, self.kind
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for JsonPatchError {
+}
+
+/// Why an operation of a JSON Patch failed, see [`JsonPatchError`]
+#[derive(Debug)]
+pub enum JsonPatchErrorKind {
+    /// The operation doesn't have an `op` and a `path`, or it doesn't have
+    /// the `value` that it needs
+    Malformed,
+    /// The operation isn't supported
+    Unsupported(String),
+    /// The path doesn't point to a field
+    Path(PathError),
+    /// The value couldn't be written to the field
+    Value(serde_json::Error),
+    /// The field isn't an `Option`, so it can't be removed
+    NotRemovable,
+}
+
+impl fmt::Display for JsonPatchErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JsonPatchErrorKind::Malformed => {
+                f.write_str("the operation is malformed")
+            },
+            JsonPatchErrorKind::Unsupported(op) => {
+                write!(f, "`{}` operations aren't supported", op)
+            },
+            JsonPatchErrorKind::Path(err) => err.fmt(f),
+            JsonPatchErrorKind::Value(err) => err.fmt(f),
+            JsonPatchErrorKind::NotRemovable => {
+                f.write_str("only `Option` fields can be removed")
+            },
+        }
+    }
+}
@@ -11,18 +11,150 @@
 #[cfg(all(not(feature = "std"), feature = "alloc"))]
 extern crate alloc as std;
 
+mod any_field;
+mod array;
+#[cfg(feature = "atomic")]
+pub mod atomic;
+mod by_field;
 mod chain;
+mod copy;
+mod dependent;
+mod descriptor;
+mod destructure;
+mod disjoint;
 mod dynamic;
+pub mod ecs;
+#[cfg(any(feature = "defmt", feature = "ufmt"))]
+mod embedded_fmt;
+#[cfg(feature = "ffi-export")]
+pub mod ffi;
+#[cfg(feature = "alloc")]
+mod field_index;
+#[cfg(feature = "alloc")]
+mod field_map;
+#[cfg(feature = "alloc")]
+mod field_set;
+mod guard;
+#[cfg(feature = "alloc")]
+mod handle;
+mod index;
+mod init;
+mod intrusive;
+mod iter;
+#[cfg(feature = "serde_json")]
+pub mod json_patch;
 #[doc(hidden)]
 pub mod macros;
+mod migrate;
+mod mirror;
+mod named;
+#[cfg(feature = "observe")]
+pub mod observe;
+mod out;
+mod overrides;
+mod owned;
+mod path;
 mod pin;
+#[cfg(feature = "pin-cell")]
+mod pin_cell;
 mod project;
+#[cfg(feature = "bevy_reflect")]
+pub mod reflect;
+#[cfg(feature = "registers")]
+pub mod registers;
+mod registry;
+mod replace;
+mod result;
+mod scoped;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "shared-memory")]
+pub mod shared;
+#[cfg(feature = "alloc")]
+mod split;
+pub mod std_fields;
+#[cfg(feature = "futures")]
+mod stream;
+mod struct_info;
+mod take;
+#[cfg(feature = "tokio")]
+mod tokio_guard;
+mod tuple;
 mod unchecked_project;
 
 #[doc(hidden)]
 pub mod type_list;
 
-pub use self::{chain::*, dynamic::Dynamic, pin::*};
+pub use self::{
+    any_field::{AnyField, UnknownField},
+    array::ArrayField,
+    by_field::{
+        binary_search_by_field, max_by_field, max_by_field_by,
+        max_by_field_key, min_by_field, min_by_field_by, min_by_field_key,
+    },
+    chain::*,
+    copy::{
+        clone_field_into, clone_fields_into, copy_field, copy_field_to_slice,
+        CloneInto,
+    },
+    dependent::BorrowDependent,
+    descriptor::{FieldDescriptor, OffsetError},
+    destructure::Destructure,
+    disjoint::Disjoint,
+    dynamic::Dynamic,
+    guard::ProjectableGuard,
+    index::Index,
+    init::InitTracker,
+    intrusive::{Adapter, NodeIter, NodeIterMut},
+    iter::{
+        extend_field, fold_field, mean_field, project_iter, project_iter_mut,
+        sum_field, CloneValue, ProjectIter, ProjectIterMut, Select,
+        SelectCloned, SelectExt,
+    },
+    migrate::{migrate_fields, Migration},
+    mirror::{Mirror, MirrorField, MirrorList},
+    named::{FieldPathDisplay, NamedField},
+    out::Out,
+    owned::{CloneStableDeref, OwnedProjection, StableDeref},
+    overrides::{
+        apply_overrides, set_field_from_str, OverrideError, OverrideErrorKind,
+    },
+    path::{
+        CompleteFieldLookup, FieldInfo, FieldLookup, FieldPath,
+        JsonPointerDisplay, PathError,
+    },
+    pin::*,
+    project::FieldExt,
+    registry::{Registry, RegistryEntry, ResolveError, ResolvedField},
+    replace::ReplaceList,
+    result::{OkThen, ProjectOk, ResultField},
+    scoped::{ScopedGuard, ScopedSet},
+    struct_info::{HasStructInfo, StructInfo},
+    take::{take_set, TakeDefault, TakeField},
+    tuple::*,
+};
+#[cfg(feature = "alloc")]
+pub use self::{
+    by_field::{dedup_by_field, sort_by_field},
+    field_index::{ordered_index_by, OrderedFieldIndex},
+    destructure::{DestructureBox, ExtractField},
+    field_map::{FieldMap, FieldMapIter},
+    field_set::{
+        AnyFieldMut, AnyFieldRef, DynFieldSet, FieldSetBuilder, OverlapError,
+    },
+    handle::{ArcProjectExt, OwnedFieldHandle},
+    iter::collect_field,
+    split::{split, Splitter},
+};
+#[cfg(feature = "std")]
+pub use self::{
+    by_field::{group_by_field, join_by_field},
+    field_index::{index_by, position_index_by, FieldIndex},
+};
+#[cfg(feature = "futures")]
+pub use self::stream::PinStreamExt;
+#[cfg(feature = "pin-cell")]
+pub use self::pin_cell::PinMutField;
 pub use gfp_derive::Field;
 
 use core::{marker::PhantomData, ops::Range};
@@ -37,7 +169,11 @@ macro_rules! ptr_project {
 
 #[doc(hidden)]
 pub mod derive {
-    pub use core::iter::{once, Once};
+    pub use core::{
+        iter::{once, Once},
+        mem::offset_of,
+    };
+    pub use typsy::hlist::{Cons, Nil};
     use core::marker::PhantomData;
 
     pub struct Invariant<T: ?Sized>(PhantomData<fn() -> *mut T>);
@@ -53,6 +189,190 @@ pub mod derive {
     }
     impl<T: ?Sized> Copy for Invariant<T> {
     }
+
+    /// Round `offset` up to the next multiple of `align`
+    pub const fn align_up(offset: usize, align: usize) -> usize {
+        offset.div_ceil(align) * align
+    }
+
+    /// The FNV-1a hash of the name of a field, which stands in for the name
+    /// in [`FieldByName`]
+    pub const fn name_hash(name: &str) -> u64 {
+        let bytes = name.as_bytes();
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            hash ^= bytes[i] as u64;
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+            i += 1;
+        }
+
+        hash
+    }
+
+    /// Finds the field of `Self` whose name hashes to `NAME`, this is used by
+    /// [`chain_type!`](crate::chain_type) to name the types of fields
+    pub trait FieldByName<const NAME: u64> {
+        type Field: crate::Field<Parent = Self>;
+
+        const FIELD: Self::Field;
+    }
+
+    type Lookup = Option<fn(&str) -> Option<crate::FieldInfo>>;
+
+    /// Finds the `FieldLookup` impl of `T`, if it has one
+    ///
+    /// `(&&LookupProbe::<T>::NEW).lookup()` resolves to `ViaLookup` if `T`
+    /// implements `FieldLookup`, and falls back to `ViaNone` otherwise.
+    pub struct LookupProbe<T>(PhantomData<T>);
+
+    impl<T> LookupProbe<T> {
+        pub const NEW: Self = Self(PhantomData);
+    }
+
+    pub trait ViaLookup {
+        fn lookup(&self) -> Lookup;
+    }
+
+    impl<T: crate::FieldLookup> ViaLookup for &LookupProbe<T> {
+        fn lookup(&self) -> Lookup {
+            Some(T::lookup_field)
+        }
+    }
+
+    pub trait ViaNone {
+        fn lookup(&self) -> Lookup;
+    }
+
+    impl<T> ViaNone for LookupProbe<T> {
+        fn lookup(&self) -> Lookup {
+            None
+        }
+    }
+
+    /// Allows a `FieldInfo` to clone its field if `T` implements `Clone`
+    ///
+    /// `(&&CloneProbe::<T>::NEW).with_clone(info)` resolves to `ViaClone` if
+    /// `T` implements `Clone`, and falls back to `ViaNoClone` otherwise.
+    pub struct CloneProbe<T>(PhantomData<T>);
+
+    impl<T> CloneProbe<T> {
+        pub const NEW: Self = Self(PhantomData);
+    }
+
+    pub trait ViaClone {
+        fn with_clone(&self, info: crate::FieldInfo) -> crate::FieldInfo;
+    }
+
+    impl<T: Clone + 'static> ViaClone for &CloneProbe<T> {
+        fn with_clone(&self, info: crate::FieldInfo) -> crate::FieldInfo {
+            info.with_clone::<T>()
+        }
+    }
+
+    pub trait ViaNoClone {
+        fn with_clone(&self, info: crate::FieldInfo) -> crate::FieldInfo;
+    }
+
+    impl<T> ViaNoClone for CloneProbe<T> {
+        fn with_clone(&self, info: crate::FieldInfo) -> crate::FieldInfo {
+            info
+        }
+    }
+
+    /// Allows a `FieldInfo` to parse its field from a string if `T`, or the
+    /// `U` in `T = Option<U>`, implements `FromStr`
+    ///
+    /// `(&&&ParseProbe::<T>::NEW).with_parse(info)` resolves to `ViaParseSome`
+    /// for an `Option<U>`, to `ViaParse` for a `T`, and falls back to
+    /// `ViaNoParse` otherwise.
+    pub struct ParseProbe<T>(PhantomData<T>);
+
+    impl<T> ParseProbe<T> {
+        pub const NEW: Self = Self(PhantomData);
+    }
+
+    pub trait ViaParseSome {
+        fn with_parse(&self, info: crate::FieldInfo) -> crate::FieldInfo;
+    }
+
+    impl<T: core::str::FromStr + 'static> ViaParseSome
+        for &&ParseProbe<Option<T>>
+    {
+        fn with_parse(&self, info: crate::FieldInfo) -> crate::FieldInfo {
+            info.with_parse_some::<T>()
+        }
+    }
+
+    pub trait ViaParse {
+        fn with_parse(&self, info: crate::FieldInfo) -> crate::FieldInfo;
+    }
+
+    impl<T: core::str::FromStr + 'static> ViaParse for &ParseProbe<T> {
+        fn with_parse(&self, info: crate::FieldInfo) -> crate::FieldInfo {
+            info.with_parse::<T>()
+        }
+    }
+
+    pub trait ViaNoParse {
+        fn with_parse(&self, info: crate::FieldInfo) -> crate::FieldInfo;
+    }
+
+    impl<T> ViaNoParse for ParseProbe<T> {
+        fn with_parse(&self, info: crate::FieldInfo) -> crate::FieldInfo {
+            info
+        }
+    }
+
+    /// Allows a `FieldInfo` to deserialize its field in place if `T`
+    /// implements `DeserializeOwned` and the `serde` feature is enabled
+    ///
+    /// `(&&DeserializeProbe::<T>::NEW).with_deserialize(info)` resolves to
+    /// `ViaDeserialize` if it applies, and falls back to `ViaNoDeserialize`
+    /// otherwise.
+    pub struct DeserializeProbe<T>(PhantomData<T>);
+
+    impl<T> DeserializeProbe<T> {
+        pub const NEW: Self = Self(PhantomData);
+    }
+
+    pub trait ViaDeserialize {
+        fn with_deserialize(&self, info: crate::FieldInfo) -> crate::FieldInfo;
+    }
+
+    #[cfg(feature = "serde")]
+    impl<T: serde::de::DeserializeOwned + 'static> ViaDeserialize
+        for &DeserializeProbe<T>
+    {
+        fn with_deserialize(&self, info: crate::FieldInfo) -> crate::FieldInfo {
+            info.with_deserialize::<T>()
+        }
+    }
+
+    pub trait ViaNoDeserialize {
+        fn with_deserialize(&self, info: crate::FieldInfo) -> crate::FieldInfo;
+    }
+
+    impl<T> ViaNoDeserialize for DeserializeProbe<T> {
+        fn with_deserialize(&self, info: crate::FieldInfo) -> crate::FieldInfo {
+            info
+        }
+    }
+
+    /// The field of the parent which contains a path of fields, this backs
+    /// [`Disjoint`](crate::Disjoint)
+    pub trait PathRoot {
+        type Root;
+    }
+
+    /// `Self` and `F` are distinct fields of the same struct
+    ///
+    /// # Safety
+    ///
+    /// `Self` and `F` must never overlap
+    pub unsafe trait Siblings<F> {
+    }
 }
 
 // Dev Note: we use `fn() -> T` so that we are covariant and non-owning in `T`,
@@ -140,6 +460,18 @@ pub trait ProjectTo<F: Field> {
     fn project_to(self, field: F) -> Self::Projection;
 }
 
+/// Projects a type to a given field, if the field is present
+///
+/// This is used for fields which can't be described by a `Field`, such as
+/// [`Index`] for slices.
+pub trait TryProjectTo<F> {
+    /// Direct access to the field
+    type Projection;
+
+    /// Project to a given field, or return `None` if it isn't present
+    fn try_project_to(self, field: F) -> Option<Self::Projection>;
+}
+
 /// Project a given `Field` onto a procedurally generated Field using an `unsafe`
 /// field projection trait. Safe usage depends entirely on the type implementing
 /// this trait.
@@ -265,6 +597,14 @@ pub trait ProjectAll<Parent, F> {
 /// }
 /// ```
 ///
+/// With the `checked-projections` feature, `project_to` and `project_all`
+/// through references, `Pin`s, `Out`s and smart pointers check in debug builds
+/// that the field lies inside of its parent and is aligned, which turns an
+/// offset bug in an impl like this one into a panic. Calling
+/// [`project_raw`](Field::project_raw) directly, or projecting raw pointers
+/// with [`UncheckedProjectTo`], isn't checked, because those parents don't
+/// have to be valid.
+///
 /// Best practice is to derive `Field` on the needed types, using the
 /// [`chain`](trait.Fields.html#method.chain) combinator to project to the
 /// fields of `Field` rather than implementing `Field` manually
@@ -300,7 +640,7 @@ pub trait ProjectAll<Parent, F> {
 ///
 ///     use gfp_core::Chain;
 ///
-///     const FOO_TO_VAL: Chain<Chain<Foo_fields::bar::<Foo>, Bar_fields::tap::<Bar>>, Tap_fields::val::<Tap>> = Chain::new(
+///     const FOO_TO_VAL: Chain<Chain<foo_fields::Bar::<Foo>, bar_fields::Tap::<Bar>>, tap_fields::Val::<Tap>> = Chain::new(
 ///         Chain::new(
 ///             Foo::FIELDS.bar,
 ///             Bar::FIELDS.tap,
@@ -311,13 +651,15 @@ pub trait ProjectAll<Parent, F> {
 /// # }
 /// ```
 pub unsafe trait Field {
-    // TODO: find a way to relax both of these bounds on `Parent` and `Type` to
-    // `?Sized` see
+    // TODO: find a way to relax the bound on `Type` to `?Sized` see
     // https://github.com/RustyYato/generic-field-projection/issues/39 for more
     // information
 
     /// Type which is generating `Field`
-    type Parent;
+    ///
+    /// This may be unsized, for example a struct with a slice as its last
+    /// field. The offset-based methods are only available for sized parents.
+    type Parent: ?Sized;
 
     /// A type representation of `Field` itself
     type Type;
@@ -340,20 +682,38 @@ pub unsafe trait Field {
     -> *mut Self::Type;
 
     /// Return range of offsets covered by the field
-    fn range(&self) -> Range<usize> {
+    fn range(&self) -> Range<usize>
+    where
+        Self::Parent: Sized,
+    {
         let offset = self.field_offset();
         offset..offset.wrapping_add(core::mem::size_of::<Self::Type>())
     }
 
     /// Create an equivalent run-time offset-based `Field`
-    fn dynamic(&self) -> Dynamic<Self::Parent, Self::Type> {
+    fn dynamic(&self) -> Dynamic<Self::Parent, Self::Type>
+    where
+        Self::Parent: Sized,
+    {
         unsafe { Dynamic::from_offset(self.field_offset()) }
     }
 
+    /// Create a type-erased description of where this `Field` lives inside of
+    /// its `Parent`
+    fn descriptor(&self) -> FieldDescriptor
+    where
+        Self::Parent: Sized,
+    {
+        FieldDescriptor::new::<Self::Parent, Self::Type>(self.field_offset())
+    }
+
     /// Return the offset of a `Field` from a base pointer of a `Parent`, the
     /// offset will always be positive, since `Field`s are derived from
     /// `Parent`s
-    fn field_offset(&self) -> usize {
+    fn field_offset(&self) -> usize
+    where
+        Self::Parent: Sized,
+    {
         use core::mem::MaybeUninit;
 
         unsafe {
@@ -370,12 +730,9 @@ pub unsafe trait Field {
             // Safety
             // * `parent_ptr` and `field_ptr` are guaranteed to be in the same
             //      allocation because of the safety requirements on `Field`
-            let offset =
-                field_ptr.cast::<u8>().offset_from(parent_ptr.cast::<u8>());
-
-            // The offset will always be positive, because fields must come
-            // after their parents
-            offset as usize
+            // * The offset will always be positive, because fields must come
+            //      after their parents
+            descriptor::offset_between(parent_ptr.cast(), field_ptr.cast())
         }
     }
 
@@ -404,11 +761,14 @@ pub unsafe trait Field {
     unsafe fn inverse_project_raw(
         &self,
         ptr: *const Self::Type,
-    ) -> *const Self::Parent {
+    ) -> *const Self::Parent
+    where
+        Self::Parent: Sized,
+    {
         // Safety
         // * `ptr` is guaranteed to be a pointer to a field of `Parent`
         // * `field_offset` is guarateed to give the correct offset of the field
-        ptr.cast::<u8>().sub(self.field_offset()).cast()
+        descriptor::sub_offset(ptr.cast(), self.field_offset()).cast()
     }
 
     /// Project a raw pointer from a `Type` to its generating `Parent`
@@ -436,11 +796,15 @@ pub unsafe trait Field {
     unsafe fn inverse_project_raw_mut(
         &self,
         ptr: *mut Self::Type,
-    ) -> *mut Self::Parent {
+    ) -> *mut Self::Parent
+    where
+        Self::Parent: Sized,
+    {
         // Safety
         // * `ptr` is guaranteed to be a pointer to a field of `Parent`
         // * `field_offset` is guarateed to give the correct offset of the field
-        ptr.cast::<u8>().sub(self.field_offset()).cast()
+        descriptor::sub_offset(ptr as *const u8, self.field_offset())
+            as *mut Self::Parent
     }
 
     /// Project a raw pointer from a `Type` to its `Parent`
@@ -452,8 +816,11 @@ pub unsafe trait Field {
     fn wrapping_project_raw(
         &self,
         ptr: *const Self::Type,
-    ) -> *const Self::Parent {
-        ptr.cast::<u8>().wrapping_add(self.field_offset()).cast()
+    ) -> *const Self::Parent
+    where
+        Self::Parent: Sized,
+    {
+        descriptor::wrapping_add_offset(ptr.cast(), self.field_offset()).cast()
     }
 
     /// Project a raw pointer from a `Type` to its `Parent`
@@ -465,8 +832,12 @@ pub unsafe trait Field {
     fn wrapping_project_raw_mut(
         &self,
         ptr: *mut Self::Type,
-    ) -> *mut Self::Parent {
-        ptr.cast::<u8>().wrapping_add(self.field_offset()).cast()
+    ) -> *mut Self::Parent
+    where
+        Self::Parent: Sized,
+    {
+        descriptor::wrapping_add_offset(ptr as *const u8, self.field_offset())
+            as *mut Self::Parent
     }
 
     /// Projects a raw pointer from a `Type` to its `Parent`
@@ -478,8 +849,11 @@ pub unsafe trait Field {
     fn wrapping_inverse_project_raw(
         &self,
         ptr: *const Self::Type,
-    ) -> *const Self::Parent {
-        ptr.cast::<u8>().wrapping_sub(self.field_offset()).cast()
+    ) -> *const Self::Parent
+    where
+        Self::Parent: Sized,
+    {
+        descriptor::wrapping_sub_offset(ptr.cast(), self.field_offset()).cast()
     }
 
     /// Projects a raw pointer from a `Type` to its `Parent`
@@ -491,8 +865,12 @@ pub unsafe trait Field {
     fn wrapping_inverse_project_raw_mut(
         &self,
         ptr: *mut Self::Type,
-    ) -> *mut Self::Parent {
-        ptr.cast::<u8>().wrapping_sub(self.field_offset()).cast()
+    ) -> *mut Self::Parent
+    where
+        Self::Parent: Sized,
+    {
+        descriptor::wrapping_sub_offset(ptr as *const u8, self.field_offset())
+            as *mut Self::Parent
     }
 
     /// Chain a projection of one `Field` with another
@@ -57,3 +57,308 @@ macro_rules! field {
         }
     };
 }
+
+/// Implement `Field` for the fields of a type that you don't own
+///
+/// This generates the same field types as `#[derive(Field)]` inside of the
+/// given module, along with a `Fields` struct holding all of them and a
+/// `FIELDS` constant.
+///
+/// ```
+/// # #![feature(raw_ref_op)]
+/// use gfp_core::ProjectTo;
+/// use std::ops::Range;
+///
+/// gfp_core::field_impl! {
+///     unsafe mod range_fields for Range<u32> {
+///         pub start: u32,
+///         pub end: u32,
+///     }
+/// }
+///
+/// let range = 1..4;
+/// assert_eq!(*range.project_to(range_fields::FIELDS.end), 4);
+/// ```
+///
+/// The macro checks that every listed field is a field of the type, and that
+/// it has the given type.
+///
+/// # Safety
+///
+/// The type must not be `#[repr(packed)]`
+#[macro_export]
+macro_rules! field_impl {
+    (
+        unsafe $vis:vis mod $module:ident for $parent:ty {
+            $($field_vis:vis $field:ident: $type:ty),* $(,)?
+        }
+    ) => {
+        #[allow(non_snake_case)]
+        $vis mod $module {
+            #[allow(unused_imports)]
+            use super::*;
+
+            $(
+                #[allow(non_camel_case_types)]
+                pub struct $field<T: ?Sized>($crate::derive::Invariant<T>);
+
+                impl<T: ?Sized> $field<T> {
+                    pub const INIT: Self = Self($crate::derive::Invariant::INIT);
+                }
+
+                impl<T: ?Sized> Clone for $field<T> {
+                    fn clone(&self) -> Self {
+                        *self
+                    }
+                }
+
+                impl<T: ?Sized> Copy for $field<T> {
+                }
+
+                unsafe impl $crate::Field for $field<$parent> {
+                    type Parent = $parent;
+                    type Type = $type;
+
+                    #[inline]
+                    unsafe fn project_raw(
+                        &self,
+                        ptr: *const Self::Parent,
+                    ) -> *const Self::Type {
+                        $crate::ptr_project!(const ptr $field)
+                    }
+
+                    #[inline]
+                    unsafe fn project_raw_mut(
+                        &self,
+                        ptr: *mut Self::Parent,
+                    ) -> *mut Self::Type {
+                        $crate::ptr_project!(mut ptr $field)
+                    }
+                }
+
+                impl $crate::NamedField for $field<$parent> {
+                    type Name = $crate::derive::Once<&'static str>;
+
+                    #[inline]
+                    fn name(&self) -> Self::Name {
+                        $crate::derive::once(stringify!($field))
+                    }
+                }
+
+                impl $crate::AutoUnpin for $field<$parent> {
+                }
+
+                impl $crate::derive::PathRoot for $field<$parent> {
+                    type Root = Self;
+                }
+            )*
+
+            $crate::field_impl!(@siblings $parent; $($field)*);
+
+            // Safety
+            // * `(*ptr).field` could go through a `Deref` impl if the parent
+            //      doesn't have the field, but a struct pattern can't, so
+            //      this ensures that every field is a real field of `$parent`
+            const _: fn($parent) = |parent| {
+                type Parent = $parent;
+                let Parent { $($field: _,)* .. } = parent;
+            };
+
+            /// All of the field types of the parent
+            #[derive(Clone, Copy)]
+            pub struct Fields {
+                $($field_vis $field: $field<$parent>,)*
+            }
+
+            /// All of the field types of the parent
+            pub const FIELDS: Fields = Fields {
+                $($field: $field::INIT,)*
+            };
+        }
+    };
+    // every pair of distinct fields are siblings, the fields are checked to be
+    // distinct fields of the parent by the struct pattern
+    (@siblings $parent:ty; $first:ident $($rest:ident)*) => {
+        $(
+            unsafe impl $crate::derive::Siblings<$rest<$parent>>
+                for $first<$parent>
+            {
+            }
+
+            unsafe impl $crate::derive::Siblings<$first<$parent>>
+                for $rest<$parent>
+            {
+            }
+        )*
+
+        $crate::field_impl!(@siblings $parent; $($rest)*);
+    };
+    (@siblings $parent:ty;) => {};
+}
+
+/// Implement `Field` for the fields of a struct of this crate, without a
+/// derive
+///
+/// `unsafe_fields! { Foo { bar: u32, tap: Tap } }` gives `Foo` the same
+/// `FIELDS` constant and `fields()` function as `#[derive(Field)]`, and its
+/// fields can be named by [`chain_type!`]. The field types are generated
+/// like [`field_impl!`], so every listed field must be a field of the struct,
+/// with the given type, or the expansion fails to compile.
+///
+/// ```
+/// # #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::ProjectTo;
+///
+/// struct Foo {
+///     bar: u32,
+///     tap: (u8, u8),
+/// }
+///
+/// gfp_core::unsafe_fields! {
+///     Foo { bar: u32, tap: (u8, u8) }
+/// }
+///
+/// # fn main() {
+/// let foo = Foo { bar: 1, tap: (2, 3) };
+/// assert_eq!(*foo.project_to(Foo::FIELDS.bar), 1);
+/// # }
+/// # }
+/// ```
+///
+/// # Safety
+///
+/// The field list must match the struct. This is the only obligation left,
+/// and the expansion checks it, since a field which isn't a field of the
+/// struct itself, or which has a different type, fails to compile. Fields
+/// which may be misaligned in a `#[repr(packed)]` parent are rejected too.
+#[macro_export]
+macro_rules! unsafe_fields {
+    ($parent:ty { $($field:ident: $type:ty),* $(,)? }) => {
+        const _: () = {
+            $crate::field_impl! {
+                unsafe mod fields for $parent {
+                    $(pub $field: $type,)*
+                }
+            }
+
+            impl $parent {
+                #[allow(dead_code)]
+                const FIELDS: fields::Fields = fields::FIELDS;
+
+                #[allow(dead_code)]
+                const fn fields() -> fields::Fields {
+                    fields::FIELDS
+                }
+            }
+
+            $(
+                impl $crate::derive::FieldByName<
+                    { $crate::derive::name_hash(stringify!($field)) },
+                > for $parent
+                {
+                    type Field = fields::$field<$parent>;
+
+                    const FIELD: Self::Field = fields::$field::INIT;
+                }
+
+                // a projection must never be misaligned, which is only
+                // possible in a `#[repr(packed)]` parent
+                assert!(
+                    ::core::mem::align_of::<$parent>()
+                        >= ::core::mem::align_of::<$type>()
+                        && $crate::derive::offset_of!($parent, $field)
+                            % ::core::mem::align_of::<$type>()
+                            == 0,
+                    concat!(
+                        "`",
+                        stringify!($field),
+                        "` may be misaligned in a `#[repr(packed)]` parent"
+                    ),
+                );
+            )*
+        };
+    };
+}
+
+/// Name the type of a chain of fields, and create a constant of that type
+///
+/// `chain_type!(FooToVal = Foo => bar.tap.val)` expands to a type alias
+/// `FooToVal` for `Chain<Chain<.., ..>, ..>`, and a constant `FooToVal` of
+/// that type, so the chain can be spelled out in API signatures and used
+/// like a unit struct.
+///
+/// ```
+/// # #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{chain_type, Field, ProjectTo};
+///
+/// #[derive(Field)]
+/// struct Foo {
+///     bar: Bar,
+/// }
+///
+/// #[derive(Field)]
+/// struct Bar {
+///     tap: (u8, u32),
+/// }
+///
+/// chain_type!(pub FooToTap = Foo => bar.tap.1);
+///
+/// fn tap(foo: &Foo, field: FooToTap) -> u32 {
+///     *foo.project_to(field)
+/// }
+///
+/// # fn main() {
+/// let foo = Foo { bar: Bar { tap: (1, 2) } };
+/// assert_eq!(tap(&foo, FooToTap), 2);
+/// # }
+/// # }
+/// ```
+///
+/// Every field along the path must be a field of a tuple, an element of an
+/// array, a field of one of the types in [`std_fields`](crate::std_fields),
+/// or a field of a struct which derives `Field` or uses
+/// [`unsafe_fields!`](crate::unsafe_fields). Fields from
+/// [`field_impl!`](crate::field_impl) can't be named, because their parents
+/// are foreign types. Two tuple or array indices can't follow each other,
+/// since `0.1` is lexed as a single number.
+#[macro_export]
+macro_rules! chain_type {
+    ($vis:vis $name:ident = $parent:ty => $first:tt $(. $rest:tt)*) => {
+        $crate::chain_type! {
+            @build $vis $name;
+            [<$parent as $crate::derive::FieldByName<
+                { $crate::derive::name_hash(stringify!($first)) },
+            >>::Field]
+            [<$parent as $crate::derive::FieldByName<
+                { $crate::derive::name_hash(stringify!($first)) },
+            >>::FIELD]
+            $($rest)*
+        }
+    };
+    (@build $vis:vis $name:ident; [$ty:ty] [$value:expr] $next:tt $($rest:tt)*) => {
+        $crate::chain_type! {
+            @build $vis $name;
+            [$crate::Chain<
+                $ty,
+                <<$ty as $crate::Field>::Type as $crate::derive::FieldByName<
+                    { $crate::derive::name_hash(stringify!($next)) },
+                >>::Field,
+            >]
+            [$crate::Chain::new(
+                $value,
+                <<$ty as $crate::Field>::Type as $crate::derive::FieldByName<
+                    { $crate::derive::name_hash(stringify!($next)) },
+                >>::FIELD,
+            )]
+            $($rest)*
+        }
+    };
+    (@build $vis:vis $name:ident; [$ty:ty] [$value:expr]) => {
+        $vis type $name = $ty;
+
+        #[allow(non_upper_case_globals)]
+        $vis const $name: $name = $value;
+    };
+}
@@ -0,0 +1,170 @@
+//! Copying the fields which two versions of a struct have in common
+
+use crate::{FieldInfo, FieldLookup};
+
+use core::{fmt, marker::PhantomData};
+
+/// Clone every field of `from` into the field of `to` with the same name and
+/// type, and report the fields which don't have a match
+///
+/// This carries the data of an old version of a struct over to a new
+/// version, i.e. when upgrading persisted state, so only the fields which
+/// were added, removed, or changed need to be handled by hand. Fields are
+/// matched by their names in [`FieldLookup`], and are only copied if their
+/// types are the same and they can be cloned, see
+/// [`FieldInfo::with_clone`]. The other fields of `to` are left untouched.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{migrate_fields, Field};
+///
+/// #[derive(Field)]
+/// struct SettingsV1 {
+///     volume: u8,
+///     theme:  String,
+///     legacy: bool,
+/// }
+///
+/// #[derive(Field)]
+/// struct SettingsV2 {
+///     volume: u8,
+///     theme:  String,
+///     scale:  f32,
+/// }
+///
+/// # fn main() {
+/// let old = SettingsV1 {
+///     volume: 7,
+///     theme:  "dark".to_string(),
+///     legacy: true,
+/// };
+/// let mut new = SettingsV2 {
+///     volume: 0,
+///     theme:  String::new(),
+///     scale:  1.0,
+/// };
+///
+/// let migration = migrate_fields(&old, &mut new);
+///
+/// assert_eq!(new.volume, 7);
+/// assert_eq!(new.theme, "dark");
+/// assert_eq!(migration.copied(), 2);
+/// assert!(migration.unmatched_from().eq(["legacy"]));
+/// assert!(migration.unmatched_to().eq(["scale"]));
+/// # }
+/// # }
+/// ```
+pub fn migrate_fields<V1: FieldLookup, V2: FieldLookup>(
+    from: &V1,
+    to: &mut V2,
+) -> Migration<V1, V2> {
+    let from = from as *const V1 as *const u8;
+    let to = to as *mut V2 as *mut u8;
+    let mut copied = 0;
+
+    for name in V2::field_names() {
+        let (to_field, from_field) = match matching::<V2, V1>(name) {
+            Some(pair) => pair,
+            None => continue,
+        };
+
+        // Safety
+        // * both fields were looked up from their parents, and have the same
+        //      type
+        // * `to` is a unique pointer to its parent
+        unsafe {
+            to_field.clone_value(
+                from_field.descriptor().project_raw(from),
+                to_field.descriptor().project_raw_mut(to),
+            );
+        }
+
+        copied += 1;
+    }
+
+    Migration {
+        copied,
+        _versions: PhantomData,
+    }
+}
+
+/// The field of `P` named `name`, and the field of `Q` that it can be cloned
+/// to or from, if there is one
+fn matching<P: FieldLookup, Q: FieldLookup>(
+    name: &str,
+) -> Option<(FieldInfo, FieldInfo)> {
+    let field = P::lookup_field(name)?;
+    let other = Q::lookup_field(name)?;
+
+    let can_clone = field.is_clone() && other.is_clone();
+
+    if field.type_id() == other.type_id() && can_clone {
+        Some((field, other))
+    } else {
+        None
+    }
+}
+
+/// The result of [`migrate_fields`] from `V1` to `V2`
+pub struct Migration<V1, V2> {
+    copied:    usize,
+    _versions: PhantomData<fn() -> (V1, V2)>,
+}
+
+impl<V1: FieldLookup, V2: FieldLookup> Migration<V1, V2> {
+    /// The number of fields which were copied
+    pub fn copied(&self) -> usize {
+        self.copied
+    }
+
+    /// The names of the fields of `V1` which weren't copied, in declaration
+    /// order
+    pub fn unmatched_from(&self) -> impl Iterator<Item = &'static str> {
+        unmatched::<V1, V2>()
+    }
+
+    /// The names of the fields of `V2` which weren't written, in declaration
+    /// order
+    pub fn unmatched_to(&self) -> impl Iterator<Item = &'static str> {
+        unmatched::<V2, V1>()
+    }
+
+    /// Check if every field of both versions was matched
+    pub fn is_complete(&self) -> bool {
+        self.unmatched_from().next().is_none()
+            && self.unmatched_to().next().is_none()
+    }
+}
+
+/// The names of the fields of `P` which don't match a field of `Q`
+fn unmatched<P: FieldLookup, Q: FieldLookup>(
+) -> impl Iterator<Item = &'static str> {
+    P::field_names()
+        .iter()
+        .copied()
+        .filter(|name| matching::<P, Q>(name).is_none())
+}
+
+impl<V1: FieldLookup, V2: FieldLookup> fmt::Debug for Migration<V1, V2> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Migration")
+            .field("copied", &self.copied)
+            .field("unmatched_from", &DebugNames(|| self.unmatched_from()))
+            .field("unmatched_to", &DebugNames(|| self.unmatched_to()))
+            .finish()
+    }
+}
+
+/// Debugs the names of fields as a list
+struct DebugNames<F>(F);
+
+impl<F, I> fmt::Debug for DebugNames<F>
+where
+    F: Fn() -> I,
+    I: Iterator<Item = &'static str>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries((self.0)()).finish()
+    }
+}
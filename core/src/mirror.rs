@@ -0,0 +1,202 @@
+//! Pairing up the fields of two different parents which hold the same data
+
+use crate::{Field, ProjectTo};
+
+use typsy::hlist::{Cons, Nil};
+
+/// A field of `A::Parent` and a field of `B::Parent` with the same type,
+/// which mirror each other
+///
+/// This pairs up the corresponding fields of types that carry the same data
+/// in different shapes, i.e. a domain model and its DTO, so they can be
+/// copied in either direction, or compared. A list of them makes a
+/// [`Mirror`].
+#[derive(Debug, Clone, Copy)]
+pub struct MirrorField<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A: Field, B: Field<Type = A::Type>> MirrorField<A, B> {
+    /// Pair up `a` and `b`
+    pub const fn new(a: A, b: B) -> Self {
+        Self {
+            a,
+            b,
+        }
+    }
+
+    /// The field of `A::Parent`
+    pub fn a(&self) -> &A {
+        &self.a
+    }
+
+    /// The field of `B::Parent`
+    pub fn b(&self) -> &B {
+        &self.b
+    }
+
+    /// Clone the field of `from` into the mirrored field of `to`
+    pub fn copy_a_to_b(&self, from: &A::Parent, to: &mut B::Parent)
+    where
+        A::Type: Clone,
+    {
+        to.project_to(&self.b).clone_from(from.project_to(&self.a))
+    }
+
+    /// Clone the field of `from` into the mirrored field of `to`
+    pub fn copy_b_to_a(&self, from: &B::Parent, to: &mut A::Parent)
+    where
+        A::Type: Clone,
+    {
+        to.project_to(&self.a).clone_from(from.project_to(&self.b))
+    }
+
+    /// Check if the fields of `a` and `b` are equal
+    pub fn is_synced(&self, a: &A::Parent, b: &B::Parent) -> bool
+    where
+        A::Type: PartialEq,
+    {
+        a.project_to(&self.a) == b.project_to(&self.b)
+    }
+}
+
+/// A set of [`MirrorField`]s between the parents `PA` and `PB`, which copies
+/// or compares all of them at once
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{Field, Mirror, MirrorField};
+/// use typsy::convert::Convert;
+///
+/// #[derive(Field)]
+/// struct User {
+///     id:       u64,
+///     name:     String,
+///     password: String,
+/// }
+///
+/// #[derive(Field, Default)]
+/// struct UserDto {
+///     user_id:      u64,
+///     display_name: String,
+/// }
+///
+/// # fn main() {
+/// let mirror = Mirror::new(
+///     (
+///         MirrorField::new(User::FIELDS.id, UserDto::FIELDS.user_id),
+///         MirrorField::new(User::FIELDS.name, UserDto::FIELDS.display_name),
+///     )
+///         .into_hlist(),
+/// );
+///
+/// let mut user = User {
+///     id:       7,
+///     name:     "ferris".to_string(),
+///     password: "hunter2".to_string(),
+/// };
+///
+/// let mut dto = UserDto::default();
+/// mirror.copy_a_to_b(&user, &mut dto);
+/// assert_eq!(dto.display_name, "ferris");
+/// assert!(mirror.is_synced(&user, &dto));
+///
+/// dto.display_name = "crab".to_string();
+/// mirror.copy_b_to_a(&dto, &mut user);
+/// assert_eq!(user.name, "crab");
+/// assert_eq!(user.password, "hunter2");
+/// # }
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Mirror<L> {
+    fields: L,
+}
+
+impl<L> Mirror<L> {
+    /// Create a mirror from a list of [`MirrorField`]s, built like the lists
+    /// for `ProjectAll`
+    pub const fn new(fields: L) -> Self {
+        Self {
+            fields,
+        }
+    }
+
+    /// The list of mirrored fields
+    pub fn fields(&self) -> &L {
+        &self.fields
+    }
+
+    /// Clone every mirrored field of `from` into `to`
+    pub fn copy_a_to_b<PA: ?Sized, PB: ?Sized>(&self, from: &PA, to: &mut PB)
+    where
+        L: MirrorList<PA, PB>,
+    {
+        self.fields.copy_a_to_b(from, to)
+    }
+
+    /// Clone every mirrored field of `from` into `to`
+    pub fn copy_b_to_a<PA: ?Sized, PB: ?Sized>(&self, from: &PB, to: &mut PA)
+    where
+        L: MirrorList<PA, PB>,
+    {
+        self.fields.copy_b_to_a(from, to)
+    }
+
+    /// Check if every mirrored field of `a` and `b` is equal
+    pub fn is_synced<PA: ?Sized, PB: ?Sized>(&self, a: &PA, b: &PB) -> bool
+    where
+        L: MirrorList<PA, PB>,
+    {
+        self.fields.is_synced(a, b)
+    }
+}
+
+/// A list of [`MirrorField`]s between `PA` and `PB`, whose types can be
+/// cloned and compared
+pub trait MirrorList<PA: ?Sized, PB: ?Sized> {
+    /// Clone every mirrored field of `from` into `to`
+    fn copy_a_to_b(&self, from: &PA, to: &mut PB);
+
+    /// Clone every mirrored field of `from` into `to`
+    fn copy_b_to_a(&self, from: &PB, to: &mut PA);
+
+    /// Check if every mirrored field of `a` and `b` is equal
+    fn is_synced(&self, a: &PA, b: &PB) -> bool;
+}
+
+impl<PA: ?Sized, PB: ?Sized> MirrorList<PA, PB> for Nil {
+    fn copy_a_to_b(&self, _: &PA, _: &mut PB) {
+    }
+
+    fn copy_b_to_a(&self, _: &PB, _: &mut PA) {
+    }
+
+    fn is_synced(&self, _: &PA, _: &PB) -> bool {
+        true
+    }
+}
+
+impl<A, B, R> MirrorList<A::Parent, B::Parent> for Cons<MirrorField<A, B>, R>
+where
+    A: Field,
+    B: Field<Type = A::Type>,
+    A::Type: Clone + PartialEq,
+    R: MirrorList<A::Parent, B::Parent>,
+{
+    fn copy_a_to_b(&self, from: &A::Parent, to: &mut B::Parent) {
+        self.value.copy_a_to_b(from, to);
+        self.rest.copy_a_to_b(from, to)
+    }
+
+    fn copy_b_to_a(&self, from: &B::Parent, to: &mut A::Parent) {
+        self.value.copy_b_to_a(from, to);
+        self.rest.copy_b_to_a(from, to)
+    }
+
+    fn is_synced(&self, a: &A::Parent, b: &B::Parent) -> bool {
+        self.value.is_synced(a, b) && self.rest.is_synced(a, b)
+    }
+}
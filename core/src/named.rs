@@ -0,0 +1,100 @@
+//! The names of fields, for diagnostics and logging
+
+use crate::Field;
+
+use core::fmt;
+
+/// A `Field` which knows the names of the fields along its path
+///
+/// Derived fields have a single name, and a `Chain` of named fields yields
+/// the names of both of its halves, outermost first.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{Field, NamedField};
+///
+/// #[derive(Field)]
+/// struct Foo {
+///     bar: Bar,
+/// }
+///
+/// #[derive(Field)]
+/// struct Bar {
+///     tap: u32,
+/// }
+///
+/// # fn main() {
+/// let path = Foo::FIELDS.bar.chain(Bar::FIELDS.tap);
+///
+/// assert!(path.name().eq(["bar", "tap"]));
+/// assert_eq!(path.display_path().to_string(), "bar.tap");
+/// # }
+/// # }
+/// ```
+pub trait NamedField: Field {
+    /// An iterator over the names of the fields along the path
+    type Name: Iterator<Item = &'static str>;
+
+    /// The names of the fields along the path, outermost first
+    fn name(&self) -> Self::Name;
+
+    /// Display the path to this field, dot-joined, i.e. `bar.tap.val`
+    fn display_path(&self) -> FieldPathDisplay<'_, Self> {
+        FieldPathDisplay {
+            field: self,
+        }
+    }
+}
+
+/// Displays the path to a field, created by [`NamedField::display_path`]
+pub struct FieldPathDisplay<'a, F: ?Sized> {
+    field: &'a F,
+}
+
+impl<F: ?Sized> Clone for FieldPathDisplay<'_, F> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<F: ?Sized> Copy for FieldPathDisplay<'_, F> {
+}
+
+impl<F: ?Sized + NamedField> fmt::Display for FieldPathDisplay<'_, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, name) in self.field.name().enumerate() {
+            if i != 0 {
+                f.write_str(".")?;
+            }
+
+            f.write_str(name)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<F: ?Sized + NamedField> fmt::Debug for FieldPathDisplay<'_, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl<F: ?Sized + NamedField> NamedField for &F {
+    type Name = F::Name;
+
+    #[inline]
+    fn name(&self) -> Self::Name {
+        F::name(self)
+    }
+}
+
+impl<F: ?Sized + NamedField> NamedField for &mut F {
+    type Name = F::Name;
+
+    #[inline]
+    fn name(&self) -> Self::Name {
+        F::name(self)
+    }
+}
@@ -0,0 +1,198 @@
+//! Observing every projection, for tracing, sanitizers, and fuzzing
+//! harnesses
+//!
+//! A [`ProjectionObserver`] set with [`set_projection_observer`] is called
+//! on every projection through a reference or a smart pointer, i.e.
+//! `ProjectTo` for `&P`, `&mut P`, `Box<P>`, `Rc<P>`, and `Arc<P>`, and
+//! through `Pin`s of those. Raw pointer projections aren't observed.
+//!
+//! ```rust
+//! #![feature(raw_ref_op)]
+//! # mod __ {
+//! use gfp_core::{
+//!     observe::{set_projection_observer, Projection, ProjectionObserver},
+//!     Field,
+//!     ProjectTo,
+//! };
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//!
+//! struct Counter(AtomicUsize);
+//!
+//! impl ProjectionObserver for Counter {
+//!     fn observe(&self, projection: &Projection) {
+//!         if projection.offset() != 0 {
+//!             self.0.fetch_add(1, Ordering::Relaxed);
+//!         }
+//!     }
+//! }
+//!
+//! static COUNTER: Counter = Counter(AtomicUsize::new(0));
+//!
+//! #[derive(Field)]
+//! struct Point {
+//!     x: u32,
+//!     y: u32,
+//! }
+//!
+//! # fn main() {
+//! set_projection_observer(&COUNTER).unwrap();
+//!
+//! let point = Point { x: 1, y: 2 };
+//! assert_eq!(*(&point).project_to(Point::FIELDS.y), 2);
+//! assert_eq!(COUNTER.0.load(Ordering::Relaxed), 1);
+//! # }
+//! # }
+//! ```
+
+use crate::Field;
+
+use core::{
+    any, fmt,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Is called on every projection, see the [module docs](self)
+///
+/// The observer is called on the thread that projects, before the
+/// projection is handed out, so it must be cheap and must not project
+/// itself.
+pub trait ProjectionObserver: Sync {
+    /// Observe a single projection
+    fn observe(&self, projection: &Projection);
+}
+
+/// A projection which is passed to a [`ProjectionObserver`]
+#[derive(Clone, Copy)]
+pub struct Projection {
+    parent:      *const u8,
+    offset:      usize,
+    path:        &'static str,
+    parent_type: &'static str,
+    field_type:  &'static str,
+}
+
+impl Projection {
+    /// The address of the parent
+    pub fn parent(&self) -> *const u8 {
+        self.parent
+    }
+
+    /// The offset of the field from the parent in bytes
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The name of the `Field` type which was projected through, which
+    /// includes the whole path for chains of fields, for diagnostics
+    pub fn path(&self) -> &'static str {
+        self.path
+    }
+
+    /// The name of the type of the parent
+    pub fn parent_type(&self) -> &'static str {
+        self.parent_type
+    }
+
+    /// The name of the type of the field
+    pub fn field_type(&self) -> &'static str {
+        self.field_type
+    }
+}
+
+// Safety
+// * the parent is only exposed as an address, it is never dereferenced
+unsafe impl Send for Projection {
+}
+unsafe impl Sync for Projection {
+}
+
+impl fmt::Debug for Projection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Projection")
+            .field("parent", &self.parent)
+            .field("offset", &self.offset)
+            .field("path", &self.path)
+            .field("parent_type", &self.parent_type)
+            .field("field_type", &self.field_type)
+            .finish()
+    }
+}
+
+const UNSET: usize = 0;
+const SETTING: usize = 1;
+const SET: usize = 2;
+
+static STATE: AtomicUsize = AtomicUsize::new(UNSET);
+static mut OBSERVER: Option<&'static dyn ProjectionObserver> = None;
+
+/// Set the observer of all projections, which can only be done once
+///
+/// # Errors
+///
+/// If an observer was already set
+pub fn set_projection_observer(
+    observer: &'static dyn ProjectionObserver,
+) -> Result<(), ObserverAlreadySet> {
+    match STATE.compare_exchange(
+        UNSET,
+        SETTING,
+        Ordering::Acquire,
+        Ordering::Relaxed,
+    ) {
+        Ok(_) => {
+            // Safety
+            // * only this thread can be here, and `OBSERVER` isn't read until
+            //      `STATE` is `SET`
+            unsafe { OBSERVER = Some(observer) }
+            STATE.store(SET, Ordering::Release);
+            Ok(())
+        },
+        Err(_) => Err(ObserverAlreadySet),
+    }
+}
+
+/// The observer of all projections, if one was set
+fn observer() -> Option<&'static dyn ProjectionObserver> {
+    if STATE.load(Ordering::Acquire) == SET {
+        // Safety
+        // * `OBSERVER` is never written after `STATE` is `SET`
+        unsafe { OBSERVER }
+    } else {
+        None
+    }
+}
+
+/// Pass the projection of `field` from `parent` to `ptr` to the observer
+#[inline]
+pub(crate) fn notify<F: Field>(
+    _field: &F,
+    parent: *const F::Parent,
+    ptr: *const F::Type,
+) {
+    if let Some(observer) = observer() {
+        let parent = parent as *const u8;
+
+        observer.observe(&Projection {
+            parent,
+            offset: (ptr as *const u8 as usize).wrapping_sub(parent as usize),
+            path: any::type_name::<F>(),
+            parent_type: any::type_name::<F::Parent>(),
+            field_type: any::type_name::<F::Type>(),
+        });
+    }
+}
+
+/// The error returned from [`set_projection_observer`] if an observer was
+/// already set
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObserverAlreadySet;
+
+impl fmt::Display for ObserverAlreadySet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a projection observer was already set")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ObserverAlreadySet {
+}
@@ -0,0 +1,109 @@
+//! A write-only pointer to uninitialized memory
+
+use core::{fmt, marker::PhantomData, mem::MaybeUninit, ptr::NonNull};
+
+/// A pointer to a `T` which must be written before it can be read
+///
+/// This is what `&mut MaybeUninit<T>` means, but unlike `MaybeUninit` it is a
+/// projection target: an `Out` to a parent projects to an `Out` to each of its
+/// fields with `ProjectTo` and `ProjectAll`. The only way to get a `&mut T`
+/// out of it is [`write`](Out::write), so a field which is handed out as an
+/// `Out` is known to be initialized by whoever gets it.
+///
+/// A `&mut MaybeUninit<P>` is turned into an `Out` with `Out::from` before
+/// projecting, as below. It can't project to an `Out` with `ProjectTo` itself,
+/// because that impl would overlap with `ProjectTo` for `&mut F::Parent`, for
+/// fields whose parent is a `MaybeUninit`, and its `ProjectAll` projects to
+/// a `&mut MaybeUninit` for each field.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use core::mem::MaybeUninit;
+/// use gfp_core::{Field, Out, ProjectTo};
+///
+/// #[derive(Field)]
+/// struct Point {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// fn origin(mut out: Out<'_, Point>) {
+///     out.reborrow().project_to(Point::FIELDS.x).write(0.0);
+///     out.project_to(Point::FIELDS.y).write(0.0);
+/// }
+///
+/// # fn main() {
+/// let mut point = MaybeUninit::uninit();
+/// origin(Out::from(&mut point));
+///
+/// let point = unsafe { point.assume_init() };
+/// assert_eq!(point.x, 0.0);
+/// # }
+/// # }
+/// ```
+pub struct Out<'a, T> {
+    ptr:   NonNull<T>,
+    _mark: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> Out<'a, T> {
+    /// Create an `Out` from a raw pointer
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be non-null, aligned, and valid for writes of `T` for `'a`,
+    /// and nothing else may access it for `'a`
+    #[inline]
+    pub unsafe fn from_raw(ptr: *mut T) -> Self {
+        Self {
+            ptr:   NonNull::new_unchecked(ptr),
+            _mark: PhantomData,
+        }
+    }
+
+    /// Initialize the pointee with `value`, and get a reference to it
+    ///
+    /// If the pointee was already initialized, the old value is overwritten
+    /// without being dropped
+    #[inline]
+    pub fn write(self, value: T) -> &'a mut T {
+        // Safety
+        // * `ptr` is valid for writes and unique for `'a`
+        unsafe {
+            self.ptr.as_ptr().write(value);
+            &mut *self.ptr.as_ptr()
+        }
+    }
+
+    /// Borrow this `Out` for a shorter lifetime, so it can be projected more
+    /// than once
+    #[inline]
+    pub fn reborrow(&mut self) -> Out<'_, T> {
+        Out {
+            ptr:   self.ptr,
+            _mark: PhantomData,
+        }
+    }
+
+    /// Get the raw pointer to the pointee, which may be uninitialized
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.ptr.as_ptr()
+    }
+}
+
+impl<'a, T> From<&'a mut MaybeUninit<T>> for Out<'a, T> {
+    #[inline]
+    fn from(slot: &'a mut MaybeUninit<T>) -> Self {
+        // Safety
+        // * the reference is valid and unique for `'a`
+        unsafe { Self::from_raw(slot.as_mut_ptr()) }
+    }
+}
+
+impl<T> fmt::Debug for Out<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Out").field(&self.ptr).finish()
+    }
+}
@@ -0,0 +1,229 @@
+//! Overriding the fields of a parent from `key=value` strings
+
+use crate::{
+    path::{self, Resolved},
+    FieldDescriptor,
+    FieldLookup,
+    PathError,
+};
+
+use core::{any, fmt};
+
+/// Parse `value` and write it to the field of `parent` at the dot-separated
+/// `path`, i.e. `server.port`
+///
+/// The path is resolved like [`FieldPath::parse`](crate::FieldPath::parse),
+/// and the value is parsed with the `FromStr` impl of the type of the field.
+/// This is supported for the fields whose type `#[derive(Field)]` found to
+/// implement `FromStr`, and for `Option`s of those, which are set to `Some`,
+/// see [`FieldInfo::with_parse`](crate::FieldInfo::with_parse).
+///
+/// # Errors
+///
+/// If the path doesn't name a field, if the field has a type which isn't
+/// supported, or if the value doesn't parse. The field is left untouched.
+pub fn set_field_from_str<P: FieldLookup>(
+    parent: &mut P,
+    path: &str,
+    value: &str,
+) -> Result<(), OverrideErrorKind> {
+    let resolved = path::resolve(
+        path.split('.'),
+        P::lookup_field,
+        any::type_name::<P>(),
+        FieldDescriptor::identity::<P>(),
+    )
+    .map_err(OverrideErrorKind::Path)?;
+
+    // Safety
+    // * the path was resolved from the fields of `P`, and `parent` is a
+    //      unique pointer to a `P`
+    unsafe { write_parsed(&resolved, parent as *mut P as *mut u8, value) }
+}
+
+/// Apply each override of the form `key=value` to the fields of `parent`, in
+/// order, i.e. from `--set server.port=8080` flags or environment variables
+///
+/// Each key is a dot-separated path, and each value is split off at the
+/// first `=`, so the value may contain more of them. See
+/// [`set_field_from_str`] for the types which can be overridden.
+///
+/// # Errors
+///
+/// If an override is malformed, or can't be applied, the error has its
+/// index. The overrides before it are already applied, and the ones after it
+/// aren't.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{apply_overrides, Field};
+///
+/// #[derive(Field)]
+/// struct Config {
+///     name:   String,
+///     server: Server,
+/// }
+///
+/// #[derive(Field)]
+/// struct Server {
+///     port:    u16,
+///     verbose: bool,
+/// }
+///
+/// # fn main() {
+/// let mut config = Config {
+///     name:   "api".to_string(),
+///     server: Server { port: 80, verbose: false },
+/// };
+///
+/// apply_overrides(&mut config, &["server.port=8080", "name=a=b"]).unwrap();
+/// assert_eq!(config.server.port, 8080);
+/// assert_eq!(config.name, "a=b");
+///
+/// let err = apply_overrides(&mut config, &["server.verbose=yes"]);
+/// assert_eq!(err.unwrap_err().index(), 0);
+/// assert!(!config.server.verbose);
+/// # }
+/// # }
+/// ```
+pub fn apply_overrides<P, I>(
+    parent: &mut P,
+    overrides: I,
+) -> Result<(), OverrideError>
+where
+    P: FieldLookup,
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    for (index, assignment) in overrides.into_iter().enumerate() {
+        let assignment = assignment.as_ref();
+
+        let result = match assignment.find('=') {
+            Some(eq) => {
+                set_field_from_str(
+                    parent,
+                    &assignment[..eq],
+                    &assignment[eq + 1..],
+                )
+            },
+            None => Err(OverrideErrorKind::Malformed),
+        };
+
+        result.map_err(|kind| {
+            OverrideError {
+                index,
+                kind,
+            }
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Parse `value` as the type of the resolved field, and write it to the
+/// field inside of `parent`
+///
+/// # Safety
+///
+/// `resolved` must be resolved from the fields of the parent, and `parent`
+/// must be a unique pointer to the parent
+unsafe fn write_parsed(
+    resolved: &Resolved,
+    parent: *mut u8,
+    value: &str,
+) -> Result<(), OverrideErrorKind> {
+    let type_name = resolved.field.type_name();
+    let ptr: *mut u8 = resolved.descriptor.project_raw_mut(parent);
+
+    let parse = match resolved.field.parse() {
+        Some(parse) => parse,
+        None => {
+            return Err(OverrideErrorKind::Unsupported {
+                type_name,
+            })
+        },
+    };
+
+    // Safety
+    // * `ptr` is a unique pointer to the initialized field, and `parse` was
+    //      recorded for the type of the field
+    if parse(value, ptr) {
+        Ok(())
+    } else {
+        Err(OverrideErrorKind::InvalidValue {
+            type_name,
+        })
+    }
+}
+
+/// The error returned from [`apply_overrides`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverrideError {
+    index: usize,
+    kind:  OverrideErrorKind,
+}
+
+impl OverrideError {
+    /// The index of the override which failed
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Why the override failed
+    pub fn kind(&self) -> &OverrideErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for OverrideError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "override {} failed: {}", self.index, self.kind)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OverrideError {
+}
+
+/// Why an override failed, see [`OverrideError`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OverrideErrorKind {
+    /// The override isn't of the form `key=value`
+    Malformed,
+    /// The key doesn't name a field
+    Path(PathError),
+    /// The type of the field can't be parsed from a string
+    Unsupported {
+        /// The name of the type of the field
+        type_name: &'static str,
+    },
+    /// The value isn't valid for the type of the field
+    InvalidValue {
+        /// The name of the type of the field
+        type_name: &'static str,
+    },
+}
+
+impl fmt::Display for OverrideErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OverrideErrorKind::Malformed => {
+                f.write_str("overrides must be of the form `key=value`")
+            },
+            OverrideErrorKind::Path(err) => err.fmt(f),
+            OverrideErrorKind::Unsupported {
+                type_name,
+            } => {
+                write!(f, "fields of type `{}` can't be overridden", type_name)
+            },
+            OverrideErrorKind::InvalidValue {
+                type_name,
+            } => write!(f, "the value isn't a valid `{}`", type_name),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OverrideErrorKind {
+}
@@ -0,0 +1,189 @@
+//! Projections which own the container that they point into
+
+use crate::Field;
+
+use core::{fmt, ops::Deref, ptr::NonNull};
+#[cfg(feature = "alloc")]
+use std::{rc::Rc, sync::Arc};
+
+/// A container whose target stays at the same address when the container is
+/// moved, like `Rc` or `Arc`
+///
+/// `Box`, `Vec`, `String` and `&mut T` aren't stable in this sense, because
+/// moving them asserts unique access to their target, which invalidates any
+/// pointer derived from an earlier borrow of it.
+///
+/// # Safety
+///
+/// `deref` must always return the same address while the container isn't
+/// mutated, and pointers derived from that address must stay valid when the
+/// container is moved
+pub unsafe trait StableDeref: Deref {
+}
+
+/// A [`StableDeref`] container whose clones deref to the same address, like
+/// `Arc` or `Rc`
+///
+/// # Safety
+///
+/// The clones of the container must deref to the same address as the
+/// container
+pub unsafe trait CloneStableDeref: StableDeref + Clone {
+}
+
+unsafe impl<T: ?Sized> StableDeref for &T {
+}
+unsafe impl<T: ?Sized> CloneStableDeref for &T {
+}
+
+#[cfg(feature = "alloc")]
+unsafe impl<T: ?Sized> StableDeref for Rc<T> {
+}
+#[cfg(feature = "alloc")]
+unsafe impl<T: ?Sized> CloneStableDeref for Rc<T> {
+}
+#[cfg(feature = "alloc")]
+unsafe impl<T: ?Sized> StableDeref for Arc<T> {
+}
+#[cfg(feature = "alloc")]
+unsafe impl<T: ?Sized> CloneStableDeref for Arc<T> {
+}
+
+/// A shared projection to a `T` inside of the container `O`, which owns the
+/// container
+///
+/// This is a self-contained value, like the `OwningRef` of `owning_ref` or a
+/// `Yoke`, which is `'static` if the container is, so it can be stored or
+/// sent without borrowing from anything. It works for any [`StableDeref`]
+/// container, where `project_to` on `Arc` only works for that.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{Field, OwnedProjection};
+/// use std::sync::Arc;
+///
+/// #[derive(Field)]
+/// struct Config {
+///     name:   String,
+///     limits: Limits,
+/// }
+///
+/// #[derive(Field)]
+/// struct Limits {
+///     memory: u64,
+/// }
+///
+/// /// Only the limits are handed out, but they keep the whole config alive
+/// fn limits(config: Arc<Config>) -> OwnedProjection<Arc<Config>, Limits> {
+///     OwnedProjection::new(config, Config::FIELDS.limits)
+/// }
+///
+/// # fn main() {
+/// let config = Arc::new(Config {
+///     name:   "api".to_string(),
+///     limits: Limits { memory: 512 },
+/// });
+///
+/// let limits = limits(config);
+/// assert_eq!(limits.memory, 512);
+///
+/// let memory = limits.project(Limits::FIELDS.memory);
+/// assert_eq!(*memory, 512);
+/// assert_eq!(memory.owner().name, "api");
+/// # }
+/// # }
+/// ```
+pub struct OwnedProjection<O, T: ?Sized> {
+    field: NonNull<T>,
+    owner: O,
+}
+
+unsafe impl<O: Send, T: ?Sized + Sync> Send for OwnedProjection<O, T> {
+}
+unsafe impl<O: Sync, T: ?Sized + Sync> Sync for OwnedProjection<O, T> {
+}
+
+impl<O: StableDeref> OwnedProjection<O, O::Target> {
+    /// Own `owner`, and point to all of its target
+    pub fn from_owner(owner: O) -> Self {
+        Self {
+            field: NonNull::from(&*owner),
+            owner,
+        }
+    }
+}
+
+impl<O: StableDeref, T: ?Sized> OwnedProjection<O, T> {
+    /// Own `owner`, and project to `field` of its target
+    pub fn new<F>(owner: O, field: F) -> Self
+    where
+        F: Field<Parent = O::Target, Type = T>,
+    {
+        // Safety
+        // * the target of `owner` is a valid `F::Parent`
+        let field = unsafe { field.project_raw(&*owner) };
+
+        Self {
+            // Safety
+            // * projecting from a reference never gives a null pointer
+            field: unsafe { NonNull::new_unchecked(field as *mut T) },
+            owner,
+        }
+    }
+
+    /// Project further, to `field` of the current target
+    pub fn project<F>(self, field: F) -> OwnedProjection<O, F::Type>
+    where
+        F: Field<Parent = T>,
+    {
+        // Safety
+        // * the current target is valid for as long as the owner is alive
+        let field = unsafe { field.project_raw(self.field.as_ptr()) };
+
+        OwnedProjection {
+            // Safety
+            // * projecting from a valid pointer never gives a null pointer
+            field: unsafe { NonNull::new_unchecked(field as *mut F::Type) },
+            owner: self.owner,
+        }
+    }
+
+    /// The target of the container which is owned
+    pub fn owner(&self) -> &O::Target {
+        &self.owner
+    }
+
+    /// Give up the projection, and get back the container
+    pub fn into_owner(self) -> O {
+        self.owner
+    }
+}
+
+impl<O: StableDeref, T: ?Sized> Deref for OwnedProjection<O, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // Safety
+        // * the owner keeps the target alive, and it isn't moved, because the
+        //      owner is `StableDeref`
+        unsafe { self.field.as_ref() }
+    }
+}
+
+impl<O: CloneStableDeref, T: ?Sized> Clone for OwnedProjection<O, T> {
+    fn clone(&self) -> Self {
+        Self {
+            field: self.field,
+            owner: self.owner.clone(),
+        }
+    }
+}
+
+impl<O: StableDeref, T: ?Sized + fmt::Debug> fmt::Debug
+    for OwnedProjection<O, T>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        T::fmt(self, f)
+    }
+}
@@ -0,0 +1,942 @@
+//! Looking up paths of fields by name at run-time
+
+use crate::{derive::Invariant, Dynamic, Field, FieldDescriptor, NamedField};
+
+use core::{
+    any::{self, TypeId},
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    ptr,
+    str::FromStr,
+};
+
+/// A type whose fields can be looked up by name at run-time
+///
+/// This is implemented by `#[derive(Field)]` for structs, and is what
+/// [`FieldPath`] uses to resolve each segment of a path. Paths can only
+/// continue into fields whose type is known to implement `FieldLookup` where
+/// the parent is derived, so a field of a generic type `T` always ends the
+/// path.
+pub trait FieldLookup: 'static {
+    /// Look up the field named `name`, if there is one
+    fn lookup_field(name: &str) -> Option<FieldInfo>;
+
+    /// The names of all of the fields, in declaration order
+    fn field_names() -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Look up the field with the tag `tag`, if there is one
+    ///
+    /// Tags are stable numbers for fields, like the field numbers of
+    /// protobuf, which are given with `#[field(tag = 3)]` when deriving. The
+    /// default searches the fields listed in `field_names`.
+    ///
+    /// ```rust
+    /// #![feature(raw_ref_op)]
+    /// # mod __ {
+    /// use gfp_core::{Field, FieldLookup};
+    ///
+    /// #[derive(Field)]
+    /// struct Person {
+    ///     #[field(tag = 1)]
+    ///     name:  String,
+    ///     #[field(tag = 3)]
+    ///     email: String,
+    ///     cache: Vec<u8>,
+    /// }
+    ///
+    /// # fn main() {
+    /// assert_eq!(Person::field_by_tag(3).unwrap().name(), "email");
+    /// assert!(Person::field_by_tag(2).is_none());
+    ///
+    /// assert_eq!(Person::lookup_field("name").unwrap().tag(), Some(1));
+    /// assert_eq!(Person::lookup_field("cache").unwrap().tag(), None);
+    /// # }
+    /// # }
+    /// ```
+    fn field_by_tag(tag: u32) -> Option<FieldInfo> {
+        Self::field_names()
+            .iter()
+            .filter_map(|name| Self::lookup_field(name))
+            .find(|field| field.tag == Some(tag))
+    }
+}
+
+/// A [`FieldLookup`] which lists every field of the type
+///
+/// This is implemented by `#[derive(Field)]` alongside `FieldLookup`, and
+/// lets code like [`InitTracker`](crate::InitTracker) rely on the fields
+/// being complete.
+///
+/// # Safety
+///
+/// `field_names` must list every field of `Self` exactly once, and
+/// `lookup_field` must return the field for each of those names
+pub unsafe trait CompleteFieldLookup: FieldLookup {
+}
+
+/// The index in declaration order of the field of type `T` of `P` described
+/// by `descriptor`, preferring fields whose bit isn't set in `used`
+///
+/// Zero-sized fields of the same type may share a descriptor, so those are
+/// told apart by which of them are used.
+pub(crate) fn field_index<P: FieldLookup, T: 'static>(
+    descriptor: FieldDescriptor,
+    used: u64,
+) -> Option<u32> {
+    let mut found = None;
+
+    for (index, name) in P::field_names().iter().enumerate() {
+        let info = match P::lookup_field(name) {
+            Some(info) => info,
+            None => continue,
+        };
+
+        if info.descriptor == descriptor && info.type_id == TypeId::of::<T>() {
+            let index = index as u32;
+
+            if used & 1 << index == 0 {
+                return Some(index)
+            }
+
+            found = found.or(Some(index));
+        }
+    }
+
+    found
+}
+
+/// The run-time description of a single field, as returned from
+/// [`FieldLookup::lookup_field`]
+#[derive(Clone, Copy)]
+pub struct FieldInfo {
+    name:        &'static str,
+    descriptor:  FieldDescriptor,
+    type_id:     TypeId,
+    type_name:   &'static str,
+    lookup:      Option<fn(&str) -> Option<FieldInfo>>,
+    tag:         Option<u32>,
+    display:     Option<&'static str>,
+    meta:        &'static [(&'static str, &'static str)],
+    clone:       Option<unsafe fn(*const u8, *mut u8)>,
+    parse:       Option<unsafe fn(&str, *mut u8) -> bool>,
+    #[cfg(feature = "serde")]
+    deserialize: Option<DeserializeInPlace>,
+    drop:        unsafe fn(*mut u8),
+}
+
+/// Replaces the value at the pointer with one read from the deserializer
+#[cfg(feature = "serde")]
+pub(crate) type DeserializeInPlace =
+    unsafe fn(
+        &mut dyn erased_serde::Deserializer<'_>,
+        *mut u8,
+    ) -> Result<(), erased_serde::Error>;
+
+impl FieldInfo {
+    /// Describe `field`, which is named `name`
+    ///
+    /// `lookup` is used to resolve the fields of the field's type, if its
+    /// type implements `FieldLookup` it should be
+    /// `Some(<F::Type as FieldLookup>::lookup_field)`
+    pub fn new<F: Field>(
+        name: &'static str,
+        field: F,
+        lookup: Option<fn(&str) -> Option<FieldInfo>>,
+    ) -> Self
+    where
+        F::Parent: Sized,
+        F::Type: 'static,
+    {
+        Self {
+            name,
+            descriptor: field.descriptor(),
+            type_id: TypeId::of::<F::Type>(),
+            type_name: any::type_name::<F::Type>(),
+            lookup,
+            tag: None,
+            display: None,
+            meta: &[],
+            clone: None,
+            parse: None,
+            #[cfg(feature = "serde")]
+            deserialize: None,
+            drop: drop_erased::<F::Type>,
+        }
+    }
+
+    /// Give the field the tag `tag`, see [`FieldLookup::field_by_tag`]
+    pub fn with_tag(self, tag: u32) -> Self {
+        Self {
+            tag: Some(tag),
+            ..self
+        }
+    }
+
+    /// Give the field the human-readable label `display`, see
+    /// [`FieldInfo::display_name`]
+    pub fn with_display(self, display: &'static str) -> Self {
+        Self {
+            display: Some(display),
+            ..self
+        }
+    }
+
+    /// Attach the metadata `meta` to the field, as pairs of keys and values,
+    /// see [`FieldInfo::meta`]
+    pub fn with_meta(
+        self,
+        meta: &'static [(&'static str, &'static str)],
+    ) -> Self {
+        Self {
+            meta,
+            ..self
+        }
+    }
+
+    /// Allow the field to be cloned between parents, i.e. by
+    /// [`migrate_fields`](crate::migrate_fields)
+    ///
+    /// `#[derive(Field)]` does this for every field whose type is known to
+    /// implement `Clone`.
+    ///
+    /// # Panics
+    ///
+    /// If `T` isn't the type of the field
+    pub fn with_clone<T: Clone + 'static>(self) -> Self {
+        assert!(
+            self.type_id == TypeId::of::<T>(),
+            "`{}` doesn't have the type `{}`",
+            self.name,
+            any::type_name::<T>()
+        );
+
+        Self {
+            clone: Some(clone_erased::<T>),
+            ..self
+        }
+    }
+
+    /// Allow the field to be parsed from a string, i.e. by
+    /// [`apply_overrides`](crate::apply_overrides)
+    ///
+    /// `#[derive(Field)]` does this for every field whose type is known to
+    /// implement `FromStr`, and for every `Option` of such a type, see
+    /// [`FieldInfo::with_parse_some`].
+    ///
+    /// # Panics
+    ///
+    /// If `T` isn't the type of the field
+    pub fn with_parse<T: FromStr + 'static>(self) -> Self {
+        assert!(
+            self.type_id == TypeId::of::<T>(),
+            "`{}` doesn't have the type `{}`",
+            self.name,
+            any::type_name::<T>()
+        );
+
+        Self {
+            parse: Some(parse_erased::<T>),
+            ..self
+        }
+    }
+
+    /// Allow the field, an `Option<T>`, to be set to `Some` value parsed from
+    /// a string, see [`FieldInfo::with_parse`]
+    ///
+    /// # Panics
+    ///
+    /// If `Option<T>` isn't the type of the field
+    pub fn with_parse_some<T: FromStr + 'static>(self) -> Self {
+        assert!(
+            self.type_id == TypeId::of::<Option<T>>(),
+            "`{}` doesn't have the type `{}`",
+            self.name,
+            any::type_name::<Option<T>>()
+        );
+
+        Self {
+            parse: Some(parse_some_erased::<T>),
+            ..self
+        }
+    }
+
+    /// Allow the field to be deserialized in place, i.e. by
+    /// [`deserialize_into`](crate::serde::deserialize_into)
+    ///
+    /// `#[derive(Field)]` does this for every field whose type is known to
+    /// implement `DeserializeOwned`.
+    ///
+    /// # Panics
+    ///
+    /// If `T` isn't the type of the field
+    #[cfg(feature = "serde")]
+    pub fn with_deserialize<T: serde::de::DeserializeOwned + 'static>(
+        self,
+    ) -> Self {
+        assert!(
+            self.type_id == TypeId::of::<T>(),
+            "`{}` doesn't have the type `{}`",
+            self.name,
+            any::type_name::<T>()
+        );
+
+        Self {
+            deserialize: Some(deserialize_erased::<T>),
+            ..self
+        }
+    }
+
+    /// The name of the field
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Where the field lives inside of its parent
+    pub fn descriptor(&self) -> FieldDescriptor {
+        self.descriptor
+    }
+
+    /// The `TypeId` of the type of the field
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// The name of the type of the field, for diagnostics
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// The tag of the field, if it has one
+    pub fn tag(&self) -> Option<u32> {
+        self.tag
+    }
+
+    /// The human-readable label of the field, or its name if it doesn't have
+    /// one
+    pub fn display_name(&self) -> &'static str {
+        self.display.unwrap_or(self.name)
+    }
+
+    /// The value of the metadata `key` of the field, if it has one
+    ///
+    /// `#[derive(Field)]` gives keys without a value, i.e. `sensitive` in
+    /// `#[field(meta(unit = "ms", sensitive))]`, the value `""`.
+    pub fn meta(&self, key: &str) -> Option<&'static str> {
+        self.meta
+            .iter()
+            .find(|(other, _)| *other == key)
+            .map(|(_, value)| *value)
+    }
+
+    /// All of the metadata of the field, as pairs of keys and values
+    pub fn meta_entries(&self) -> &'static [(&'static str, &'static str)] {
+        self.meta
+    }
+
+    /// Check if the field can be cloned, see [`FieldInfo::with_clone`]
+    pub fn is_clone(&self) -> bool {
+        self.clone.is_some()
+    }
+
+    /// Parses a string into the field, see [`FieldInfo::with_parse`]
+    pub(crate) fn parse(&self) -> Option<unsafe fn(&str, *mut u8) -> bool> {
+        self.parse
+    }
+
+    /// The `FieldLookup` of the type of the field, if it has one
+    #[cfg(feature = "serde")]
+    pub(crate) fn lookup(&self) -> Option<fn(&str) -> Option<FieldInfo>> {
+        self.lookup
+    }
+
+    /// Deserializes the field in place, see [`FieldInfo::with_deserialize`]
+    #[cfg(feature = "serde")]
+    pub(crate) fn deserialize(&self) -> Option<DeserializeInPlace> {
+        self.deserialize
+    }
+
+    /// Drop the field in place
+    ///
+    /// # Safety
+    ///
+    /// * `parent` must point to a valid allocation of the parent
+    /// * the field must be initialized, and must not be used afterwards
+    pub(crate) unsafe fn drop_in_place(&self, parent: *mut u8) {
+        (self.drop)(self.descriptor.project_raw_mut(parent))
+    }
+
+    /// Clone the value of the field at `from` into the value at `to`, and
+    /// return `false` if the field can't be cloned
+    ///
+    /// # Safety
+    ///
+    /// `from` and `to` must point to initialized values of the type of the
+    /// field, and `to` must be unique
+    pub(crate) unsafe fn clone_value(
+        &self,
+        from: *const u8,
+        to: *mut u8,
+    ) -> bool {
+        match self.clone {
+            Some(clone) => {
+                clone(from, to);
+                true
+            },
+            None => false,
+        }
+    }
+}
+
+/// Drop the `T` at `ptr`
+unsafe fn drop_erased<T>(ptr: *mut u8) {
+    ptr::drop_in_place(ptr.cast::<T>())
+}
+
+/// Clone the `T` at `from` into the initialized `T` at `to`
+unsafe fn clone_erased<T: Clone>(from: *const u8, to: *mut u8) {
+    (*to.cast::<T>()).clone_from(&*from.cast::<T>())
+}
+
+/// Parse `value` into the initialized `T` at `to`, and return `false` if it
+/// doesn't parse
+unsafe fn parse_erased<T: FromStr>(value: &str, to: *mut u8) -> bool {
+    match value.parse() {
+        Ok(value) => {
+            *to.cast::<T>() = value;
+            true
+        },
+        Err(_) => false,
+    }
+}
+
+/// Parse `value` into the initialized `Option<T>` at `to`, and return `false`
+/// if it doesn't parse
+unsafe fn parse_some_erased<T: FromStr>(value: &str, to: *mut u8) -> bool {
+    match value.parse() {
+        Ok(value) => {
+            *to.cast::<Option<T>>() = Some(value);
+            true
+        },
+        Err(_) => false,
+    }
+}
+
+/// Replace the initialized `T` at `ptr` with one read from `deserializer`
+#[cfg(feature = "serde")]
+unsafe fn deserialize_erased<T: serde::de::DeserializeOwned>(
+    deserializer: &mut dyn erased_serde::Deserializer<'_>,
+    ptr: *mut u8,
+) -> Result<(), erased_serde::Error> {
+    *ptr.cast::<T>() = erased_serde::deserialize(deserializer)?;
+    Ok(())
+}
+
+impl fmt::Debug for FieldInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FieldInfo")
+            .field("name", &self.name)
+            .field("type", &self.type_name)
+            .field("descriptor", &self.descriptor)
+            .field("tag", &self.tag)
+            .finish()
+    }
+}
+
+/// A path of fields inside of `P`, parsed from a dot-separated string
+///
+/// Every segment of the path is checked against the fields generated by
+/// `#[derive(Field)]`, and the result is a single offset into `P` which can
+/// be turned into a typed field with [`FieldPath::downcast`].
+///
+/// A `FieldPath` owns the names of its segments, so it displays as the
+/// dot-joined path, and it can be compared, hashed, and sorted by those
+/// names. This makes it usable as a key in sets and maps, independent of the
+/// field types it was created from. Paths are capped at
+/// [`FieldPath::MAX_DEPTH`] segments, so they don't need to allocate.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{Field, FieldPath, ProjectTo};
+///
+/// #[derive(Field)]
+/// struct Foo {
+///     bar: Bar,
+/// }
+///
+/// #[derive(Field)]
+/// struct Bar {
+///     tap: u32,
+///     val: u64,
+/// }
+///
+/// # fn main() {
+/// let foo = Foo { bar: Bar { tap: 1, val: 2 } };
+///
+/// let path = "bar.val".parse::<FieldPath<Foo>>().unwrap();
+/// assert_eq!(*foo.project_to(path.downcast::<u64>().unwrap()), 2);
+///
+/// let err = "bar.nope".parse::<FieldPath<Foo>>().unwrap_err();
+/// assert_eq!(err.segment(), 1);
+///
+/// let tap = FieldPath::of(Foo::FIELDS.bar.chain(Bar::FIELDS.tap));
+/// assert_eq!(tap.to_string(), "bar.tap");
+/// assert!(tap < path);
+/// # }
+/// # }
+/// ```
+pub struct FieldPath<P> {
+    segments:   [&'static str; MAX_DEPTH],
+    depth:      usize,
+    descriptor: FieldDescriptor,
+    type_id:    TypeId,
+    type_name:  &'static str,
+    _parent:    Invariant<P>,
+}
+
+pub(crate) const MAX_DEPTH: usize = 8;
+
+impl<P> Clone for FieldPath<P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P> Copy for FieldPath<P> {
+}
+
+impl<P: FieldLookup> FieldPath<P> {
+    /// Resolve a dot-separated path of field names, i.e. `bar.tap.val`
+    pub fn parse(path: &str) -> Result<Self, PathError> {
+        let resolved = resolve(
+            path.split('.'),
+            P::lookup_field,
+            any::type_name::<P>(),
+            FieldDescriptor::identity::<P>(),
+        )?;
+
+        Ok(Self::from_resolved(resolved))
+    }
+
+    /// Resolve a JSON Pointer (RFC 6901) to a field, i.e. `/bar/tap/val`
+    ///
+    /// The names of fields never contain `~` or `/`, so a segment with an
+    /// escape sequence never names a field. The pointer to the whole
+    /// document, `""`, isn't a path to a field.
+    ///
+    /// ```rust
+    /// #![feature(raw_ref_op)]
+    /// # mod __ {
+    /// use gfp_core::{Field, FieldPath};
+    ///
+    /// #[derive(Field)]
+    /// struct Foo {
+    ///     bar: Bar,
+    /// }
+    ///
+    /// #[derive(Field)]
+    /// struct Bar {
+    ///     tap: u32,
+    /// }
+    ///
+    /// # fn main() {
+    /// let path = FieldPath::<Foo>::from_json_pointer("/bar/tap").unwrap();
+    /// assert_eq!(path.to_string(), "bar.tap");
+    /// assert_eq!(path.json_pointer().to_string(), "/bar/tap");
+    ///
+    /// let err = FieldPath::<Foo>::from_json_pointer("bar/tap").unwrap_err();
+    /// assert!(err.is_invalid_pointer());
+    /// # }
+    /// # }
+    /// ```
+    pub fn from_json_pointer(pointer: &str) -> Result<Self, PathError> {
+        resolve_json_pointer::<P>(pointer).map(Self::from_resolved)
+    }
+
+    fn from_resolved(resolved: Resolved) -> Self {
+        Self {
+            segments:   resolved.segments,
+            depth:      resolved.depth,
+            descriptor: resolved.descriptor,
+            type_id:    resolved.field.type_id,
+            type_name:  resolved.field.type_name,
+            _parent:    Invariant::INIT,
+        }
+    }
+}
+
+/// A path which was resolved one segment at a time
+pub(crate) struct Resolved {
+    pub segments:   [&'static str; MAX_DEPTH],
+    pub depth:      usize,
+    pub descriptor: FieldDescriptor,
+    pub field:      FieldInfo,
+}
+
+/// Resolve a JSON Pointer to a field of `P`, see
+/// [`FieldPath::from_json_pointer`]
+pub(crate) fn resolve_json_pointer<P: FieldLookup>(
+    pointer: &str,
+) -> Result<Resolved, PathError> {
+    let segments = match pointer.strip_prefix('/') {
+        Some(segments) => segments,
+        None => {
+            return Err(PathError::new(
+                Reason::InvalidPointer,
+                0,
+                any::type_name::<P>(),
+                pointer,
+            ))
+        },
+    };
+
+    resolve(
+        segments.split('/'),
+        P::lookup_field,
+        any::type_name::<P>(),
+        FieldDescriptor::identity::<P>(),
+    )
+}
+
+/// Resolve the names in `path`, starting with the fields from `lookup`
+/// inside of a parent described by `descriptor`
+pub(crate) fn resolve<'a, I>(
+    path: I,
+    mut lookup: fn(&str) -> Option<FieldInfo>,
+    mut parent: &'static str,
+    mut descriptor: FieldDescriptor,
+) -> Result<Resolved, PathError>
+where
+    I: Iterator<Item = &'a str> + Clone,
+{
+    let mut segments = [""; MAX_DEPTH];
+    let mut found = None;
+
+    for (segment, name) in path.clone().enumerate() {
+        if segment == MAX_DEPTH {
+            return Err(PathError::new(Reason::TooDeep, segment, parent, name))
+        }
+
+        let field = match lookup(name) {
+            Some(field) => field,
+            None => {
+                return Err(PathError::new(
+                    Reason::UnknownField,
+                    segment,
+                    parent,
+                    name,
+                ))
+            },
+        };
+
+        segments[segment] = field.name;
+        descriptor = descriptor.chain(field.descriptor);
+        parent = field.type_name;
+        found = Some(field);
+
+        lookup = match field.lookup {
+            Some(lookup) => lookup,
+            None => no_fields,
+        };
+    }
+
+    Ok(Resolved {
+        segments,
+        depth: path.count(),
+        descriptor,
+        // `split` always yields at least one segment
+        field: found.unwrap(),
+    })
+}
+
+impl<P> FieldPath<P> {
+    /// The maximum number of segments in a path
+    pub const MAX_DEPTH: usize = MAX_DEPTH;
+
+    /// Create the path of `field`, from the names of its segments
+    ///
+    /// # Panics
+    ///
+    /// If `field` has more than [`FieldPath::MAX_DEPTH`] segments
+    pub fn of<F: NamedField<Parent = P>>(field: F) -> Self
+    where
+        P: Sized,
+        F::Type: 'static,
+    {
+        let mut segments = [""; MAX_DEPTH];
+        let mut depth = 0;
+
+        for name in field.name() {
+            assert!(depth < MAX_DEPTH, "field path is too deep");
+            segments[depth] = name;
+            depth += 1;
+        }
+
+        Self {
+            segments,
+            depth,
+            descriptor: field.descriptor(),
+            type_id: TypeId::of::<F::Type>(),
+            type_name: any::type_name::<F::Type>(),
+            _parent: Invariant::INIT,
+        }
+    }
+
+    /// The names of the segments of the path, outermost first
+    pub fn segments(&self) -> &[&'static str] {
+        &self.segments[..self.depth]
+    }
+
+    /// Display the path as a JSON Pointer (RFC 6901), i.e. `/bar/tap/val`
+    pub fn json_pointer(&self) -> JsonPointerDisplay<'_, P> {
+        JsonPointerDisplay {
+            path: self,
+        }
+    }
+
+    /// Where the field at the end of the path lives inside of `P`
+    pub fn descriptor(&self) -> FieldDescriptor {
+        self.descriptor
+    }
+
+    /// The `TypeId` of the type of the field at the end of the path
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// The name of the type of the field at the end of the path, for
+    /// diagnostics
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Check if the field at the end of the path has the type `T`
+    pub fn is<T: 'static>(&self) -> bool {
+        self.type_id == TypeId::of::<T>()
+    }
+
+    /// Recover a typed field, if the field at the end of the path has the
+    /// type `T`
+    pub fn downcast<T: 'static>(&self) -> Option<Dynamic<P, T>> {
+        if self.is::<T>() {
+            // Safety
+            // * every segment of the path was resolved from a `Field`, so the
+            //      chained offset points to a `T` inside of `P`
+            unsafe { Some(Dynamic::from_offset(self.descriptor.offset())) }
+        } else {
+            None
+        }
+    }
+}
+
+impl<P: FieldLookup> FromStr for FieldPath<P> {
+    type Err = PathError;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        Self::parse(path)
+    }
+}
+
+impl<P> fmt::Debug for FieldPath<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FieldPath")
+            .field("segments", &self.segments())
+            .field("type", &self.type_name)
+            .field("descriptor", &self.descriptor)
+            .finish()
+    }
+}
+
+impl<P> fmt::Display for FieldPath<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, name) in self.segments().iter().enumerate() {
+            if i != 0 {
+                f.write_str(".")?;
+            }
+
+            f.write_str(name)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Displays a [`FieldPath`] as a JSON Pointer, created by
+/// [`FieldPath::json_pointer`]
+pub struct JsonPointerDisplay<'a, P> {
+    path: &'a FieldPath<P>,
+}
+
+impl<P> Clone for JsonPointerDisplay<'_, P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P> Copy for JsonPointerDisplay<'_, P> {
+}
+
+impl<P> fmt::Display for JsonPointerDisplay<'_, P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for name in self.path.segments() {
+            f.write_str("/")?;
+
+            for c in name.chars() {
+                match c {
+                    '~' => f.write_str("~0")?,
+                    '/' => f.write_str("~1")?,
+                    c => fmt::Write::write_char(f, c)?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<P> fmt::Debug for JsonPointerDisplay<'_, P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+// The segments of a path determine the field, so they are all that needs to
+// be compared or hashed
+
+impl<P> PartialEq for FieldPath<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.segments() == other.segments()
+    }
+}
+
+impl<P> Eq for FieldPath<P> {
+}
+
+impl<P> PartialOrd for FieldPath<P> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<P> Ord for FieldPath<P> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.segments().cmp(other.segments())
+    }
+}
+
+impl<P> Hash for FieldPath<P> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.segments().hash(state);
+    }
+}
+
+fn no_fields(_: &str) -> Option<FieldInfo> {
+    None
+}
+
+/// The error returned when a segment of a [`FieldPath`] doesn't name a field,
+/// when the path is longer than [`FieldPath::MAX_DEPTH`], or when a JSON
+/// Pointer doesn't start with `/`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathError {
+    reason:  Reason,
+    segment: usize,
+    parent:  &'static str,
+    #[cfg(feature = "alloc")]
+    name:    std::string::String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Reason {
+    UnknownField,
+    TooDeep,
+    InvalidPointer,
+}
+
+impl PathError {
+    fn new(
+        reason: Reason,
+        segment: usize,
+        parent: &'static str,
+        _name: &str,
+    ) -> Self {
+        Self {
+            reason,
+            segment,
+            parent,
+            #[cfg(feature = "alloc")]
+            name: _name.into(),
+        }
+    }
+
+    /// Check if the path was longer than [`FieldPath::MAX_DEPTH`]
+    pub fn is_too_deep(&self) -> bool {
+        self.reason == Reason::TooDeep
+    }
+
+    /// Check if a JSON Pointer didn't start with `/`
+    pub fn is_invalid_pointer(&self) -> bool {
+        self.reason == Reason::InvalidPointer
+    }
+
+    /// The index of the first segment which doesn't name a field
+    pub fn segment(&self) -> usize {
+        self.segment
+    }
+
+    /// The name of the type which doesn't have the field
+    pub fn parent(&self) -> &'static str {
+        self.parent
+    }
+
+    /// The segment which doesn't name a field
+    #[cfg(feature = "alloc")]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_too_deep() {
+            return write!(
+                f,
+                "path is deeper than the maximum of {} segments",
+                MAX_DEPTH
+            )
+        }
+
+        if self.is_invalid_pointer() {
+            return f.write_str("JSON pointers to fields must start with `/`")
+        }
+
+        self.fmt_unknown_field(f)
+    }
+}
+
+impl PathError {
+    #[cfg(feature = "alloc")]
+    fn fmt_unknown_field(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "`{}` has no field named `{}` (segment {} of the path)",
+            self.parent, self.name, self.segment
+        )
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    fn fmt_unknown_field(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "`{}` has no field named by segment {} of the path",
+            self.parent, self.segment
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PathError {
+}
@@ -1,5 +1,12 @@
 use super::*;
 
+use core::{
+    future::Future,
+    mem::{self, MaybeUninit},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
 /// A marker trait that specifies pointer safely project inside of a pin
 ///
 /// # Safety
@@ -7,13 +14,59 @@ use super::*;
 /// FIXME: add safety docs
 pub unsafe trait PinnablePointer: core::ops::Deref {}
 
+/// A field which a `Pin` projects to an unpinned pointer if its type is
+/// `Unpin`
+///
+/// A pinned `Unpin` value can always be unpinned, so `Pin<&mut P>` projects
+/// to `&mut F` and `Pin<&P>` to `&F` with the field itself, without wrapping
+/// it in [`PinToPtr`], for `project_to` and `project_all`. This is a marker
+/// instead of a bound on the type of the field, so that it works without
+/// specialization, because [`PinToPin`] and [`PinToPtr`] don't implement it.
+///
+/// Derived fields, fields from [`field_impl!`](crate::field_impl), tuple,
+/// array and `std_fields` fields, and chains of fields implement it.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use core::{marker::PhantomPinned, pin::Pin};
+/// use gfp_core::{Field, ProjectTo};
+///
+/// #[derive(Field)]
+/// struct Timer {
+///     ticks:   u64,
+///     _pinned: PhantomPinned,
+/// }
+///
+/// # fn main() {
+/// let mut timer = Box::pin(Timer {
+///     ticks:   0,
+///     _pinned: PhantomPinned,
+/// });
+///
+/// let ticks: &mut u64 = timer.as_mut().project_to(Timer::FIELDS.ticks);
+/// *ticks += 1;
+/// assert_eq!(timer.ticks, 1);
+/// # }
+/// # }
+/// ```
+pub trait AutoUnpin: Field {
+}
+
 /// Represents a field that can will be projected to a pointer when projected
 /// from a `Pin`
+///
+/// `Pin<&P>` projects to `&F` and `Pin<&mut P>` projects to `&mut F`, both for
+/// single fields with `project_to` and for field lists with `project_all`
 #[repr(transparent)]
 #[derive(Copy, Clone)]
 pub struct PinToPtr<F: Field>(pub F);
 
 /// A field-type which is pin-projectable
+///
+/// `Pin<&P>` projects to `Pin<&F>` and `Pin<&mut P>` projects to
+/// `Pin<&mut F>`, both for single fields with `project_to` and for field lists
+/// with `project_all`
 #[repr(transparent)]
 #[derive(Copy, Clone)]
 pub struct PinToPin<F: Field> {
@@ -62,6 +115,24 @@ unsafe impl<F: Field> Field for PinToPtr<F> {
     }
 }
 
+impl<F: NamedField> NamedField for PinToPin<F> {
+    type Name = F::Name;
+
+    #[inline]
+    fn name(&self) -> Self::Name {
+        self.field.name()
+    }
+}
+
+impl<F: NamedField> NamedField for PinToPtr<F> {
+    type Name = F::Name;
+
+    #[inline]
+    fn name(&self) -> Self::Name {
+        self.0.name()
+    }
+}
+
 impl<F: Field> PinToPin<F> {
     /// You must validate the safety notes of
     /// [`PinProjectable<F>`](trait.PinProjectable.html)
@@ -90,7 +161,10 @@ impl<F: Field> PinToPin<F> {
     }
 
     /// Convert to a dynamic field that can project pinned types to pinned fields
-    pub fn pin_dynamic(&self) -> PinToPin<crate::Dynamic<F::Parent, F::Type>> {
+    pub fn pin_dynamic(&self) -> PinToPin<crate::Dynamic<F::Parent, F::Type>>
+    where
+        F::Parent: Sized,
+    {
         // # Safety
         //
         // * It is to go from `Pin<Ptr<T>>` to `Pin<Ptr<Field>>` for any
@@ -98,6 +172,72 @@ impl<F: Field> PinToPin<F> {
         //   and `Field::dynamic` returning the *same* field
         unsafe { PinToPin::new_unchecked(self.field.dynamic()) }
     }
+
+    /// Project a pinned, possibly uninitialized parent to its pinned, possibly
+    /// uninitialized field
+    ///
+    /// This is how a self-referential value is built in place after it was
+    /// pinned: each field is written through its own slot, and the address of
+    /// one slot can be stored in another.
+    ///
+    /// ```rust
+    /// #![feature(raw_ref_op)]
+    /// # mod __ {
+    /// use core::{marker::PhantomPinned, mem::MaybeUninit, pin::Pin, ptr};
+    /// use gfp_core::{Field, PinToPin, PinToPtr};
+    ///
+    /// #[derive(Field)]
+    /// struct Node {
+    ///     value: u32,
+    ///     this:  *const u32,
+    ///     _pin:  PhantomPinned,
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut slot = Box::pin(MaybeUninit::<Node>::uninit());
+    ///
+    /// let value = unsafe { PinToPin::new_unchecked(Node::FIELDS.value) };
+    /// let value = value.project_uninit(slot.as_mut());
+    /// let value: *const u32 = unsafe { value.get_unchecked_mut().write(10) };
+    ///
+    /// let this = PinToPtr::new(Node::FIELDS.this);
+    /// this.project_uninit(slot.as_mut()).write(value);
+    ///
+    /// let pin = PinToPtr::new(Node::FIELDS._pin);
+    /// pin.project_uninit(slot.as_mut()).write(PhantomPinned);
+    ///
+    /// let node = unsafe { slot.as_ref().get_ref().assume_init_ref() };
+    /// assert!(ptr::eq(node.this, &node.value));
+    /// # }
+    /// # }
+    /// ```
+    #[inline]
+    pub fn project_uninit<'a>(
+        &self,
+        parent: Pin<&'a mut MaybeUninit<F::Parent>>,
+    ) -> Pin<&'a mut MaybeUninit<F::Type>>
+    where
+        F::Parent: Sized,
+    {
+        // # Safety
+        //
+        // * `MaybeUninit<T>` has the same layout as `T`, and projecting only
+        //   computes the address of the field, so it doesn't read the
+        //   uninitialized parent
+        // * It is safe to go from `Pin<&mut Parent>` to `Pin<&mut Field>` by
+        //   virtue of `Self` being a `PinToPin`
+        unsafe {
+            let parent = Pin::into_inner_unchecked(parent).as_mut_ptr();
+            let field = self.field.project_raw_mut(parent);
+            crate::project::check_projection(
+                parent,
+                mem::size_of::<F::Parent>(),
+                field,
+            );
+
+            Pin::new_unchecked(&mut *(field as *mut MaybeUninit<F::Type>))
+        }
+    }
 }
 
 impl<F: Field> PinToPtr<F> {
@@ -113,4 +253,294 @@ impl<F: Field> PinToPtr<F> {
     pub fn as_ref(&self) -> PinToPtr<&F> {
         PinToPtr(&self.0)
     }
+
+    /// Project a pinned, possibly uninitialized parent to its possibly
+    /// uninitialized field, which isn't pinned
+    ///
+    /// See [`PinToPin::project_uninit`] for initializing a pinned value in
+    /// place
+    #[inline]
+    pub fn project_uninit<'a>(
+        &self,
+        parent: Pin<&'a mut MaybeUninit<F::Parent>>,
+    ) -> &'a mut MaybeUninit<F::Type>
+    where
+        F::Parent: Sized,
+    {
+        // # Safety
+        //
+        // * `MaybeUninit<T>` has the same layout as `T`, and projecting only
+        //   computes the address of the field, so it doesn't read the
+        //   uninitialized parent
+        // * `Self` being a `PinToPtr` means that the field isn't pinned
+        unsafe {
+            let parent = Pin::into_inner_unchecked(parent).as_mut_ptr();
+            let field = self.0.project_raw_mut(parent);
+            crate::project::check_projection(
+                parent,
+                mem::size_of::<F::Parent>(),
+                field,
+            );
+
+            &mut *(field as *mut MaybeUninit<F::Type>)
+        }
+    }
+}
+
+/// Projection methods for `Pin<&mut T>`, which take the pin first and the
+/// field second
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use core::pin::Pin;
+/// use gfp_core::{Field, PinProjectExt, PinToPin, PinToPtr};
+///
+/// #[derive(Field)]
+/// struct Foo {
+///     bar: u32,
+///     tap: u64,
+/// }
+///
+/// # fn main() {
+/// let mut foo = Foo { bar: 0, tap: 0 };
+/// let mut pin = Pin::new(&mut foo);
+///
+/// let bar = unsafe { PinToPin::new_unchecked(Foo::FIELDS.bar) };
+/// *pin.as_mut().project(bar) = 1;
+/// *pin.as_mut().project(PinToPtr::new(Foo::FIELDS.tap)) = 2;
+///
+/// assert_eq!(foo.bar, 1);
+/// assert_eq!(foo.tap, 2);
+/// # }
+/// # }
+/// ```
+pub trait PinProjectExt<'a, T> {
+    /// Project to `field`, the same as `ProjectTo::project_to`
+    fn project<F: Field<Parent = T>>(
+        self,
+        field: F,
+    ) -> <Pin<&'a mut T> as ProjectTo<F>>::Projection
+    where
+        Pin<&'a mut T>: ProjectTo<F>;
+
+    /// Project to every field in `fields`, the same as
+    /// `ProjectAll::project_all`
+    fn project_set<F>(
+        self,
+        fields: F,
+    ) -> <Pin<&'a mut T> as ProjectAll<T, F>>::Projection
+    where
+        Pin<&'a mut T>: ProjectAll<T, F>;
+
+    /// Poll the pinned future in `field`
+    fn poll_field<F>(
+        self,
+        field: PinToPin<F>,
+        cx: &mut Context<'_>,
+    ) -> Poll<<F::Type as Future>::Output>
+    where
+        F: Field<Parent = T>,
+        F::Type: Future;
+
+    /// Poll the pinned future in the `Option` in `field`, and clear the field
+    /// once the future is ready
+    ///
+    /// An empty field is ready with `None`, so a finished future is never
+    /// polled again.
+    fn poll_option_field<F, Fut>(
+        self,
+        field: PinToPin<F>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Fut::Output>>
+    where
+        F: Field<Parent = T, Type = Option<Fut>>,
+        Fut: Future;
+
+    /// Project to the pinned value in the `Option` in `field`, if there is
+    /// one, like a `#[pin] Option<Fut>` field with `pin-project`
+    fn project_option_field<F, U>(
+        self,
+        field: PinToPin<F>,
+    ) -> Option<Pin<&'a mut U>>
+    where
+        F: Field<Parent = T, Type = Option<U>>;
+
+    /// Replace the `Option` in `field` with `value`
+    ///
+    /// The old value is dropped in place, so it never moves while it is
+    /// pinned.
+    fn set_option_field<F, U>(self, field: PinToPin<F>, value: Option<U>)
+    where
+        F: Field<Parent = T, Type = Option<U>>;
+
+    /// Take the value out of the `Option` in `field`, and leave `None`
+    ///
+    /// This moves the value, so it must be `Unpin`. Values which aren't can
+    /// be dropped in place with [`set_option_field`](Self::set_option_field)
+    /// instead.
+    fn take_option_field<F, U>(self, field: PinToPin<F>) -> Option<U>
+    where
+        F: Field<Parent = T, Type = Option<U>>,
+        U: Unpin;
+
+    /// Replace the pinned value with `replacement`, and move the unpinned
+    /// `fields` out of the old value, like `project_replace` with
+    /// `pin-project`
+    ///
+    /// Every other field of the old value is dropped in place, so nothing
+    /// pinned ever moves, and the values of `fields` are returned as a list.
+    /// This lets a state machine move its unpinned state into the next state.
+    ///
+    /// ```rust
+    /// #![feature(raw_ref_op)]
+    /// # mod __ {
+    /// use core::{marker::PhantomPinned, pin::Pin};
+    /// use gfp_core::{Field, PinProjectExt, PinToPtr};
+    /// use typsy::convert::Convert;
+    ///
+    /// #[derive(Field)]
+    /// struct Connect {
+    ///     address: String,
+    ///     retries: u32,
+    ///     _pinned: PhantomPinned,
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut state = Box::pin(Connect {
+    ///     address: "localhost".to_string(),
+    ///     retries: 3,
+    ///     _pinned: PhantomPinned,
+    /// });
+    ///
+    /// let next = Connect {
+    ///     address: String::new(),
+    ///     retries: 0,
+    ///     _pinned: PhantomPinned,
+    /// };
+    ///
+    /// let fields = (PinToPtr::new(Connect::FIELDS.address),).into_hlist();
+    /// let typsy::hlist_pat!(address) =
+    ///     unsafe { state.as_mut().project_replace(next, fields) };
+    ///
+    /// assert_eq!(address, "localhost");
+    /// assert_eq!(state.retries, 0);
+    /// # }
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If one of `fields` isn't one of the fields of `T`, i.e. if it is a
+    /// chain of fields, if it is in `fields` more than once, or if `T` has
+    /// more than 64 fields. The old value is left untouched.
+    ///
+    /// # Safety
+    ///
+    /// `T` must not implement `Drop`, because it isn't run for the old value,
+    /// like with a partial move
+    unsafe fn project_replace<L>(self, replacement: T, fields: L) -> L::Taken
+    where
+        T: CompleteFieldLookup,
+        L: ReplaceList<T>;
+}
+
+impl<'a, T> PinProjectExt<'a, T> for Pin<&'a mut T> {
+    #[inline]
+    fn project<F: Field<Parent = T>>(
+        self,
+        field: F,
+    ) -> <Pin<&'a mut T> as ProjectTo<F>>::Projection
+    where
+        Pin<&'a mut T>: ProjectTo<F>,
+    {
+        self.project_to(field)
+    }
+
+    #[inline]
+    fn project_set<F>(
+        self,
+        fields: F,
+    ) -> <Pin<&'a mut T> as ProjectAll<T, F>>::Projection
+    where
+        Pin<&'a mut T>: ProjectAll<T, F>,
+    {
+        self.project_all(fields)
+    }
+
+    #[inline]
+    fn poll_field<F>(
+        self,
+        field: PinToPin<F>,
+        cx: &mut Context<'_>,
+    ) -> Poll<<F::Type as Future>::Output>
+    where
+        F: Field<Parent = T>,
+        F::Type: Future,
+    {
+        self.project_to(field).poll(cx)
+    }
+
+    fn poll_option_field<F, Fut>(
+        self,
+        field: PinToPin<F>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Fut::Output>>
+    where
+        F: Field<Parent = T, Type = Option<Fut>>,
+        Fut: Future,
+    {
+        let mut slot = self.project_to(field);
+
+        let output = match slot.as_mut().as_pin_mut() {
+            Some(future) => {
+                match future.poll(cx) {
+                    Poll::Ready(output) => output,
+                    Poll::Pending => return Poll::Pending,
+                }
+            },
+            None => return Poll::Ready(None),
+        };
+
+        // dropping the future in place keeps the pinning guarantees
+        slot.set(None);
+        Poll::Ready(Some(output))
+    }
+
+    #[inline]
+    fn project_option_field<F, U>(
+        self,
+        field: PinToPin<F>,
+    ) -> Option<Pin<&'a mut U>>
+    where
+        F: Field<Parent = T, Type = Option<U>>,
+    {
+        self.project_to(field).as_pin_mut()
+    }
+
+    #[inline]
+    fn set_option_field<F, U>(self, field: PinToPin<F>, value: Option<U>)
+    where
+        F: Field<Parent = T, Type = Option<U>>,
+    {
+        self.project_to(field).set(value)
+    }
+
+    #[inline]
+    fn take_option_field<F, U>(self, field: PinToPin<F>) -> Option<U>
+    where
+        F: Field<Parent = T, Type = Option<U>>,
+        U: Unpin,
+    {
+        Pin::get_mut(self.project_to(field)).take()
+    }
+
+    #[inline]
+    unsafe fn project_replace<L>(self, replacement: T, fields: L) -> L::Taken
+    where
+        T: CompleteFieldLookup,
+        L: ReplaceList<T>,
+    {
+        crate::replace::project_replace(self, replacement, fields)
+    }
 }
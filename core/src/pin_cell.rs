@@ -0,0 +1,119 @@
+//! Projections through the pinned borrows of a `pin_cell::PinCell`
+
+use crate::{Field, ProjectTo};
+
+use core::{fmt, ops::Deref, pin::Pin};
+
+use pin_cell::PinMut;
+
+/// A `pin_cell::PinMut` guard narrowed down to the field `F` of the value in
+/// the `PinCell`, which is what `project_to` on a `PinMut` gives
+///
+/// `PinMut` can't be mapped, so this keeps the borrow of the whole value, and
+/// projects to the field whenever it is accessed. It derefs to the field, and
+/// [`as_mut`](Self::as_mut) projects to it through `Pin<&mut _>`, so
+/// [`PinToPin`](crate::PinToPin) fields stay pinned, and
+/// [`PinToPtr`](crate::PinToPtr) and `Unpin` fields are plain references.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use core::{marker::PhantomPinned, pin::Pin};
+/// use gfp_core::{Field, PinMutField, PinToPin, PinToPtr, ProjectTo};
+/// use pin_cell::PinCell;
+///
+/// #[derive(Field)]
+/// struct Task {
+///     future:  Timer,
+///     polls:   u32,
+///     _pinned: PhantomPinned,
+/// }
+///
+/// struct Timer {
+///     ticks:   u32,
+///     _pinned: PhantomPinned,
+/// }
+///
+/// # fn main() {
+/// let task = Box::pin(PinCell::new(Task {
+///     future:  Timer { ticks: 0, _pinned: PhantomPinned },
+///     polls:   0,
+///     _pinned: PhantomPinned,
+/// }));
+///
+/// let future = unsafe { PinToPin::new_unchecked(Task::FIELDS.future) };
+/// let mut timer = task.as_ref().borrow_mut().project_to(future);
+/// let timer: Pin<&mut Timer> = PinMutField::as_mut(&mut timer);
+/// unsafe { timer.get_unchecked_mut().ticks += 1 };
+///
+/// let mut polls = task.as_ref().borrow_mut().project_to(Task::FIELDS.polls);
+/// *PinMutField::as_mut(&mut polls) += 1;
+/// drop(polls);
+///
+/// assert_eq!(task.borrow().future.ticks, 1);
+/// assert_eq!(task.borrow().polls, 1);
+/// # }
+/// # }
+/// ```
+pub struct PinMutField<'a, F: Field> {
+    guard: PinMut<'a, F::Parent>,
+    field: F,
+}
+
+impl<'a, F: Field> PinMutField<'a, F> {
+    /// Project to the field through `Pin<&mut _>`
+    ///
+    /// This is an associated function, like `PinMut::as_mut`, so it doesn't
+    /// shadow the methods of the field.
+    pub fn as_mut<'b>(
+        this: &'b mut Self,
+    ) -> <Pin<&'b mut F::Parent> as ProjectTo<F>>::Projection
+    where
+        F: Copy,
+        Pin<&'b mut F::Parent>: ProjectTo<F>,
+    {
+        PinMut::as_mut(&mut this.guard).project_to(this.field)
+    }
+
+    /// The field which the guard is narrowed down to
+    pub fn field(this: &Self) -> &F {
+        &this.field
+    }
+
+    /// Release the field, and get back the guard of the whole value
+    pub fn into_inner(this: Self) -> PinMut<'a, F::Parent> {
+        this.guard
+    }
+}
+
+impl<'a, F: Field> ProjectTo<F> for PinMut<'a, F::Parent> {
+    type Projection = PinMutField<'a, F>;
+
+    #[inline]
+    fn project_to(self, field: F) -> Self::Projection {
+        PinMutField {
+            guard: self,
+            field,
+        }
+    }
+}
+
+impl<F: Field> Deref for PinMutField<'_, F> {
+    type Target = F::Type;
+
+    #[inline]
+    fn deref(&self) -> &F::Type {
+        // Safety
+        // * the guard points to a valid parent, which it borrows
+        unsafe { &*self.field.project_raw(&*self.guard) }
+    }
+}
+
+impl<F: Field> fmt::Debug for PinMutField<'_, F>
+where
+    F::Type: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        F::Type::fmt(self, f)
+    }
+}
@@ -7,6 +7,7 @@ pub mod from_arc;
 #[cfg(feature = "alloc")]
 pub mod from_box;
 pub mod from_mut;
+pub mod from_out;
 pub mod from_pin;
 #[cfg(feature = "alloc")]
 pub mod from_rc;
@@ -27,6 +28,50 @@ impl<F: Field, T: ProjectTo<F>> ProjectTo<F> for Option<T> {
     }
 }
 
+/// Access fields through plain references, without going through
+/// [`ProjectTo`] and its `Projection` types
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{Field, FieldExt};
+///
+/// #[derive(Field)]
+/// struct Foo {
+///     bar: u32,
+///     tap: u64,
+/// }
+///
+/// # fn main() {
+/// let mut foo = Foo { bar: 0, tap: 0 };
+///
+/// *(&mut foo).field_mut(Foo::FIELDS.tap) = 10;
+///
+/// assert_eq!(*(&foo).field(Foo::FIELDS.bar), 0);
+/// assert_eq!(*foo.field(Foo::FIELDS.tap), 10);
+/// # }
+/// # }
+/// ```
+pub trait FieldExt {
+    /// Borrow the field `field` of `self`
+    fn field<F: Field<Parent = Self>>(&self, field: F) -> &F::Type;
+
+    /// Mutably borrow the field `field` of `self`
+    fn field_mut<F: Field<Parent = Self>>(&mut self, field: F) -> &mut F::Type;
+}
+
+impl<T: ?Sized> FieldExt for T {
+    #[inline]
+    fn field<F: Field<Parent = Self>>(&self, field: F) -> &F::Type {
+        self.project_to(field)
+    }
+
+    #[inline]
+    fn field_mut<F: Field<Parent = Self>>(&mut self, field: F) -> &mut F::Type {
+        self.project_to(field)
+    }
+}
+
 pub struct PtrToRef<'a>(PhantomData<&'a ()>);
 
 typsy::call! {
@@ -70,7 +115,11 @@ typsy::call! {
         })
     }
 
-    fn[I: Field, J: Field](&mut self: FindOverlapInner<I>, input: J) -> bool {
+    fn[I: Field, J: Field](&mut self: FindOverlapInner<I>, input: J) -> bool
+    where(
+        I::Parent: Sized,
+        J::Parent: Sized,
+    ){
         self.counter += 1;
 
         if self.id <= self.counter {
@@ -85,7 +134,7 @@ typsy::call! {
 }
 
 #[allow(clippy::suspicious_operation_groupings)]
-fn is_overlapping(a: Range<usize>, b: Range<usize>) -> bool {
+pub(crate) fn is_overlapping(a: Range<usize>, b: Range<usize>) -> bool {
     !b.is_empty()
         && !a.is_empty()
         && (a.contains(&b.start) || b.contains(&a.start))
@@ -135,3 +184,68 @@ mod tests {
         assert!(!is_overlapping(5..10, 1..4));
     }
 }
+
+/// Called by every projection through a reference or a smart pointer, with
+/// the `size` of the parent at `parent`, and the field `ptr` that `field`
+/// projected to
+#[inline]
+#[track_caller]
+pub(crate) fn on_projection<F: Field>(
+    field: &F,
+    parent: *const F::Parent,
+    size: usize,
+    ptr: *const F::Type,
+) {
+    check_projection(parent, size, ptr);
+
+    #[cfg(feature = "observe")]
+    crate::observe::notify(field, parent, ptr);
+
+    #[cfg(not(feature = "observe"))]
+    let _ = field;
+}
+
+/// Check that the field at `field` lies inside of the `size` bytes of the
+/// parent at `parent`, and that it is aligned for its type
+///
+/// With the `checked-projections` feature this panics in debug builds, so an
+/// offset bug in a hand-written `Field` impl, or a packed or transmuted
+/// parent, fails at the projection, instead of going on to read or write
+/// outside of the parent or through a misaligned pointer.
+#[inline]
+#[track_caller]
+pub(crate) fn check_projection<P: ?Sized, T>(
+    parent: *const P,
+    size: usize,
+    field: *const T,
+) {
+    #[cfg(feature = "checked-projections")]
+    {
+        let start = parent as *const u8 as usize;
+        let offset = (field as *const u8 as usize).wrapping_sub(start);
+
+        debug_assert!(
+            field as *const u8 as usize >= start
+                && offset + core::mem::size_of::<T>() <= size,
+            "the projection to `{}` at offset {} is outside of the {} bytes \
+             of `{}`",
+            core::any::type_name::<T>(),
+            offset as isize,
+            size,
+            core::any::type_name::<P>(),
+        );
+
+        debug_assert!(
+            field as *const u8 as usize & (core::mem::align_of::<T>() - 1) == 0,
+            "the projection to `{}` at offset {} of `{}` is misaligned, it \
+             needs an alignment of {}",
+            core::any::type_name::<T>(),
+            offset,
+            core::any::type_name::<P>(),
+            core::mem::align_of::<T>(),
+        );
+    }
+
+    #[cfg(not(feature = "checked-projections"))]
+    let _ = (parent, size, field);
+}
@@ -10,19 +10,19 @@ use super::*;
 
 use std::sync::Arc;
 
-pub struct ProjectedArc<P, T> {
+pub struct ProjectedArc<P: ?Sized, T> {
     _own:  Arc<P>,
     field: *const T,
 }
 
-unsafe impl<P, T> Send for ProjectedArc<P, T> where Arc<P>: Send
+unsafe impl<P: ?Sized, T> Send for ProjectedArc<P, T> where Arc<P>: Send
 {
 }
-unsafe impl<P, T> Sync for ProjectedArc<P, T> where Arc<P>: Sync
+unsafe impl<P: ?Sized, T> Sync for ProjectedArc<P, T> where Arc<P>: Sync
 {
 }
 
-impl<P, T> Deref for ProjectedArc<P, T> {
+impl<P: ?Sized, T> Deref for ProjectedArc<P, T> {
     type Target = T;
 
     fn deref(&self) -> &T {
@@ -37,10 +37,12 @@ impl<F: Field> ProjectTo<F> for Arc<F::Parent> {
 
     fn project_to(self, field: F) -> Self::Projection {
         unsafe {
-            let field = field.project_raw(&self as &_);
+            let parent: &F::Parent = &self;
+            let ptr = field.project_raw(parent);
+            on_projection(&field, parent, core::mem::size_of_val(parent), ptr);
             ProjectedArc {
-                _own: self,
-                field,
+                _own:  self,
+                field: ptr,
             }
         }
     }
@@ -16,12 +16,12 @@ use std::boxed::Box;
 //     fn(self: PtrToNonNull, ptr: *mut T) -> NonNull<T> { unsafe { NonNull::new_unchecked(ptr) } }
 // }
 
-pub struct BoxProjection<T, F> {
+pub struct BoxProjection<T: ?Sized, F> {
     bx:    NonNull<T>,
     field: NonNull<F>,
 }
 
-impl<T, F> Deref for BoxProjection<T, F> {
+impl<T: ?Sized, F> Deref for BoxProjection<T, F> {
     type Target = F;
 
     fn deref(&self) -> &F {
@@ -29,16 +29,16 @@ impl<T, F> Deref for BoxProjection<T, F> {
     }
 }
 
-impl<T, F> DerefMut for BoxProjection<T, F> {
+impl<T: ?Sized, F> DerefMut for BoxProjection<T, F> {
     fn deref_mut(&mut self) -> &mut F {
         unsafe { self.field.as_mut() }
     }
 }
 
-unsafe impl<#[may_dangle] T, #[may_dangle] F> Drop for BoxProjection<T, F> {
+unsafe impl<#[may_dangle] T: ?Sized, #[may_dangle] F> Drop for BoxProjection<T, F> {
     fn drop(&mut self) {
         unsafe {
-            Box::from_raw(self.bx.as_ptr());
+            drop(Box::from_raw(self.bx.as_ptr()));
         }
     }
 }
@@ -49,11 +49,14 @@ impl<F: Field> ProjectTo<F> for Box<F::Parent> {
     type Projection = BoxProjection<F::Parent, F::Type>;
 
     fn project_to(self, field: F) -> Self::Projection {
+        let size = core::mem::size_of_val(&*self);
+
         unsafe {
             let bx = Box::into_raw(self);
-            let field = field.project_raw_mut(bx);
+            let ptr = field.project_raw_mut(bx);
+            on_projection(&field, bx, size, ptr);
             let bx = NonNull::new_unchecked(bx);
-            let field = NonNull::new_unchecked(field);
+            let field = NonNull::new_unchecked(ptr);
 
             BoxProjection {
                 bx,
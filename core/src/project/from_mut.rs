@@ -1,4 +1,5 @@
 use super::*;
+use core::mem::MaybeUninit;
 use type_list::{FieldList, ProjectRawMut, ProjectedMut};
 use typsy::{
     cmp::Any,
@@ -13,6 +14,14 @@ typsy::call! {
     }
 }
 
+pub struct PtrToUninitMut<'a>(PhantomData<&'a ()>);
+
+typsy::call! {
+    fn['a, T: 'a](&mut self: PtrToUninitMut<'a>, ptr: *mut T) -> &'a mut MaybeUninit<T> {
+        unsafe { &mut *(ptr as *mut MaybeUninit<T>) }
+    }
+}
+
 unsafe impl<T: ?Sized> PinnablePointer for &mut T {
 }
 impl<'a, F: Field> ProjectTo<F> for &'a mut F::Parent
@@ -23,7 +32,11 @@ where
     type Projection = &'a mut F::Type;
 
     fn project_to(self, field: F) -> Self::Projection {
-        unsafe { &mut *field.project_raw_mut(self) }
+        let size = core::mem::size_of_val(self);
+        let parent = self as *mut F::Parent;
+        let ptr = unsafe { field.project_raw_mut(parent) };
+        on_projection(&field, parent, size, ptr);
+        unsafe { &mut *ptr }
     }
 }
 
@@ -50,3 +63,59 @@ where
         }
     }
 }
+
+/// Projects a possibly uninitialized parent to its possibly uninitialized
+/// fields, so that several fields can be written from one place
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use core::mem::MaybeUninit;
+/// use gfp_core::{Field, ProjectAll};
+/// use typsy::convert::Convert;
+///
+/// #[derive(Field)]
+/// struct Record {
+///     id:   u32,
+///     name: String,
+/// }
+///
+/// # fn main() {
+/// let mut record = MaybeUninit::<Record>::uninit();
+///
+/// let fields = Record::fields();
+/// let typsy::hlist_pat!(id, name) =
+///     (&mut record).project_all((fields.id, fields.name).into_hlist());
+/// id.write(7);
+/// name.write("seven".to_string());
+///
+/// let record = unsafe { record.assume_init() };
+/// assert_eq!(record.id, 7);
+/// # }
+/// # }
+/// ```
+impl<'a, F, Parent> ProjectAll<Parent, F> for &'a mut MaybeUninit<Parent>
+where
+    F: FieldList<Parent>,
+    ProjectedMut<Parent, F>: Map<PtrToUninitMut<'a>>,
+    F: Copy + for<'b> Any<'b, FindOverlap<F>>,
+{
+    /// The projection of the type, can be used to initialize the fields
+    type Projection = Mapped<ProjectedMut<Parent, F>, PtrToUninitMut<'a>>;
+
+    /// projects to the given fields
+    fn project_all(self, field: F) -> Self::Projection {
+        assert!(
+            !field.any(FindOverlap::new(field)),
+            "Found overlapping fields"
+        );
+
+        // projecting only computes the addresses of the fields, so it doesn't
+        // read the uninitialized parent
+        unsafe {
+            field
+                .map(ProjectRawMut::uninit(self.as_mut_ptr()))
+                .map(PtrToUninitMut(PhantomData))
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! Projects through an `Out`, to the `Out`s of the fields
+
+use super::*;
+use crate::Out;
+use type_list::{FieldList, ProjectRawMut, ProjectedMut};
+use typsy::{
+    cmp::Any,
+    map::{Map, Mapped},
+};
+
+pub struct PtrToOut<'a>(PhantomData<&'a ()>);
+
+typsy::call! {
+    fn['a, T: 'a](&mut self: PtrToOut<'a>, ptr: *mut T) -> Out<'a, T> {
+        unsafe { Out::from_raw(ptr) }
+    }
+}
+
+impl<'a, F: Field> ProjectTo<F> for Out<'a, F::Parent>
+where
+    F::Parent: Sized + 'a,
+    F::Type: 'a,
+{
+    type Projection = Out<'a, F::Type>;
+
+    fn project_to(mut self, field: F) -> Self::Projection {
+        let parent = self.as_mut_ptr();
+        let ptr = unsafe { field.project_raw_mut(parent) };
+        check_projection(parent, core::mem::size_of::<F::Parent>(), ptr);
+        unsafe { Out::from_raw(ptr) }
+    }
+}
+
+impl<'a, F, Parent> ProjectAll<Parent, F> for Out<'a, Parent>
+where
+    F: FieldList<Parent>,
+    ProjectedMut<Parent, F>: Map<PtrToOut<'a>>,
+    F: Copy + for<'b> Any<'b, FindOverlap<F>>,
+{
+    /// The projection of the type, can be used to initialize the fields
+    type Projection = Mapped<ProjectedMut<Parent, F>, PtrToOut<'a>>;
+
+    /// projects to the given fields
+    fn project_all(mut self, field: F) -> Self::Projection {
+        assert!(
+            !field.any(FindOverlap::new(field)),
+            "Found overlapping fields"
+        );
+
+        unsafe {
+            field
+                .map(ProjectRawMut::uninit(self.as_mut_ptr()))
+                .map(PtrToOut(PhantomData))
+        }
+    }
+}
@@ -40,6 +40,20 @@ where
     }
 }
 
+impl<F: AutoUnpin, P> ProjectTo<F> for Pin<P>
+where
+    P: PinnablePointer + ProjectTo<F>,
+    F::Type: Unpin,
+{
+    type Projection = P::Projection;
+
+    fn project_to(self, field: F) -> Self::Projection {
+        // Safety
+        // * the field is `Unpin`, so it doesn't need to stay pinned
+        unsafe { Pin::into_inner_unchecked(self).project_to(field) }
+    }
+}
+
 pub struct MakePin;
 pub struct MakePtr;
 
@@ -56,6 +70,13 @@ typsy::call! {
         MakePtr
     }
 
+    fn[F: AutoUnpin](&mut self: CreateTag, _field: F) -> MakePtr
+    where(
+        F::Type: Unpin,
+    ){
+        MakePtr
+    }
+
     fn[T: Deref](&mut self: BuildOutput, arg: (MakePin, T)) -> Pin<T> {
         let (MakePin,  value) = arg;
         unsafe { Pin::new_unchecked(value) }
@@ -10,12 +10,12 @@ use super::*;
 
 use std::rc::Rc;
 
-pub struct ProjectedRc<P, T> {
+pub struct ProjectedRc<P: ?Sized, T> {
     _own:  Rc<P>,
     field: *const T,
 }
 
-impl<P, T> Deref for ProjectedRc<P, T> {
+impl<P: ?Sized, T> Deref for ProjectedRc<P, T> {
     type Target = T;
 
     fn deref(&self) -> &T {
@@ -30,10 +30,12 @@ impl<F: Field> ProjectTo<F> for Rc<F::Parent> {
 
     fn project_to(self, field: F) -> Self::Projection {
         unsafe {
-            let field = field.project_raw(&self as &_);
+            let parent: &F::Parent = &self;
+            let ptr = field.project_raw(parent);
+            on_projection(&field, parent, core::mem::size_of_val(parent), ptr);
             ProjectedRc {
-                _own: self,
-                field,
+                _own:  self,
+                field: ptr,
             }
         }
     }
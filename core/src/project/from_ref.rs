@@ -13,7 +13,9 @@ where
     type Projection = &'a F::Type;
 
     fn project_to(self, field: F) -> Self::Projection {
-        unsafe { &*field.project_raw(self) }
+        let ptr = unsafe { field.project_raw(self) };
+        on_projection(&field, self, core::mem::size_of_val(self), ptr);
+        unsafe { &*ptr }
     }
 }
 
@@ -0,0 +1,104 @@
+//! A bridge to `bevy_reflect` paths
+//!
+//! [`FieldPath`]s display as dot-joined names, which is also valid syntax for
+//! a `bevy_reflect` path, so they convert directly into a [`ParsedPath`].
+//! In the other direction, [`path`] and [`path_mut`] resolve a reflection
+//! path through the derived [`FieldLookup`] impls when they can, which gives
+//! a plain projection, and fall back to reflection for anything else, like
+//! list indices or enum variants.
+//!
+//! ```rust
+//! #![feature(raw_ref_op)]
+//! # mod __ {
+//! use bevy_reflect::Reflect;
+//! use gfp_core::{reflect, Field, FieldPath};
+//!
+//! #[derive(Field, Reflect)]
+//! struct Player {
+//!     stats: Stats,
+//!     items: Vec<u32>,
+//! }
+//!
+//! #[derive(Field, Reflect)]
+//! struct Stats {
+//!     speed: f32,
+//! }
+//!
+//! # fn main() {
+//! let player = Player {
+//!     stats: Stats { speed: 1.0 },
+//!     items: vec![7],
+//! };
+//!
+//! // projects through the fields
+//! let speed = reflect::path::<_, f32>(&player, "stats.speed").unwrap();
+//! assert_eq!(*speed, 1.0);
+//! // falls back to reflection for the index
+//! let item = reflect::path::<_, u32>(&player, "items[0]").unwrap();
+//! assert_eq!(*item, 7);
+//!
+//! let speed = FieldPath::of(Player::FIELDS.stats.chain(Stats::FIELDS.speed));
+//! assert_eq!(reflect::to_parsed_path(&speed).to_string(), "stats.speed");
+//! # }
+//! # }
+//! ```
+
+use crate::{FieldLookup, FieldPath, NamedField, ProjectTo};
+
+use bevy_reflect::{GetPath, ParsedPath, Reflect, ReflectPathError};
+use std::string::ToString;
+
+/// Convert a `FieldPath` into the equivalent reflection path
+pub fn to_parsed_path<P>(path: &FieldPath<P>) -> ParsedPath {
+    ParsedPath::parse(&path.to_string())
+        .expect("field paths are valid reflection paths")
+}
+
+/// Convert a field, or a chain of fields, into the equivalent reflection path
+pub fn field_to_parsed_path<F: NamedField>(field: &F) -> ParsedPath {
+    ParsedPath::parse(&field.display_path().to_string())
+        .expect("field paths are valid reflection paths")
+}
+
+/// Resolve `path` inside of `parent`, through the derived fields if every
+/// segment names a field, or through reflection otherwise
+pub fn path<'a, 'p, P, T>(
+    parent: &'a P,
+    path: &'p str,
+) -> Result<&'a T, ReflectPathError<'p>>
+where
+    P: FieldLookup + Reflect,
+    T: Reflect,
+{
+    match field_path::<P, T>(path) {
+        Some(field) => Ok(parent.project_to(field)),
+        None => parent.path::<T>(path),
+    }
+}
+
+/// Mutably resolve `path` inside of `parent`, through the derived fields if
+/// every segment names a field, or through reflection otherwise
+pub fn path_mut<'a, 'p, P, T>(
+    parent: &'a mut P,
+    path: &'p str,
+) -> Result<&'a mut T, ReflectPathError<'p>>
+where
+    P: FieldLookup + Reflect,
+    T: Reflect,
+{
+    match field_path::<P, T>(path) {
+        Some(field) => Ok(parent.project_to(field)),
+        None => parent.path_mut::<T>(path),
+    }
+}
+
+/// The typed field for `path`, if it is a plain path of fields which ends in
+/// a `T`
+fn field_path<P: FieldLookup, T: 'static>(
+    path: &str,
+) -> Option<crate::Dynamic<P, T>> {
+    // reflection paths may start with a `.`
+    let path = path.strip_prefix('.').unwrap_or(path);
+
+    path.parse::<FieldPath<P>>().ok()?.downcast::<T>()
+}
@@ -0,0 +1,294 @@
+//! Volatile projections for memory-mapped register blocks
+//!
+//! A peripheral is described by a `#[repr(C)]` struct of its registers, which
+//! derives `Field` as usual. A [`Volatile`] pointer to the block can then be
+//! projected to each register, and every read and write through it is
+//! volatile. Bit-fields inside of a register are described by [`Bits`], and
+//! the offsets of the registers are available in `const` contexts through the
+//! generated `DESCRIPTOR`s.
+//!
+//! ```rust
+//! #![feature(raw_ref_op)]
+//! # mod __ {
+//! use gfp_core::{
+//!     registers::{Bits, Volatile},
+//!     Field,
+//! };
+//!
+//! #[derive(Field)]
+//! #[repr(C)]
+//! struct Uart {
+//!     data:   u32,
+//!     status: u32,
+//!     ctrl:   u32,
+//! }
+//!
+//! const CTRL_OFFSET: usize = uart_fields::Ctrl::<Uart>::DESCRIPTOR.offset();
+//! const ENABLE: Bits<u32> = Bits::new(0, 1);
+//! const BAUD: Bits<u32> = Bits::new(4, 4);
+//!
+//! # fn main() {
+//! let mut block = Uart { data: 0, status: 0, ctrl: 0 };
+//! let uart = unsafe { Volatile::new(&mut block as *mut Uart) };
+//!
+//! let ctrl = uart.project(Uart::FIELDS.ctrl);
+//! ctrl.bits(ENABLE).write(1);
+//! ctrl.bits(BAUD).write(0b1010);
+//!
+//! assert_eq!(ctrl.bits(BAUD).read(), 0b1010);
+//! assert_eq!(ctrl.read(), 0b1010_0001);
+//! assert_eq!(CTRL_OFFSET, 8);
+//! # }
+//! # }
+//! ```
+
+use crate::Field;
+
+use core::{cell::UnsafeCell, fmt, marker::PhantomData, mem, ptr};
+
+/// An integer type which can hold bit-fields
+pub trait Register: Copy {
+    /// Widen the register to a `u64`
+    fn to_bits(self) -> u64;
+
+    /// Truncate a `u64` to the register
+    fn from_bits(bits: u64) -> Self;
+}
+
+macro_rules! register {
+    ($($type:ty)*) => {$(
+        impl Register for $type {
+            #[inline]
+            fn to_bits(self) -> u64 {
+                self as u64
+            }
+
+            #[inline]
+            fn from_bits(bits: u64) -> Self {
+                bits as $type
+            }
+        }
+    )*};
+}
+
+register! { u8 u16 u32 u64 usize }
+
+/// A pointer to a register, or to a block of registers, which is only ever
+/// accessed with volatile reads and writes
+///
+/// The pointer is valid for `'a`, but it doesn't guarantee that the value
+/// stays the same between accesses, which is why there are no references to
+/// the value.
+pub struct Volatile<'a, T> {
+    ptr:   *mut T,
+    _mark: PhantomData<&'a UnsafeCell<T>>,
+}
+
+impl<T> Clone for Volatile<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Volatile<'_, T> {
+}
+
+impl<'a, T> Volatile<'a, T> {
+    /// Create a volatile pointer to a register block
+    ///
+    /// # Safety
+    ///
+    /// * `ptr` must be aligned, and valid for volatile reads and writes of
+    ///     `T` for `'a`
+    /// * while the pointer is in use, the value must not be accessed through
+    ///     a reference
+    pub const unsafe fn new(ptr: *mut T) -> Self {
+        Self {
+            ptr,
+            _mark: PhantomData,
+        }
+    }
+
+    /// Get the underlying pointer
+    pub fn as_ptr(self) -> *mut T {
+        self.ptr
+    }
+
+    /// Project to a register inside of the block
+    #[inline]
+    pub fn project<F: Field<Parent = T>>(
+        self,
+        field: F,
+    ) -> Volatile<'a, F::Type> {
+        Volatile {
+            // Safety
+            // * `ptr` points to a valid allocation of `T`
+            ptr:   unsafe { field.project_raw_mut(self.ptr) },
+            _mark: PhantomData,
+        }
+    }
+
+    /// Read the register
+    #[inline]
+    pub fn read(self) -> T
+    where
+        T: Copy,
+    {
+        unsafe { ptr::read_volatile(self.ptr) }
+    }
+
+    /// Write to the register
+    #[inline]
+    pub fn write(self, value: T)
+    where
+        T: Copy,
+    {
+        unsafe { ptr::write_volatile(self.ptr, value) }
+    }
+
+    /// Read the register, and write back the result of `f`
+    #[inline]
+    pub fn modify(self, f: impl FnOnce(T) -> T)
+    where
+        T: Copy,
+    {
+        self.write(f(self.read()))
+    }
+
+    /// Project to a bit-field of the register
+    #[inline]
+    pub fn bits(self, bits: Bits<T>) -> VolatileBits<'a, T>
+    where
+        T: Register,
+    {
+        VolatileBits {
+            reg: self,
+            bits,
+        }
+    }
+}
+
+impl<T> fmt::Debug for Volatile<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Volatile").field(&self.ptr).finish()
+    }
+}
+
+/// A bit-field of a register of type `T`, which is `width` bits starting at
+/// bit `shift`
+///
+/// This acts like a field of the register, but it can't be a `Field` because
+/// it doesn't have an address.
+pub struct Bits<T> {
+    shift: u32,
+    width: u32,
+    _reg:  PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for Bits<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Bits<T> {
+}
+
+impl<T> Bits<T> {
+    /// The bit-field which is `width` bits starting at bit `shift`
+    ///
+    /// # Panics
+    ///
+    /// If the bit-field doesn't fit inside of a `T`
+    pub const fn new(shift: u32, width: u32) -> Self {
+        assert!(
+            width != 0
+                && shift as usize + width as usize <= mem::size_of::<T>() * 8,
+            "bit-field does not fit inside of its register"
+        );
+
+        Self {
+            shift,
+            width,
+            _reg: PhantomData,
+        }
+    }
+
+    /// The index of the lowest bit of the bit-field
+    pub const fn shift(&self) -> u32 {
+        self.shift
+    }
+
+    /// The number of bits in the bit-field
+    pub const fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The bits of the register which are covered by the bit-field
+    pub const fn mask(&self) -> u64 {
+        (u64::MAX >> (64 - self.width)) << self.shift
+    }
+}
+
+impl<T: Register> Bits<T> {
+    /// Extract the bit-field from the value of a register
+    #[inline]
+    pub fn get(self, reg: T) -> T {
+        T::from_bits((reg.to_bits() & self.mask()) >> self.shift)
+    }
+
+    /// Replace the bit-field in the value of a register, the extra high bits
+    /// of `value` are ignored
+    #[inline]
+    pub fn set(self, reg: T, value: T) -> T {
+        let value = (value.to_bits() << self.shift) & self.mask();
+        T::from_bits(reg.to_bits() & !self.mask() | value)
+    }
+}
+
+impl<T> fmt::Debug for Bits<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Bits")
+            .field("shift", &self.shift)
+            .field("width", &self.width)
+            .finish()
+    }
+}
+
+/// A bit-field of a [`Volatile`] register, created by [`Volatile::bits`]
+///
+/// Writes are a volatile read-modify-write of the whole register.
+pub struct VolatileBits<'a, T> {
+    reg:  Volatile<'a, T>,
+    bits: Bits<T>,
+}
+
+impl<T> Clone for VolatileBits<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for VolatileBits<'_, T> {
+}
+
+impl<T: Register> VolatileBits<'_, T> {
+    /// Read the bit-field
+    #[inline]
+    pub fn read(self) -> T {
+        self.bits.get(self.reg.read())
+    }
+
+    /// Write to the bit-field, leaving the rest of the register unchanged
+    #[inline]
+    pub fn write(self, value: T) {
+        let bits = self.bits;
+        self.reg.modify(|reg| bits.set(reg, value))
+    }
+
+    /// Read the bit-field, and write back the result of `f`
+    #[inline]
+    pub fn modify(self, f: impl FnOnce(T) -> T) {
+        self.write(f(self.read()))
+    }
+}
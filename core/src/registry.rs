@@ -0,0 +1,242 @@
+//! A registry of types whose fields can be resolved by name, without `alloc`
+
+use crate::{
+    path::{self, Resolved},
+    FieldDescriptor,
+    FieldInfo,
+    FieldLookup,
+    PathError,
+};
+
+use core::{
+    any::{self, TypeId},
+    fmt,
+};
+
+/// A type in a [`Registry`]
+#[derive(Clone, Copy)]
+pub struct RegistryEntry {
+    name:        &'static str,
+    type_name:   fn() -> &'static str,
+    descriptor:  FieldDescriptor,
+    lookup:      fn(&str) -> Option<FieldInfo>,
+    field_names: fn() -> &'static [&'static str],
+}
+
+impl RegistryEntry {
+    /// The entry for `T`, which is found in the registry by `name`
+    pub const fn of<T: FieldLookup>(name: &'static str) -> Self {
+        Self {
+            name,
+            type_name: any::type_name::<T>,
+            descriptor: FieldDescriptor::identity::<T>(),
+            lookup: T::lookup_field,
+            field_names: T::field_names,
+        }
+    }
+
+    /// The name of the type in the registry
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The names of the fields of the type, in declaration order
+    pub fn field_names(&self) -> &'static [&'static str] {
+        (self.field_names)()
+    }
+
+    /// Look up the field named `name`, if there is one
+    pub fn lookup_field(&self, name: &str) -> Option<FieldInfo> {
+        (self.lookup)(name)
+    }
+}
+
+impl fmt::Debug for RegistryEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RegistryEntry")
+            .field("name", &self.name)
+            .field("type", &(self.type_name)())
+            .finish()
+    }
+}
+
+/// A fixed set of types whose fields can be resolved from strings at
+/// run-time, i.e. from the commands of a debug console
+///
+/// The registry only borrows a slice of entries, which are usually in a
+/// `static`, and resolving a path doesn't allocate, so it works on targets
+/// without `alloc`.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{Field, Registry, RegistryEntry};
+///
+/// #[derive(Field)]
+/// struct Config {
+///     net: Net,
+/// }
+///
+/// #[derive(Field)]
+/// struct Net {
+///     port: u16,
+/// }
+///
+/// static TYPES: [RegistryEntry; 2] = [
+///     RegistryEntry::of::<Config>("Config"),
+///     RegistryEntry::of::<Net>("Net"),
+/// ];
+///
+/// static REGISTRY: Registry = Registry::new(&TYPES);
+///
+/// # fn main() {
+/// let config = Config { net: Net { port: 80 } };
+///
+/// let port = REGISTRY.resolve("Config.net.port").unwrap();
+/// assert!(port.is::<u16>());
+///
+/// let ptr = unsafe { port.project_raw((&config as *const Config).cast()) };
+/// assert_eq!(unsafe { *ptr.cast::<u16>() }, 80);
+///
+/// assert_eq!(REGISTRY.get("Net").unwrap().field_names(), ["port"]);
+/// # }
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Registry<'a> {
+    entries: &'a [RegistryEntry],
+}
+
+impl<'a> Registry<'a> {
+    /// Create a registry of `entries`
+    pub const fn new(entries: &'a [RegistryEntry]) -> Self {
+        Self {
+            entries,
+        }
+    }
+
+    /// All of the types in the registry
+    pub fn entries(&self) -> &'a [RegistryEntry] {
+        self.entries
+    }
+
+    /// Find the type named `name`
+    pub fn get(&self, name: &str) -> Option<&'a RegistryEntry> {
+        self.entries.iter().find(|entry| entry.name == name)
+    }
+
+    /// Resolve a path which starts with the name of a type, followed by a
+    /// dot-separated path of its fields, i.e. `Config.net.port`
+    pub fn resolve(
+        &self,
+        path: &str,
+    ) -> Result<ResolvedField<'a>, ResolveError> {
+        let (root, fields) = match path.find('.') {
+            Some(dot) => (&path[..dot], &path[dot + 1..]),
+            None => (path, ""),
+        };
+
+        let entry = self.get(root).ok_or(ResolveError::UnknownType)?;
+
+        if fields.is_empty() {
+            return Err(ResolveError::NoFields)
+        }
+
+        let resolved = path::resolve(
+            fields.split('.'),
+            entry.lookup,
+            (entry.type_name)(),
+            entry.descriptor,
+        )
+        .map_err(ResolveError::Path)?;
+
+        Ok(ResolvedField {
+            entry,
+            resolved,
+        })
+    }
+}
+
+/// A field which was found with [`Registry::resolve`]
+pub struct ResolvedField<'a> {
+    entry:    &'a RegistryEntry,
+    resolved: Resolved,
+}
+
+impl<'a> ResolvedField<'a> {
+    /// The type the path started from
+    pub fn root(&self) -> &'a RegistryEntry {
+        self.entry
+    }
+
+    /// The names of the fields along the path, outermost first
+    pub fn segments(&self) -> &[&'static str] {
+        &self.resolved.segments[..self.resolved.depth]
+    }
+
+    /// Where the field lives inside of the root type
+    pub fn descriptor(&self) -> FieldDescriptor {
+        self.resolved.descriptor
+    }
+
+    /// The `TypeId` of the type of the field
+    pub fn type_id(&self) -> TypeId {
+        self.resolved.field.type_id()
+    }
+
+    /// The name of the type of the field, for diagnostics
+    pub fn type_name(&self) -> &'static str {
+        self.resolved.field.type_name()
+    }
+
+    /// Check if the field has the type `T`
+    pub fn is<T: 'static>(&self) -> bool {
+        self.type_id() == TypeId::of::<T>()
+    }
+
+    /// Project a raw pointer from the root type to the field
+    ///
+    /// # Safety
+    ///
+    /// * `ptr` must point to a valid allocation of the root type
+    /// * the projection is not safe to write to
+    pub unsafe fn project_raw(&self, ptr: *const u8) -> *const u8 {
+        self.resolved.descriptor.project_raw(ptr)
+    }
+}
+
+impl fmt::Debug for ResolvedField<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ResolvedField")
+            .field("root", &self.entry.name)
+            .field("segments", &self.segments())
+            .field("type", &self.type_name())
+            .field("descriptor", &self.descriptor())
+            .finish()
+    }
+}
+
+/// The error returned from [`Registry::resolve`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolveError {
+    /// The first segment doesn't name a type in the registry
+    UnknownType,
+    /// The path only names a type, without any fields
+    NoFields,
+    /// The fields of the path couldn't be resolved
+    Path(PathError),
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ResolveError::UnknownType => f.write_str("unknown type"),
+            ResolveError::NoFields => f.write_str("the path has no fields"),
+            ResolveError::Path(err) => err.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ResolveError {
+}
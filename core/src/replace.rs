@@ -0,0 +1,184 @@
+//! Replacing a pinned value, and moving the unpinned fields out of the old one
+
+use crate::{path, CompleteFieldLookup, Field, PinToPtr};
+
+use core::{
+    mem::{self, ManuallyDrop},
+    pin::Pin,
+    ptr,
+};
+use typsy::hlist::{Cons, Nil};
+
+/// A list of the unpinned fields of `P` to move out of a pinned `P` when it
+/// is replaced, see [`PinProjectExt::project_replace`]
+///
+/// This is implemented for lists of [`PinToPtr`] fields, built like the lists
+/// for `ProjectAll`, and `Taken` is the list of their values.
+///
+/// [`PinProjectExt::project_replace`]: crate::PinProjectExt::project_replace
+pub trait ReplaceList<P: CompleteFieldLookup> {
+    /// The values of the fields, in the same order
+    type Taken;
+
+    /// Mark each field as taken
+    ///
+    /// # Panics
+    ///
+    /// If a field isn't one of the fields of `P`, or if it is in the list
+    /// more than once
+    #[doc(hidden)]
+    fn mark_taken(&self, taken: &mut u64);
+
+    /// Move each field out of `parent`
+    ///
+    /// # Safety
+    ///
+    /// `parent` must be initialized, and the fields must be marked as taken,
+    /// so they won't be read or dropped again
+    #[doc(hidden)]
+    unsafe fn take(&self, parent: *const P) -> Self::Taken;
+}
+
+impl<P: CompleteFieldLookup> ReplaceList<P> for Nil {
+    type Taken = Nil;
+
+    fn mark_taken(&self, _: &mut u64) {
+    }
+
+    unsafe fn take(&self, _: *const P) -> Nil {
+        Nil
+    }
+}
+
+impl<P, F, R> ReplaceList<P> for Cons<PinToPtr<F>, R>
+where
+    P: CompleteFieldLookup,
+    F: Field<Parent = P>,
+    F::Type: Sized + 'static,
+    R: ReplaceList<P>,
+{
+    type Taken = Cons<F::Type, R::Taken>;
+
+    fn mark_taken(&self, taken: &mut u64) {
+        let descriptor = self.value.descriptor();
+        let index = path::field_index::<P, F::Type>(descriptor, *taken)
+            .expect("only the fields of the parent can be moved out");
+
+        assert!(*taken & 1 << index == 0, "the field was already moved out");
+        *taken |= 1 << index;
+
+        self.rest.mark_taken(taken)
+    }
+
+    unsafe fn take(&self, parent: *const P) -> Self::Taken {
+        Cons {
+            value: ptr::read(self.value.project_raw(parent)),
+            rest:  self.rest.take(parent),
+        }
+    }
+}
+
+/// Replace the value behind `pin` with `replacement`, moving `fields` out of
+/// the old value and dropping the rest of its fields in place
+///
+/// # Safety
+///
+/// `P` must not implement `Drop`, since it isn't run for the old value
+pub(crate) unsafe fn project_replace<P, L>(
+    pin: Pin<&mut P>,
+    replacement: P,
+    fields: L,
+) -> L::Taken
+where
+    P: CompleteFieldLookup,
+    L: ReplaceList<P>,
+{
+    assert!(
+        P::field_names().len() <= 64,
+        "only types with at most 64 fields can be replaced"
+    );
+
+    // every field is checked before any of them are moved out, so a panic
+    // leaves the old value untouched
+    let mut taken = 0;
+    fields.mark_taken(&mut taken);
+
+    // Safety
+    // * the fields which are moved out are unpinned, and every other field
+    //      is dropped in place before the value is overwritten
+    let parent: *mut P = Pin::get_unchecked_mut(pin);
+    let moved = fields.take(parent);
+
+    // this writes the replacement even if dropping a field panics, so the
+    // pinned value is never left dropped
+    let _overwrite = Overwrite {
+        target: parent,
+        value:  ManuallyDrop::new(replacement),
+    };
+
+    drop_pinned(parent, taken);
+
+    moved
+}
+
+/// Drop the fields of `parent` which aren't marked in `taken`, in declaration
+/// order, and continue with the rest of them if one of them panics, like the
+/// drop glue of a struct
+///
+/// # Safety
+///
+/// `parent` must be initialized, except for the fields marked in `taken`
+unsafe fn drop_pinned<P: CompleteFieldLookup>(parent: *mut P, mut taken: u64) {
+    for (index, name) in P::field_names().iter().enumerate() {
+        if taken & 1 << index != 0 {
+            continue
+        }
+
+        taken |= 1 << index;
+
+        let info = match P::lookup_field(name) {
+            Some(info) => info,
+            None => continue,
+        };
+
+        let rest = DropPinned {
+            parent,
+            taken,
+        };
+
+        // Safety
+        // * the field wasn't moved out, and it is marked, so it won't be
+        //      dropped again
+        info.drop_in_place(parent as *mut u8);
+        mem::forget(rest);
+    }
+}
+
+/// Drops the fields of `parent` which aren't marked in `taken` if a field
+/// panics while it is dropped
+struct DropPinned<P: CompleteFieldLookup> {
+    parent: *mut P,
+    taken:  u64,
+}
+
+impl<P: CompleteFieldLookup> Drop for DropPinned<P> {
+    fn drop(&mut self) {
+        // Safety
+        // * the fields which aren't marked are still initialized
+        unsafe { drop_pinned(self.parent, self.taken) }
+    }
+}
+
+/// Writes `value` to `target` when it is dropped
+struct Overwrite<P> {
+    target: *mut P,
+    value:  ManuallyDrop<P>,
+}
+
+impl<P> Drop for Overwrite<P> {
+    fn drop(&mut self) {
+        // Safety
+        // * every field of the old value was moved out or dropped
+        unsafe { ptr::write(self.target, ManuallyDrop::take(&mut self.value)) }
+    }
+}
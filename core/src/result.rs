@@ -0,0 +1,146 @@
+//! Projections which pass through the `Ok` value of a `Result` field
+
+use crate::{Chain, Field, ProjectTo};
+
+/// Fields whose type is a `Result`
+///
+/// This is implemented for every such field, and is used to continue a
+/// projection into the `Ok` value, see [`OkThen`].
+pub trait ResultField: Field {
+    /// The `Ok` type of the field
+    type Ok;
+
+    /// The `Err` type of the field
+    type Err;
+
+    /// Continue the projection with `then` if the field is `Ok`
+    fn ok_then<G: Field<Parent = Self::Ok>>(self, then: G) -> OkThen<Self, G>
+    where
+        Self: Sized,
+    {
+        OkThen::new(self, then)
+    }
+}
+
+impl<F: Field<Type = Result<T, E>>, T, E> ResultField for F {
+    type Err = E;
+    type Ok = T;
+}
+
+/// A path which goes through the `Ok` value of the `Result` field `A`, then
+/// projects to `B`
+///
+/// Projecting through an `OkThen` yields a `Result` of the projection to `B`
+/// and the error in `A`, so it can be short-circuited with `?` like any other
+/// `Result`.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{Field, ProjectOk, ResultField};
+///
+/// #[derive(Field)]
+/// struct State {
+///     conn: Result<Conn, String>,
+/// }
+///
+/// #[derive(Field)]
+/// struct Conn {
+///     port: u16,
+/// }
+///
+/// fn port(state: &State) -> Result<u16, &String> {
+///     let port = State::FIELDS.conn.ok_then(Conn::FIELDS.port);
+///     Ok(*state.project_ok(port)?)
+/// }
+///
+/// # fn main() {
+/// let mut state = State { conn: Ok(Conn { port: 80 }) };
+/// assert_eq!(port(&state), Ok(80));
+///
+/// state.conn = Err("refused".to_string());
+/// assert_eq!(port(&state), Err(&"refused".to_string()));
+/// # }
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+pub struct OkThen<A, B> {
+    field: A,
+    then:  B,
+}
+
+impl<A, B> OkThen<A, B> {
+    /// Project to `field`, then to `then` if `field` is `Ok`
+    pub const fn new(field: A, then: B) -> Self {
+        Self {
+            field,
+            then,
+        }
+    }
+
+    /// Continue the projection after `B` with `next`
+    pub fn chain<C: Field>(self, next: C) -> OkThen<A, Chain<B, C>>
+    where
+        B: Field<Type = C::Parent>,
+    {
+        OkThen::new(self.field, Chain::new(self.then, next))
+    }
+}
+
+/// Projects a type through a field which may be an error, like [`OkThen`]
+pub trait ProjectOk<F> {
+    /// Direct access to the field
+    type Projection;
+
+    /// Access to the error
+    type Error;
+
+    /// Project through `field`, or return the error that was found on the way
+    fn project_ok(self, field: F) -> Result<Self::Projection, Self::Error>;
+}
+
+impl<'a, A, B, T, E> ProjectOk<OkThen<A, B>> for &'a A::Parent
+where
+    A: Field<Type = Result<T, E>>,
+    B: Field<Parent = T>,
+    B::Type: 'a,
+    T: 'a,
+    E: 'a,
+{
+    type Error = &'a E;
+    type Projection = &'a B::Type;
+
+    #[inline]
+    fn project_ok(
+        self,
+        field: OkThen<A, B>,
+    ) -> Result<Self::Projection, Self::Error> {
+        match self.project_to(field.field) {
+            Ok(value) => Ok(value.project_to(field.then)),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl<'a, A, B, T, E> ProjectOk<OkThen<A, B>> for &'a mut A::Parent
+where
+    A: Field<Type = Result<T, E>>,
+    B: Field<Parent = T>,
+    B::Type: 'a,
+    T: 'a,
+    E: 'a,
+{
+    type Error = &'a mut E;
+    type Projection = &'a mut B::Type;
+
+    #[inline]
+    fn project_ok(
+        self,
+        field: OkThen<A, B>,
+    ) -> Result<Self::Projection, Self::Error> {
+        match self.project_to(field.field) {
+            Ok(value) => Ok(value.project_to(field.then)),
+            Err(err) => Err(err),
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! Temporarily overriding a field, and restoring it afterwards
+
+use crate::{Field, ProjectTo};
+
+use core::{
+    fmt,
+    mem::{self, ManuallyDrop},
+    ops::{Deref, DerefMut},
+};
+
+/// Set a field for the lifetime of a guard, which puts the old value back
+/// when it is dropped
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{Field, ScopedSet};
+///
+/// #[derive(Field)]
+/// struct Config {
+///     verbose: bool,
+///     retries: u32,
+/// }
+///
+/// # fn main() {
+/// let mut config = Config { verbose: false, retries: 3 };
+///
+/// {
+///     let config = Config::FIELDS.verbose.scoped_set(&mut config, true);
+///     assert!(config.verbose);
+/// }
+///
+/// assert!(!config.verbose);
+/// # }
+/// # }
+/// ```
+pub trait ScopedSet: Field + Sized {
+    /// Write `value` to the field of `parent`, and get a guard which gives
+    /// access to `parent` and restores the old value when it is dropped
+    fn scoped_set(
+        self,
+        parent: &mut Self::Parent,
+        value: Self::Type,
+    ) -> ScopedGuard<'_, Self> {
+        let old = mem::replace(parent.project_to(&self), value);
+
+        ScopedGuard {
+            parent,
+            field: self,
+            old: ManuallyDrop::new(old),
+        }
+    }
+}
+
+impl<F: Field> ScopedSet for F {
+}
+
+/// Restores a field when dropped, see [`ScopedSet`]
+pub struct ScopedGuard<'a, F: Field> {
+    parent: &'a mut F::Parent,
+    field:  F,
+    old:    ManuallyDrop<F::Type>,
+}
+
+impl<F: Field> ScopedGuard<'_, F> {
+    /// The value which will be restored
+    pub fn old(&self) -> &F::Type {
+        &self.old
+    }
+}
+
+impl<F: Field> Deref for ScopedGuard<'_, F> {
+    type Target = F::Parent;
+
+    fn deref(&self) -> &F::Parent {
+        self.parent
+    }
+}
+
+impl<F: Field> DerefMut for ScopedGuard<'_, F> {
+    fn deref_mut(&mut self) -> &mut F::Parent {
+        self.parent
+    }
+}
+
+impl<F: Field> Drop for ScopedGuard<'_, F> {
+    fn drop(&mut self) {
+        // Safety
+        // * `old` isn't used after this
+        let old = unsafe { ManuallyDrop::take(&mut self.old) };
+        *self.parent.project_to(&self.field) = old;
+    }
+}
+
+impl<F: Field> fmt::Debug for ScopedGuard<'_, F>
+where
+    F::Parent: fmt::Debug,
+    F::Type: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ScopedGuard")
+            .field("parent", &self.parent)
+            .field("old", &*self.old)
+            .finish()
+    }
+}
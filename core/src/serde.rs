@@ -0,0 +1,322 @@
+//! Serializing a subset of the fields of a parent, and deserializing into
+//! some of the fields of a parent in place, with `serde`
+//!
+//! [`SerializeFields`] writes a parent as a map of only the fields in a
+//! list, keyed by their dot-joined paths, so a partial response doesn't need
+//! a hand-written mirror of the parent. [`deserialize_into`] goes the other
+//! way, and only updates the fields which are present in the input.
+//!
+//! ```rust
+//! #![feature(raw_ref_op)]
+//! # mod __ {
+//! use gfp_core::{serde::SerializeFields, Field};
+//! use typsy::convert::Convert;
+//!
+//! #[derive(Field)]
+//! struct User {
+//!     name:     String,
+//!     password: String,
+//!     account:  Account,
+//! }
+//!
+//! #[derive(Field)]
+//! struct Account {
+//!     id:      u64,
+//!     balance: i64,
+//! }
+//!
+//! # fn main() {
+//! let user = User {
+//!     name:     "ferris".to_string(),
+//!     password: "hunter2".to_string(),
+//!     account:  Account { id: 7, balance: -3 },
+//! };
+//!
+//! let id = User::FIELDS.account.chain(Account::FIELDS.id);
+//! let public = (User::FIELDS.name, id).into_hlist();
+//! let json = serde_json::to_string(&SerializeFields::new(&user, public));
+//!
+//! assert_eq!(json.unwrap(), r#"{"name":"ferris","account.id":7}"#);
+//! # }
+//! # }
+//! ```
+
+use crate::{FieldInfo, FieldLookup, NamedField, ProjectTo};
+
+use core::{any, fmt};
+use serde::{
+    de::{self, DeserializeSeed, Deserializer, MapAccess, Visitor},
+    ser::{Serialize, SerializeMap, Serializer},
+};
+use typsy::hlist::{Cons, Nil};
+
+/// Serializes the fields in the list `L` of a parent as a map, see the
+/// [module docs](self)
+///
+/// The keys are the paths of the fields, as shown by
+/// [`display_path`](NamedField::display_path), and the entries are in the
+/// order of the list.
+pub struct SerializeFields<'a, P: ?Sized, L> {
+    parent: &'a P,
+    fields: L,
+}
+
+impl<'a, P: ?Sized, L: SerializeFieldList<P>> SerializeFields<'a, P, L> {
+    /// Serialize `fields` of `parent`
+    pub fn new(parent: &'a P, fields: L) -> Self {
+        Self {
+            parent,
+            fields,
+        }
+    }
+}
+
+impl<P: ?Sized, L: SerializeFieldList<P>> Serialize
+    for SerializeFields<'_, P, L>
+{
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.fields.len()))?;
+        self.fields.serialize_entries(self.parent, &mut map)?;
+        map.end()
+    }
+}
+
+/// A list of named fields of `P` whose types are `Serialize`, built like the
+/// lists for `ProjectAll`
+pub trait SerializeFieldList<P: ?Sized> {
+    /// The number of fields in the list
+    fn len(&self) -> usize;
+
+    /// Check if the list has no fields
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Serialize each field of `parent` as an entry of `map`
+    fn serialize_entries<M: SerializeMap>(
+        &self,
+        parent: &P,
+        map: &mut M,
+    ) -> Result<(), M::Error>;
+}
+
+impl<P: ?Sized> SerializeFieldList<P> for Nil {
+    fn len(&self) -> usize {
+        0
+    }
+
+    fn serialize_entries<M: SerializeMap>(
+        &self,
+        _: &P,
+        _: &mut M,
+    ) -> Result<(), M::Error> {
+        Ok(())
+    }
+}
+
+impl<F: NamedField, R> SerializeFieldList<F::Parent> for Cons<F, R>
+where
+    F::Type: Serialize,
+    R: SerializeFieldList<F::Parent>,
+{
+    fn len(&self) -> usize {
+        1 + self.rest.len()
+    }
+
+    fn serialize_entries<M: SerializeMap>(
+        &self,
+        parent: &F::Parent,
+        map: &mut M,
+    ) -> Result<(), M::Error> {
+        let value = parent.project_to(&self.value);
+        map.serialize_entry(&PathKey(&self.value), value)?;
+        self.rest.serialize_entries(parent, map)
+    }
+}
+
+/// Serializes the path of a field as a string
+struct PathKey<'a, F>(&'a F);
+
+impl<F: NamedField> Serialize for PathKey<'_, F> {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.0.display_path())
+    }
+}
+
+/// Update the fields of `parent` which are present in the map read from
+/// `deserializer`, and leave the other fields untouched
+///
+/// This gives PATCH semantics to any derived struct. Each key of the map is
+/// resolved with [`FieldLookup`], and a map for a field whose type is also
+/// derived is merged into that field the same way, so only the fields named
+/// at every level change. Any other field is replaced, which is supported
+/// for the fields whose type `#[derive(Field)]` found to implement
+/// `DeserializeOwned`, see [`FieldInfo::with_deserialize`].
+///
+/// # Errors
+///
+/// If a key isn't a field of its parent, if a field has a type which can't be
+/// replaced, or if the value doesn't deserialize. The fields before the error
+/// are already updated.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{serde::deserialize_into, Field};
+///
+/// #[derive(Field)]
+/// struct Profile {
+///     name:  String,
+///     theme: Theme,
+/// }
+///
+/// #[derive(Field)]
+/// struct Theme {
+///     dark:   bool,
+///     accent: u32,
+/// }
+///
+/// # fn main() {
+/// let mut profile = Profile {
+///     name:  "ferris".to_string(),
+///     theme: Theme { dark: false, accent: 0xff8000 },
+/// };
+///
+/// let patch = serde_json::from_str::<serde_json::Value>(
+///     r#"{ "theme": { "dark": true } }"#,
+/// );
+/// deserialize_into(&mut profile, patch.unwrap()).unwrap();
+///
+/// assert_eq!(profile.name, "ferris");
+/// assert!(profile.theme.dark);
+/// assert_eq!(profile.theme.accent, 0xff8000);
+/// # }
+/// # }
+/// ```
+pub fn deserialize_into<'de, P: FieldLookup, D: Deserializer<'de>>(
+    parent: &mut P,
+    deserializer: D,
+) -> Result<(), D::Error> {
+    deserializer.deserialize_map(Patch {
+        parent:    parent as *mut P as *mut u8,
+        lookup:    P::lookup_field,
+        type_name: any::type_name::<P>(),
+    })
+}
+
+/// Merges a map into the fields of a parent, see [`deserialize_into`]
+struct Patch {
+    parent:    *mut u8,
+    lookup:    fn(&str) -> Option<FieldInfo>,
+    type_name: &'static str,
+}
+
+impl<'de> Visitor<'de> for Patch {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a map of the fields of `{}`", self.type_name)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let key = FieldKey {
+            lookup:    self.lookup,
+            type_name: self.type_name,
+        };
+
+        while let Some(field) = map.next_key_seed(key)? {
+            // Safety
+            // * `self.parent` is a unique pointer to the parent, and `field`
+            //      was looked up from the fields of the parent
+            let ptr =
+                unsafe { field.descriptor().project_raw_mut(self.parent) };
+
+            map.next_value_seed(FieldValue {
+                field,
+                ptr,
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Resolves a key of the map to a field of the parent
+#[derive(Clone, Copy)]
+struct FieldKey {
+    lookup:    fn(&str) -> Option<FieldInfo>,
+    type_name: &'static str,
+}
+
+impl<'de> DeserializeSeed<'de> for FieldKey {
+    type Value = FieldInfo;
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<FieldInfo, D::Error> {
+        deserializer.deserialize_identifier(self)
+    }
+}
+
+impl<'de> Visitor<'de> for FieldKey {
+    type Value = FieldInfo;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the name of a field of `{}`", self.type_name)
+    }
+
+    fn visit_str<E: de::Error>(self, name: &str) -> Result<FieldInfo, E> {
+        (self.lookup)(name).ok_or_else(|| {
+            E::custom(format_args!(
+                "`{}` has no field named `{}`",
+                self.type_name, name
+            ))
+        })
+    }
+}
+
+/// Deserializes the value of a single field in place
+pub(crate) struct FieldValue {
+    pub field: FieldInfo,
+    /// A unique pointer to the initialized field
+    pub ptr:   *mut u8,
+}
+
+impl<'de> DeserializeSeed<'de> for FieldValue {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<(), D::Error> {
+        if let Some(lookup) = self.field.lookup() {
+            return deserializer.deserialize_map(Patch {
+                parent: self.ptr,
+                lookup,
+                type_name: self.field.type_name(),
+            })
+        }
+
+        let deserialize = self.field.deserialize().ok_or_else(|| {
+            de::Error::custom(format_args!(
+                "`{}` has the type `{}`, which can't be deserialized in place",
+                self.field.name(),
+                self.field.type_name()
+            ))
+        })?;
+
+        let mut erased = <dyn erased_serde::Deserializer>::erase(deserializer);
+
+        // Safety
+        // * `self.ptr` is a unique pointer to the initialized field, and
+        //      `deserialize` was recorded for the type of the field
+        unsafe { deserialize(&mut erased, self.ptr) }.map_err(de::Error::custom)
+    }
+}
@@ -0,0 +1,205 @@
+//! Projections for structs in memory which is shared with other processes
+//!
+//! A [`Shared`] pointer is projected field by field like a [`Volatile`]
+//! block, but the accessor for each field is chosen by an [`AccessPolicy`].
+//! [`VolatilePolicy`] gives [`Volatile`] accessors, and [`AtomicPolicy`]
+//! gives references to the atomic counterparts of the fields. The policy of
+//! the pointer is the default, and single fields can be accessed with another
+//! policy with [`Shared::field_as`].
+//!
+//! ```rust
+//! #![feature(raw_ref_op)]
+//! # mod __ {
+//! use core::sync::atomic::Ordering;
+//! use gfp_core::{
+//!     shared::{AtomicPolicy, Shared, VolatilePolicy},
+//!     Field,
+//! };
+//!
+//! /// The header of a ring buffer, which is shared with another process
+//! #[derive(Field)]
+//! #[repr(C)]
+//! struct RingHeader {
+//!     head:     u32,
+//!     tail:     u32,
+//!     capacity: u32,
+//! }
+//!
+//! # fn main() {
+//! let mut mapping = RingHeader { head: 0, tail: 0, capacity: 16 };
+//! let header: Shared<RingHeader, AtomicPolicy> =
+//!     unsafe { Shared::new(&mut mapping as *mut RingHeader) };
+//!
+//! header.field(RingHeader::FIELDS.tail).fetch_add(1, Ordering::Release);
+//! let head = header.field(RingHeader::FIELDS.head).load(Ordering::Acquire);
+//!
+//! let capacity = RingHeader::FIELDS.capacity;
+//! let capacity = header.field_as::<VolatilePolicy, _>(capacity).read();
+//! assert_eq!(capacity, 16);
+//! assert_eq!(head, 0);
+//! # }
+//! # }
+//! ```
+
+use crate::{atomic::HasAtomic, registers::Volatile, Field};
+
+use core::{cell::UnsafeCell, fmt, marker::PhantomData, mem};
+
+/// How the fields of a [`Shared`] pointer are accessed
+///
+/// # Safety
+///
+/// `access` must only access the field in ways which are sound while other
+/// processes access it with the same policy
+pub unsafe trait AccessPolicy<T> {
+    /// The accessor of a field of type `T`
+    type Access<'a>
+    where
+        T: 'a;
+
+    /// Create the accessor of the field at `ptr`
+    ///
+    /// # Safety
+    ///
+    /// * `ptr` must be aligned, and valid for reads and writes of `T` for
+    ///     `'a`
+    /// * while the accessor is in use, the field must not be accessed through
+    ///     a reference
+    unsafe fn access<'a>(ptr: *mut T) -> Self::Access<'a>;
+}
+
+/// Access every field with volatile reads and writes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VolatilePolicy {}
+
+unsafe impl<T> AccessPolicy<T> for VolatilePolicy {
+    type Access<'a>
+        = Volatile<'a, T>
+    where
+        T: 'a;
+
+    #[inline]
+    unsafe fn access<'a>(ptr: *mut T) -> Volatile<'a, T> {
+        Volatile::new(ptr)
+    }
+}
+
+/// Access every field through its atomic counterpart, i.e. a `u32` field as
+/// an `AtomicU32`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AtomicPolicy {}
+
+unsafe impl<T: HasAtomic> AccessPolicy<T> for AtomicPolicy {
+    type Access<'a>
+        = &'a T::Atomic
+    where
+        T: 'a;
+
+    /// # Panics
+    ///
+    /// If the field isn't aligned for its atomic counterpart
+    #[inline]
+    unsafe fn access<'a>(ptr: *mut T) -> &'a T::Atomic {
+        assert!(
+            (ptr as usize).is_multiple_of(mem::align_of::<T::Atomic>()),
+            "the field is not aligned for its atomic counterpart"
+        );
+
+        &*ptr.cast::<T::Atomic>()
+    }
+}
+
+/// A pointer to a struct in shared memory, whose fields are accessed with
+/// the policy `P`
+pub struct Shared<'a, T, P> {
+    ptr:     *mut T,
+    _mark:   PhantomData<&'a UnsafeCell<T>>,
+    _policy: PhantomData<fn() -> P>,
+}
+
+impl<T, P> Clone for Shared<'_, T, P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, P> Copy for Shared<'_, T, P> {
+}
+
+impl<'a, T, P> Shared<'a, T, P> {
+    /// Create a pointer to a struct in shared memory
+    ///
+    /// # Safety
+    ///
+    /// * `ptr` must be aligned, and valid for reads and writes of `T` for
+    ///     `'a`
+    /// * while the pointer is in use, the value must not be accessed through
+    ///     a reference
+    /// * other processes must access each field with the same policy
+    pub const unsafe fn new(ptr: *mut T) -> Self {
+        Self {
+            ptr,
+            _mark: PhantomData,
+            _policy: PhantomData,
+        }
+    }
+
+    /// Get the underlying pointer
+    pub fn as_ptr(self) -> *mut T {
+        self.ptr
+    }
+
+    /// Access the struct with the policy `Q` instead
+    pub fn with_policy<Q>(self) -> Shared<'a, T, Q> {
+        Shared {
+            ptr:     self.ptr,
+            _mark:   PhantomData,
+            _policy: PhantomData,
+        }
+    }
+
+    /// Project to a nested struct, which is accessed with the same policy
+    #[inline]
+    pub fn project<F: Field<Parent = T>>(
+        self,
+        field: F,
+    ) -> Shared<'a, F::Type, P> {
+        Shared {
+            // Safety
+            // * `ptr` points to a valid allocation of `T`
+            ptr:     unsafe { field.project_raw_mut(self.ptr) },
+            _mark:   PhantomData,
+            _policy: PhantomData,
+        }
+    }
+
+    /// Access a field with the policy of the pointer
+    #[inline]
+    pub fn field<F>(self, field: F) -> P::Access<'a>
+    where
+        F: Field<Parent = T>,
+        P: AccessPolicy<F::Type>,
+    {
+        self.field_as::<P, F>(field)
+    }
+
+    /// Access a field with the policy `Q`, for fields which aren't accessed
+    /// like the rest of the struct
+    #[inline]
+    pub fn field_as<Q, F>(self, field: F) -> Q::Access<'a>
+    where
+        F: Field<Parent = T>,
+        Q: AccessPolicy<F::Type>,
+    {
+        // Safety
+        // * `ptr` points to a valid allocation of `T`, and the field is only
+        //      accessed through the accessor
+        unsafe { Q::access(field.project_raw_mut(self.ptr)) }
+    }
+}
+
+impl<T, P> fmt::Debug for Shared<'_, T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Shared").field(&self.ptr).finish()
+    }
+}
@@ -0,0 +1,97 @@
+//! Disjoint mutable borrows of fields, checked at runtime and scoped by a
+//! generative lifetime brand
+
+use crate::{project::is_overlapping, Field};
+
+use core::{marker::PhantomData, ops::Range};
+use std::vec::Vec;
+
+/// An invariant lifetime, which is unique to each call to [`split`]
+#[derive(Clone, Copy)]
+struct Brand<'id>(PhantomData<fn(&'id ()) -> &'id ()>);
+
+/// Hands out disjoint mutable references to the fields of a `P`
+///
+/// A `Splitter` is only available inside of the closure passed to [`split`],
+/// and it is branded with a lifetime `'id` that is unique to that call, so it
+/// can't escape the closure or be swapped with the `Splitter` of another
+/// parent. It remembers the offsets of every claimed field, so fields chosen
+/// at run-time (for example [`Dynamic`](crate::Dynamic) fields), any number
+/// of them, can be split off without knowing up front that they are
+/// disjoint.
+pub struct Splitter<'id, 'a, P> {
+    ptr:     *mut P,
+    claimed: Vec<Range<usize>>,
+    _brand:  Brand<'id>,
+    _mark:   PhantomData<&'a mut P>,
+}
+
+/// Split `parent` into disjoint mutable references to its fields
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{split, Field};
+///
+/// #[derive(Field)]
+/// struct Foo {
+///     bar: u32,
+///     tap: u64,
+/// }
+///
+/// # fn main() {
+/// let mut foo = Foo { bar: 0, tap: 0 };
+/// let fields = Foo::fields();
+///
+/// split(&mut foo, |splitter| {
+///     let bar = splitter.claim(fields.bar).unwrap();
+///     let tap = splitter.claim(fields.tap.dynamic()).unwrap();
+///     assert!(splitter.claim(fields.bar).is_none());
+///
+///     *bar = 1;
+///     *tap = 2;
+/// });
+///
+/// assert_eq!(foo.bar, 1);
+/// assert_eq!(foo.tap, 2);
+/// # }
+/// # }
+/// ```
+pub fn split<'a, P, R>(
+    parent: &'a mut P,
+    f: impl for<'id> FnOnce(&mut Splitter<'id, 'a, P>) -> R,
+) -> R {
+    f(&mut Splitter {
+        ptr:     parent,
+        claimed: Vec::new(),
+        _brand:  Brand(PhantomData),
+        _mark:   PhantomData,
+    })
+}
+
+impl<'id, 'a, P> Splitter<'id, 'a, P> {
+    /// Claim a field, or `None` if it overlaps an already claimed field
+    pub fn claim<F: Field<Parent = P>>(
+        &mut self,
+        field: F,
+    ) -> Option<&'a mut F::Type> {
+        let range = field.range();
+
+        if self
+            .claimed
+            .iter()
+            .any(|claimed| is_overlapping(claimed.clone(), range.clone()))
+        {
+            return None
+        }
+
+        // Safety
+        // * `ptr` came from a `&'a mut P`, so it is valid for `'a`
+        // * `range` is disjoint from every reference handed out so far, and
+        //      it will be checked against every reference handed out later
+        let value = unsafe { &mut *field.project_raw_mut(self.ptr) };
+        self.claimed.push(range);
+
+        Some(value)
+    }
+}
@@ -0,0 +1,193 @@
+//! `Field`s for the types in `core` that have public fields
+//!
+//! `RangeInclusive` only exposes its bounds through accessors, and the
+//! variants of `Option`, `Result`, and `Poll` aren't always present, so these
+//! can't be described by a `Field`. Instead they are projected to with
+//! [`TryProjectTo`], the bounds of a `RangeInclusive` only through shared
+//! references, and the variants through shared and mutable references.
+//!
+//! ```rust
+//! use gfp_core::{
+//!     std_fields::{OptionSome, RangeInclusiveEnd, ResultErr},
+//!     TryProjectTo,
+//! };
+//!
+//! let mut limit = Some(10);
+//! *(&mut limit).try_project_to(OptionSome).unwrap() += 1;
+//! assert_eq!(limit, Some(11));
+//!
+//! let parsed = "x".parse::<u8>();
+//! assert!((&parsed).try_project_to(ResultErr).is_some());
+//!
+//! assert_eq!((&(1..=5)).try_project_to(RangeInclusiveEnd), Some(&5));
+//! ```
+
+use crate::{
+    derive::{name_hash, FieldByName, Invariant},
+    AutoUnpin,
+    Field,
+    NamedField,
+    TryProjectTo,
+};
+
+use core::{
+    cmp::Reverse,
+    iter::{self, Once},
+    num::{Saturating, Wrapping},
+    ops::{Range, RangeFrom, RangeInclusive, RangeTo, RangeToInclusive},
+    task::Poll,
+};
+
+macro_rules! std_field {
+    ($(
+        $(#[$meta:meta])*
+        $name:ident<$param:ident>($parent:ty => $type:ty) $field:tt
+    )*) => {$(
+        $(#[$meta])*
+        pub struct $name<$param>(Invariant<$param>);
+
+        impl<$param> $name<$param> {
+            /// Default initializer for the field
+            pub const NEW: Self = Self(Invariant::INIT);
+        }
+
+        impl<$param> Clone for $name<$param> {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<$param> Copy for $name<$param> {
+        }
+
+        unsafe impl<$param> Field for $name<$param> {
+            type Parent = $parent;
+            type Type = $type;
+
+            #[inline]
+            unsafe fn project_raw(
+                &self,
+                ptr: *const Self::Parent,
+            ) -> *const Self::Type {
+                &raw const (*ptr).$field
+            }
+
+            #[inline]
+            unsafe fn project_raw_mut(
+                &self,
+                ptr: *mut Self::Parent,
+            ) -> *mut Self::Type {
+                &raw mut (*ptr).$field
+            }
+        }
+
+        impl<$param> AutoUnpin for $name<$param> {
+        }
+
+        impl<$param> NamedField for $name<$param> {
+            type Name = Once<&'static str>;
+
+            #[inline]
+            fn name(&self) -> Self::Name {
+                iter::once(stringify!($field))
+            }
+        }
+
+        impl<$param> FieldByName<{ name_hash(stringify!($field)) }> for $parent {
+            type Field = $name<$param>;
+
+            const FIELD: Self::Field = $name::NEW;
+        }
+    )*};
+}
+
+std_field! {
+    /// The `start` field of a [`Range`]
+    RangeStart<Idx>(Range<Idx> => Idx) start
+    /// The `end` field of a [`Range`]
+    RangeEnd<Idx>(Range<Idx> => Idx) end
+    /// The `start` field of a [`RangeFrom`]
+    RangeFromStart<Idx>(RangeFrom<Idx> => Idx) start
+    /// The `end` field of a [`RangeTo`]
+    RangeToEnd<Idx>(RangeTo<Idx> => Idx) end
+    /// The `end` field of a [`RangeToInclusive`]
+    RangeToInclusiveEnd<Idx>(RangeToInclusive<Idx> => Idx) end
+    /// The value inside of a [`Wrapping`]
+    WrappingValue<T>(Wrapping<T> => T) 0
+    /// The value inside of a [`Saturating`]
+    SaturatingValue<T>(Saturating<T> => T) 0
+    /// The value inside of a [`Reverse`]
+    ReverseValue<T>(Reverse<T> => T) 0
+}
+
+/// The `start` bound of a [`RangeInclusive`], through its accessor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RangeInclusiveStart;
+
+/// The `end` bound of a [`RangeInclusive`], through its accessor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RangeInclusiveEnd;
+
+impl<'a, Idx> TryProjectTo<RangeInclusiveStart> for &'a RangeInclusive<Idx> {
+    type Projection = &'a Idx;
+
+    #[inline]
+    fn try_project_to(self, _: RangeInclusiveStart) -> Option<&'a Idx> {
+        Some(self.start())
+    }
+}
+
+impl<'a, Idx> TryProjectTo<RangeInclusiveEnd> for &'a RangeInclusive<Idx> {
+    type Projection = &'a Idx;
+
+    #[inline]
+    fn try_project_to(self, _: RangeInclusiveEnd) -> Option<&'a Idx> {
+        Some(self.end())
+    }
+}
+
+macro_rules! std_prism {
+    ($(
+        $(#[$meta:meta])*
+        $name:ident<$($param:ident),*>($parent:ty => $type:ty) $variant:path
+    )*) => {$(
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct $name;
+
+        impl<'a, $($param),*> TryProjectTo<$name> for &'a $parent {
+            type Projection = &'a $type;
+
+            #[inline]
+            fn try_project_to(self, _: $name) -> Option<Self::Projection> {
+                match self {
+                    $variant(value) => Some(value),
+                    _ => None,
+                }
+            }
+        }
+
+        impl<'a, $($param),*> TryProjectTo<$name> for &'a mut $parent {
+            type Projection = &'a mut $type;
+
+            #[inline]
+            fn try_project_to(self, _: $name) -> Option<Self::Projection> {
+                match self {
+                    $variant(value) => Some(value),
+                    _ => None,
+                }
+            }
+        }
+    )*};
+}
+
+std_prism! {
+    /// The value inside of an [`Option`], if it is `Some`
+    OptionSome<T>(Option<T> => T) Some
+    /// The value inside of a [`Result`], if it is `Ok`
+    ResultOk<T, E>(Result<T, E> => T) Ok
+    /// The error inside of a [`Result`], if it is `Err`
+    ResultErr<T, E>(Result<T, E> => E) Err
+    /// The value inside of a [`Poll`], if it is `Ready`
+    PollReady<T>(Poll<T> => T) Poll::Ready
+}
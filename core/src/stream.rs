@@ -0,0 +1,167 @@
+//! `Stream` and `Sink` access to the pinned fields of a pinned parent
+
+use crate::{Field, PinToPin, ProjectTo};
+
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_core::Stream;
+use futures_sink::Sink;
+
+/// `Stream` and `Sink` methods for pinned fields of `Pin<&mut T>`, the same
+/// as [`PinProjectExt::poll_field`](crate::PinProjectExt::poll_field) is for
+/// futures
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use core::{
+///     pin::Pin,
+///     task::{Context, Poll},
+/// };
+/// use futures_core::Stream;
+/// use gfp_core::{Field, PinStreamExt, PinToPin};
+///
+/// /// Doubles every item of the inner stream
+/// #[derive(Field)]
+/// struct Doubled<S> {
+///     inner: S,
+/// }
+///
+/// impl<S: Stream<Item = u32>> Stream for Doubled<S> {
+///     type Item = u32;
+///
+///     fn poll_next(
+///         self: Pin<&mut Self>,
+///         cx: &mut Context<'_>,
+///     ) -> Poll<Option<u32>> {
+///         let inner = unsafe { PinToPin::new_unchecked(Self::FIELDS.inner) };
+///
+///         self.poll_next_field(inner, cx)
+///             .map(|item| item.map(|item| item * 2))
+///     }
+/// }
+/// # fn main() {}
+/// # }
+/// ```
+pub trait PinStreamExt<'a, T> {
+    /// Poll the next item of the pinned stream in `field`
+    fn poll_next_field<F>(
+        self,
+        field: PinToPin<F>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<<F::Type as Stream>::Item>>
+    where
+        F: Field<Parent = T>,
+        F::Type: Stream;
+
+    /// Check if the pinned sink in `field` is ready to receive an item
+    fn poll_ready_field<F, Item>(
+        self,
+        field: PinToPin<F>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), <F::Type as Sink<Item>>::Error>>
+    where
+        F: Field<Parent = T>,
+        F::Type: Sink<Item>;
+
+    /// Send `item` to the pinned sink in `field`, which must have been made
+    /// ready with [`poll_ready_field`](PinStreamExt::poll_ready_field)
+    fn start_send_field<F, Item>(
+        self,
+        field: PinToPin<F>,
+        item: Item,
+    ) -> Result<(), <F::Type as Sink<Item>>::Error>
+    where
+        F: Field<Parent = T>,
+        F::Type: Sink<Item>;
+
+    /// Flush the pinned sink in `field`
+    fn poll_flush_field<F, Item>(
+        self,
+        field: PinToPin<F>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), <F::Type as Sink<Item>>::Error>>
+    where
+        F: Field<Parent = T>,
+        F::Type: Sink<Item>;
+
+    /// Close the pinned sink in `field`
+    fn poll_close_field<F, Item>(
+        self,
+        field: PinToPin<F>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), <F::Type as Sink<Item>>::Error>>
+    where
+        F: Field<Parent = T>,
+        F::Type: Sink<Item>;
+}
+
+impl<'a, T> PinStreamExt<'a, T> for Pin<&'a mut T> {
+    #[inline]
+    fn poll_next_field<F>(
+        self,
+        field: PinToPin<F>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<<F::Type as Stream>::Item>>
+    where
+        F: Field<Parent = T>,
+        F::Type: Stream,
+    {
+        self.project_to(field).poll_next(cx)
+    }
+
+    #[inline]
+    fn poll_ready_field<F, Item>(
+        self,
+        field: PinToPin<F>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), <F::Type as Sink<Item>>::Error>>
+    where
+        F: Field<Parent = T>,
+        F::Type: Sink<Item>,
+    {
+        self.project_to(field).poll_ready(cx)
+    }
+
+    #[inline]
+    fn start_send_field<F, Item>(
+        self,
+        field: PinToPin<F>,
+        item: Item,
+    ) -> Result<(), <F::Type as Sink<Item>>::Error>
+    where
+        F: Field<Parent = T>,
+        F::Type: Sink<Item>,
+    {
+        self.project_to(field).start_send(item)
+    }
+
+    #[inline]
+    fn poll_flush_field<F, Item>(
+        self,
+        field: PinToPin<F>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), <F::Type as Sink<Item>>::Error>>
+    where
+        F: Field<Parent = T>,
+        F::Type: Sink<Item>,
+    {
+        self.project_to(field).poll_flush(cx)
+    }
+
+    #[inline]
+    fn poll_close_field<F, Item>(
+        self,
+        field: PinToPin<F>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), <F::Type as Sink<Item>>::Error>>
+    where
+        F: Field<Parent = T>,
+        F::Type: Sink<Item>,
+    {
+        self.project_to(field).poll_close(cx)
+    }
+}
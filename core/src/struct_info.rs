@@ -0,0 +1,113 @@
+//! Compile-time layout information of derived structs and unions
+
+use crate::FieldDescriptor;
+
+use core::mem;
+
+/// A type whose layout is known at compile time
+///
+/// This is implemented by `#[derive(Field)]` for non-generic structs and
+/// unions, and is what the C header export in `gfp_core::ffi` is built on.
+pub trait HasStructInfo {
+    /// The layout of the type and all of its fields
+    const STRUCT_INFO: StructInfo;
+}
+
+/// The layout of a type and its fields, in declaration order
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{Field, HasStructInfo};
+///
+/// #[derive(Field)]
+/// #[repr(C)]
+/// struct Packet {
+///     kind: u8,
+///     len:  u32,
+/// }
+///
+/// # fn main() {
+/// let info = Packet::STRUCT_INFO;
+///
+/// assert_eq!(info.name(), "Packet");
+/// assert_eq!(info.size(), 8);
+/// assert_eq!(info.fields()[1].0, "len");
+/// assert_eq!(info.fields()[1].1.offset(), 4);
+/// # }
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StructInfo {
+    name:   &'static str,
+    size:   usize,
+    align:  usize,
+    fields: &'static [(&'static str, FieldDescriptor)],
+    labels: &'static [Option<&'static str>],
+}
+
+impl StructInfo {
+    /// Describe the type `T`, named `name`, which has `fields`
+    pub const fn new<T>(
+        name: &'static str,
+        fields: &'static [(&'static str, FieldDescriptor)],
+    ) -> Self {
+        Self {
+            name,
+            size: mem::size_of::<T>(),
+            align: mem::align_of::<T>(),
+            fields,
+            labels: &[],
+        }
+    }
+
+    /// Give the fields human-readable labels, in declaration order
+    ///
+    /// Fields without a label, including any past the end of `labels`, are
+    /// labelled with their names.
+    pub const fn with_display_names(
+        self,
+        labels: &'static [Option<&'static str>],
+    ) -> Self {
+        Self {
+            labels,
+            ..self
+        }
+    }
+
+    /// The name of the type
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The size of the type in bytes
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The alignment of the type in bytes
+    pub const fn align(&self) -> usize {
+        self.align
+    }
+
+    /// The names of the fields, and where they live inside of the type
+    pub const fn fields(&self) -> &'static [(&'static str, FieldDescriptor)] {
+        self.fields
+    }
+
+    /// The human-readable label of the field at `index`, or its name if it
+    /// doesn't have one
+    ///
+    /// # Panics
+    ///
+    /// If there is no field at `index`
+    pub const fn display_name(&self, index: usize) -> &'static str {
+        if index < self.labels.len() {
+            if let Some(label) = self.labels[index] {
+                return label
+            }
+        }
+
+        self.fields[index].0
+    }
+}
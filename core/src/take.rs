@@ -0,0 +1,166 @@
+//! Temporarily moving a field out of a borrowed parent
+
+use crate::{Field, ProjectAll};
+
+use core::{mem, ptr};
+use typsy::map::{Map, Mapped};
+
+/// Replace a field with a function of its old value, through a `&mut` to its
+/// parent
+///
+/// This is the field version of `replace_with`, which is useful for state
+/// machines whose transitions consume the old state. While the closure runs
+/// the field is moved out, so there is no value to leave behind if it
+/// panics, and each method has a policy for that case:
+///
+/// * [`with_taken`](TakeField::with_taken) aborts the process
+/// * [`with_taken_or_default`](TakeField::with_taken_or_default) fills the
+///   field with `Default::default()`, then continues to unwind
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{Field, TakeField};
+///
+/// enum State {
+///     Idle(String),
+///     Running(String, u32),
+/// }
+///
+/// #[derive(Field)]
+/// struct Machine {
+///     state: State,
+/// }
+///
+/// # fn main() {
+/// let mut machine = Machine { state: State::Idle("job".to_string()) };
+///
+/// Machine::FIELDS.state.with_taken(&mut machine, |state| match state {
+///     State::Idle(name) => State::Running(name, 0),
+///     running => running,
+/// });
+///
+/// assert!(matches!(machine.state, State::Running(_, 0)));
+/// # }
+/// # }
+/// ```
+pub trait TakeField: Field {
+    /// Move the field out of `parent`, and write back the result of `f`
+    ///
+    /// # Aborts
+    ///
+    /// If `f` panics, because the field can't be left moved out
+    fn with_taken<F>(&self, parent: &mut Self::Parent, f: F)
+    where
+        F: FnOnce(Self::Type) -> Self::Type,
+    {
+        // Safety
+        // * `parent` is a valid, unique pointer to `Parent`
+        let slot = unsafe { self.project_raw_mut(parent) };
+        let bomb = AbortOnUnwind;
+
+        // Safety
+        // * the old value is moved out, and if `f` panics `bomb` aborts the
+        //      process before anything can observe the moved-out field
+        unsafe { ptr::write(slot, f(ptr::read(slot))) }
+
+        mem::forget(bomb);
+    }
+
+    /// Move the field out of `parent`, and write back the result of `f`
+    ///
+    /// If `f` panics, the field is set to `Default::default()` before the
+    /// panic continues.
+    fn with_taken_or_default<F>(&self, parent: &mut Self::Parent, f: F)
+    where
+        Self::Type: Default,
+        F: FnOnce(Self::Type) -> Self::Type,
+    {
+        // Safety
+        // * `parent` is a valid, unique pointer to `Parent`
+        let slot = unsafe { self.project_raw_mut(parent) };
+        let fill = FillOnUnwind(slot);
+
+        // Safety
+        // * the old value is moved out, and if `f` panics `fill` writes a new
+        //      value before anything can observe the moved-out field
+        unsafe { ptr::write(slot, f(ptr::read(slot))) }
+
+        mem::forget(fill);
+    }
+}
+
+impl<F: Field> TakeField for F {
+}
+
+/// Take every field in the list `fields` out of `parent`, leaving
+/// `Default::default()` in their place
+///
+/// This is `mem::take` for several fields at once. The fields are projected
+/// with `ProjectAll`, so this panics if any of them overlap.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::{take_set, Field};
+/// use typsy::convert::Convert;
+///
+/// #[derive(Field)]
+/// struct Stats {
+///     hits:   u32,
+///     misses: u32,
+///     name:   String,
+/// }
+///
+/// # fn main() {
+/// let mut stats = Stats { hits: 3, misses: 1, name: "cache".to_string() };
+///
+/// let fields = Stats::fields();
+/// let fields = (fields.hits, fields.misses).into_hlist();
+/// let typsy::hlist_pat!(hits, misses) = take_set(&mut stats, fields);
+///
+/// assert_eq!((hits, misses), (3, 1));
+/// assert_eq!((stats.hits, stats.misses), (0, 0));
+/// assert_eq!(stats.name, "cache");
+/// # }
+/// # }
+/// ```
+pub fn take_set<'a, P, L>(
+    parent: &'a mut P,
+    fields: L,
+) -> Mapped<<&'a mut P as ProjectAll<P, L>>::Projection, TakeDefault>
+where
+    &'a mut P: ProjectAll<P, L>,
+    <&'a mut P as ProjectAll<P, L>>::Projection: Map<TakeDefault>,
+{
+    parent.project_all(fields).map(TakeDefault)
+}
+
+/// Takes the value out of each reference of a list, see [`take_set`]
+pub struct TakeDefault;
+
+typsy::call! {
+    fn['a, T: Default](&mut self: TakeDefault, value: &'a mut T) -> T {
+        mem::take(value)
+    }
+}
+
+/// Panics when dropped, which aborts if it is dropped during unwinding
+struct AbortOnUnwind;
+
+impl Drop for AbortOnUnwind {
+    fn drop(&mut self) {
+        panic!("panicked while a field was taken, aborting")
+    }
+}
+
+/// Writes `T::default()` to the pointer when dropped
+struct FillOnUnwind<T: Default>(*mut T);
+
+impl<T: Default> Drop for FillOnUnwind<T> {
+    fn drop(&mut self) {
+        // Safety
+        // * the pointer is to a field whose value was moved out
+        unsafe { ptr::write(self.0, T::default()) }
+    }
+}
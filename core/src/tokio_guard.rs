@@ -0,0 +1,92 @@
+//! Projects through the owned guards of `tokio::sync`
+//!
+//! The owned guards keep the lock alive with an `Arc`, so the mapped guards
+//! are `'static`, and can be moved into a spawned task which only needs one
+//! field of the shared state.
+
+use crate::ProjectableGuard;
+
+use tokio::sync::{
+    OwnedMappedMutexGuard,
+    OwnedMutexGuard,
+    OwnedRwLockMappedWriteGuard,
+    OwnedRwLockReadGuard,
+    OwnedRwLockWriteGuard,
+};
+
+unsafe impl<P, T> ProjectableGuard<T> for OwnedMutexGuard<P> {
+    type Target = P;
+    type Mapped = OwnedMappedMutexGuard<P, T>;
+
+    unsafe fn map_raw(
+        self,
+        project: impl FnOnce(*mut P) -> *mut T,
+    ) -> Self::Mapped {
+        OwnedMutexGuard::map(self, |parent| &mut *project(parent))
+    }
+}
+
+unsafe impl<P: ?Sized, U, T> ProjectableGuard<T>
+    for OwnedMappedMutexGuard<P, U>
+{
+    type Target = U;
+    type Mapped = OwnedMappedMutexGuard<P, T>;
+
+    unsafe fn map_raw(
+        self,
+        project: impl FnOnce(*mut U) -> *mut T,
+    ) -> Self::Mapped {
+        OwnedMappedMutexGuard::map(self, |parent| &mut *project(parent))
+    }
+}
+
+unsafe impl<P: ?Sized, U, T> ProjectableGuard<T>
+    for OwnedRwLockReadGuard<P, U>
+{
+    type Target = U;
+    type Mapped = OwnedRwLockReadGuard<P, T>;
+
+    unsafe fn map_raw(
+        self,
+        project: impl FnOnce(*mut U) -> *mut T,
+    ) -> Self::Mapped {
+        // the pointer is only used to find the field, which is only read
+        OwnedRwLockReadGuard::map(self, |parent| {
+            &*project(parent as *const U as *mut U)
+        })
+    }
+}
+
+unsafe impl<P, T> ProjectableGuard<T> for OwnedRwLockWriteGuard<P> {
+    type Target = P;
+    type Mapped = OwnedRwLockMappedWriteGuard<P, T>;
+
+    unsafe fn map_raw(
+        self,
+        project: impl FnOnce(*mut P) -> *mut T,
+    ) -> Self::Mapped {
+        OwnedRwLockWriteGuard::map(self, |parent| &mut *project(parent))
+    }
+}
+
+unsafe impl<P: ?Sized, U, T> ProjectableGuard<T>
+    for OwnedRwLockMappedWriteGuard<P, U>
+{
+    type Target = U;
+    type Mapped = OwnedRwLockMappedWriteGuard<P, T>;
+
+    unsafe fn map_raw(
+        self,
+        project: impl FnOnce(*mut U) -> *mut T,
+    ) -> Self::Mapped {
+        OwnedRwLockMappedWriteGuard::map(self, |parent| &mut *project(parent))
+    }
+}
+
+crate::projectable_guard! {
+    impl[P] OwnedMutexGuard<P>,
+    impl[P: ?Sized, U] OwnedMappedMutexGuard<P, U>,
+    impl[P: ?Sized, U] OwnedRwLockReadGuard<P, U>,
+    impl[P] OwnedRwLockWriteGuard<P>,
+    impl[P: ?Sized, U] OwnedRwLockMappedWriteGuard<P, U>,
+}
@@ -0,0 +1,170 @@
+//! Fields for the elements of tuples with up to 16 elements
+
+use crate::{
+    derive::{name_hash, FieldByName, Invariant},
+    AutoUnpin,
+    Field,
+    NamedField,
+};
+
+use core::iter::{self, Once};
+
+macro_rules! tuple_markers {
+    ($($(#[$meta:meta])* $name:ident)*) => {$(
+        $(#[$meta])*
+        ///
+        /// The parameter `T` is the tuple type, for example
+        /// `At1::<(u8, u16)>::NEW` is the `u16` in a `(u8, u16)`
+        pub struct $name<T>(Invariant<T>);
+
+        impl<T> $name<T> {
+            /// Default initializer for the field
+            pub const NEW: Self = Self(Invariant::INIT);
+        }
+
+        impl<T> Clone for $name<T> {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<T> Copy for $name<T> {
+        }
+    )*};
+}
+
+macro_rules! tuple_fields {
+    ($($tuple:tt => { $($name:ident $index:tt $type:ident),* })*) => {
+        $($(tuple_fields!(@impl $tuple $name $index $type);)*)*
+    };
+    (@impl ($($param:ident),*) $name:ident $index:tt $type:ident) => {
+        unsafe impl<$($param),*> Field for $name<($($param,)*)> {
+            type Parent = ($($param,)*);
+            type Type = $type;
+
+            #[inline]
+            unsafe fn project_raw(
+                &self,
+                ptr: *const Self::Parent,
+            ) -> *const Self::Type {
+                &raw const (*ptr).$index
+            }
+
+            #[inline]
+            unsafe fn project_raw_mut(
+                &self,
+                ptr: *mut Self::Parent,
+            ) -> *mut Self::Type {
+                &raw mut (*ptr).$index
+            }
+        }
+
+        impl<$($param),*> AutoUnpin for $name<($($param,)*)> {
+        }
+
+        impl<$($param),*> NamedField for $name<($($param,)*)> {
+            type Name = Once<&'static str>;
+
+            #[inline]
+            fn name(&self) -> Self::Name {
+                iter::once(stringify!($index))
+            }
+        }
+
+        impl<$($param),*> FieldByName<{ name_hash(stringify!($index)) }>
+            for ($($param,)*)
+        {
+            type Field = $name<($($param,)*)>;
+
+            const FIELD: Self::Field = $name::NEW;
+        }
+    };
+}
+
+tuple_markers! {
+    /// The element at index 0 of a tuple
+    At0
+    /// The element at index 1 of a tuple
+    At1
+    /// The element at index 2 of a tuple
+    At2
+    /// The element at index 3 of a tuple
+    At3
+    /// The element at index 4 of a tuple
+    At4
+    /// The element at index 5 of a tuple
+    At5
+    /// The element at index 6 of a tuple
+    At6
+    /// The element at index 7 of a tuple
+    At7
+    /// The element at index 8 of a tuple
+    At8
+    /// The element at index 9 of a tuple
+    At9
+    /// The element at index 10 of a tuple
+    At10
+    /// The element at index 11 of a tuple
+    At11
+    /// The element at index 12 of a tuple
+    At12
+    /// The element at index 13 of a tuple
+    At13
+    /// The element at index 14 of a tuple
+    At14
+    /// The element at index 15 of a tuple
+    At15
+}
+
+tuple_fields! {
+    (T0) => { At0 0 T0 }
+    (T0, T1) => { At0 0 T0, At1 1 T1 }
+    (T0, T1, T2) => { At0 0 T0, At1 1 T1, At2 2 T2 }
+    (T0, T1, T2, T3) => { At0 0 T0, At1 1 T1, At2 2 T2, At3 3 T3 }
+    (T0, T1, T2, T3, T4) => { At0 0 T0, At1 1 T1, At2 2 T2, At3 3 T3, At4 4 T4 }
+    (T0, T1, T2, T3, T4, T5) => {
+        At0 0 T0, At1 1 T1, At2 2 T2, At3 3 T3, At4 4 T4, At5 5 T5
+    }
+    (T0, T1, T2, T3, T4, T5, T6) => {
+        At0 0 T0, At1 1 T1, At2 2 T2, At3 3 T3, At4 4 T4, At5 5 T5, At6 6 T6
+    }
+    (T0, T1, T2, T3, T4, T5, T6, T7) => {
+        At0 0 T0, At1 1 T1, At2 2 T2, At3 3 T3, At4 4 T4, At5 5 T5, At6 6 T6,
+        At7 7 T7
+    }
+    (T0, T1, T2, T3, T4, T5, T6, T7, T8) => {
+        At0 0 T0, At1 1 T1, At2 2 T2, At3 3 T3, At4 4 T4, At5 5 T5, At6 6 T6,
+        At7 7 T7, At8 8 T8
+    }
+    (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9) => {
+        At0 0 T0, At1 1 T1, At2 2 T2, At3 3 T3, At4 4 T4, At5 5 T5, At6 6 T6,
+        At7 7 T7, At8 8 T8, At9 9 T9
+    }
+    (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10) => {
+        At0 0 T0, At1 1 T1, At2 2 T2, At3 3 T3, At4 4 T4, At5 5 T5, At6 6 T6,
+        At7 7 T7, At8 8 T8, At9 9 T9, At10 10 T10
+    }
+    (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11) => {
+        At0 0 T0, At1 1 T1, At2 2 T2, At3 3 T3, At4 4 T4, At5 5 T5, At6 6 T6,
+        At7 7 T7, At8 8 T8, At9 9 T9, At10 10 T10, At11 11 T11
+    }
+    (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12) => {
+        At0 0 T0, At1 1 T1, At2 2 T2, At3 3 T3, At4 4 T4, At5 5 T5, At6 6 T6,
+        At7 7 T7, At8 8 T8, At9 9 T9, At10 10 T10, At11 11 T11, At12 12 T12
+    }
+    (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13) => {
+        At0 0 T0, At1 1 T1, At2 2 T2, At3 3 T3, At4 4 T4, At5 5 T5, At6 6 T6,
+        At7 7 T7, At8 8 T8, At9 9 T9, At10 10 T10, At11 11 T11, At12 12 T12,
+        At13 13 T13
+    }
+    (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14) => {
+        At0 0 T0, At1 1 T1, At2 2 T2, At3 3 T3, At4 4 T4, At5 5 T5, At6 6 T6,
+        At7 7 T7, At8 8 T8, At9 9 T9, At10 10 T10, At11 11 T11, At12 12 T12,
+        At13 13 T13, At14 14 T14
+    }
+    (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15) => {
+        At0 0 T0, At1 1 T1, At2 2 T2, At3 3 T3, At4 4 T4, At5 5 T5, At6 6 T6,
+        At7 7 T7, At8 8 T8, At9 9 T9, At10 10 T10, At11 11 T11, At12 12 T12,
+        At13 13 T13, At14 14 T14, At15 15 T15
+    }
+}
@@ -1,4 +1,6 @@
-use crate::Field;
+use crate::{project::check_projection, Field};
+
+use core::mem;
 
 use typsy::{
     hlist::{Cons, Nil},
@@ -7,10 +9,10 @@ use typsy::{
 
 pub type Projected<Parent, F> = Mapped<F, ProjectRaw<Parent>>;
 pub type ProjectedMut<Parent, F> = Mapped<F, ProjectRawMut<Parent>>;
-pub struct ProjectRaw<Parent>(*const Parent);
-pub struct ProjectRawMut<Parent>(*mut Parent);
+pub struct ProjectRaw<Parent: ?Sized>(*const Parent, usize);
+pub struct ProjectRawMut<Parent: ?Sized>(*mut Parent, usize);
 
-impl<Parent> ProjectRaw<Parent> {
+impl<Parent: ?Sized> ProjectRaw<Parent> {
     /// projects the raw pointer from the `Parent` type to the field `Type`
     ///
     /// # Safety
@@ -18,38 +20,54 @@ impl<Parent> ProjectRaw<Parent> {
     /// * `ptr` must point to a valid, initialized allocation of `Parent`
     /// * the projection is not safe to write to
     pub unsafe fn new(parent: *const Parent) -> Self {
-        Self(parent)
+        Self(parent, mem::size_of_val(&*parent))
     }
 }
 
-impl<Parent> ProjectRawMut<Parent> {
+impl<Parent: ?Sized> ProjectRawMut<Parent> {
     /// projects the raw pointer from the `Parent` type to the field `Type`
     ///
     /// # Safety
     ///
     /// * `ptr` must point to a valid, initialized allocation of `Parent`
     pub unsafe fn new(parent: *mut Parent) -> Self {
-        Self(parent)
+        Self(parent, mem::size_of_val(&*parent))
+    }
+}
+
+impl<Parent> ProjectRawMut<Parent> {
+    /// projects the raw pointer from a possibly uninitialized `Parent` to
+    /// the field `Type`
+    ///
+    /// # Safety
+    ///
+    /// * `ptr` must point to a valid allocation of `Parent`
+    pub(crate) unsafe fn uninit(parent: *mut Parent) -> Self {
+        Self(parent, mem::size_of::<Parent>())
     }
 }
 
 typsy::call! {
     fn[F: Field](&self: ProjectRaw<F::Parent>, field: F) -> *const F::Type {
-        unsafe { field.project_raw(self.0) }
+        let ptr = unsafe { field.project_raw(self.0) };
+        check_projection(self.0, self.1, ptr);
+        ptr
     }
 
     fn[F: Field](&self: ProjectRawMut<F::Parent>, field: F) -> *mut F::Type {
-        unsafe { field.project_raw_mut(self.0) }
+        let ptr = unsafe { field.project_raw_mut(self.0) };
+        check_projection(self.0, self.1, ptr);
+        ptr
     }
 }
 
 /// Represents a list of fields
-pub trait FieldList<Parent>:
+pub trait FieldList<Parent: ?Sized>:
     Map<ProjectRaw<Parent>> + Map<ProjectRawMut<Parent>>
 {
 }
 
-impl<Parent> FieldList<Parent> for Nil {
+impl<Parent: ?Sized> FieldList<Parent> for Nil {
 }
 
 impl<F: Field, R> FieldList<F::Parent> for Cons<F, R> where
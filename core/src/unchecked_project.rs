@@ -10,7 +10,10 @@ impl<F: Field> UncheckedProjectTo<F> for *const F::Parent {
     }
 }
 
-impl<F: Field> UncheckedInverseProjectTo<F> for *const F::Type {
+impl<F: Field> UncheckedInverseProjectTo<F> for *const F::Type
+where
+    F::Parent: Sized,
+{
     type Projection = *const F::Parent;
 
     unsafe fn inverse_project_to(self, field: F) -> Self::Projection {
@@ -26,7 +29,10 @@ impl<F: Field> UncheckedProjectTo<F> for *mut F::Parent {
     }
 }
 
-impl<F: Field> UncheckedInverseProjectTo<F> for *mut F::Type {
+impl<F: Field> UncheckedInverseProjectTo<F> for *mut F::Type
+where
+    F::Parent: Sized,
+{
     type Projection = *mut F::Parent;
 
     unsafe fn inverse_project_to(self, field: F) -> Self::Projection {
@@ -42,7 +48,10 @@ impl<F: Field> UncheckedProjectTo<F> for NonNull<F::Parent> {
     }
 }
 
-impl<F: Field> UncheckedInverseProjectTo<F> for NonNull<F::Type> {
+impl<F: Field> UncheckedInverseProjectTo<F> for NonNull<F::Type>
+where
+    F::Parent: Sized,
+{
     type Projection = NonNull<F::Parent>;
 
     unsafe fn inverse_project_to(self, field: F) -> Self::Projection {
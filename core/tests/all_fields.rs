@@ -0,0 +1,68 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::*;
+
+#[derive(Field, Clone, Debug, PartialEq)]
+struct Settings {
+    name:    String,
+    retries: u32,
+    verbose: bool,
+}
+
+#[derive(Field)]
+struct Pair<T>(T, u8);
+
+fn settings() -> Settings {
+    Settings {
+        name:    "api".to_string(),
+        retries: 3,
+        verbose: false,
+    }
+}
+
+#[test]
+fn project_every_field() {
+    let mut settings = settings();
+
+    let typsy::hlist_pat!(name, retries, verbose) =
+        (&settings).project_all(Settings::ALL_FIELDS);
+    assert_eq!(name, "api");
+    assert_eq!(*retries, 3);
+    assert!(!*verbose);
+
+    let typsy::hlist_pat!(name, retries, verbose) =
+        (&mut settings).project_all(Settings::ALL_FIELDS);
+    name.push_str("-v2");
+    *retries += 1;
+    *verbose = true;
+
+    assert_eq!(settings.name, "api-v2");
+    assert_eq!(settings.retries, 4);
+    assert!(settings.verbose);
+}
+
+#[test]
+fn clone_every_field() {
+    let saved = settings();
+    let mut current = Settings {
+        name:    String::new(),
+        retries: 0,
+        verbose: true,
+    };
+
+    clone_fields_into(Settings::ALL_FIELDS, &saved, &mut current);
+    assert_eq!(current, saved);
+}
+
+#[test]
+fn tuple_fields() {
+    let mut pair = Pair("a", 1);
+
+    let typsy::hlist_pat!(first, second) =
+        (&mut pair).project_all(Pair::<&str>::ALL_FIELDS);
+    *first = "b";
+    *second = 2;
+
+    assert_eq!(pair.0, "b");
+    assert_eq!(pair.1, 2);
+}
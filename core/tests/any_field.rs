@@ -0,0 +1,56 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::{AnyField, Field, ProjectTo, UnknownField};
+
+#[derive(Field)]
+struct Message {
+    id:   u32,
+    body: String,
+}
+
+#[derive(Field)]
+struct Pair(u8, u16);
+
+#[test]
+fn field_enum() {
+    let message = Message {
+        id:   7,
+        body: "hello".to_string(),
+    };
+
+    assert_eq!("body".parse(), Ok(MessageField::Body));
+    assert_eq!("nope".parse::<MessageField>(), Err(UnknownField));
+
+    let body: AnyField<Message> = Message::field_of(MessageField::Body);
+    assert_eq!(body.name(), "body");
+    assert!(body.is::<String>());
+    assert_eq!(body.descriptor(), Message::fields().body.descriptor());
+
+    let body = body.downcast::<String>().unwrap();
+    assert_eq!(message.project_to(body), "hello");
+
+    let id = Message::field_of(MessageField::Id);
+    assert!(id.downcast::<u64>().is_none());
+    assert_eq!(*message.project_to(id.downcast::<u32>().unwrap()), 7);
+
+    let second = Pair::field_of("1".parse().unwrap());
+    assert_eq!(second.name(), "1");
+    assert_eq!(*Pair(1, 2).project_to(second.downcast::<u16>().unwrap()), 2);
+}
+
+#[test]
+fn positional() {
+    assert_eq!(Message::FIELD_COUNT, 2);
+    assert_eq!(Pair::FIELD_COUNT, 2);
+
+    let names: Vec<_> = (0..Message::FIELD_COUNT)
+        .map(|i| Message::field_at(i).unwrap().name())
+        .collect();
+
+    assert_eq!(names, ["id", "body"]);
+    assert_eq!(
+        Message::field_at(1),
+        Some(Message::field_of(MessageField::Body))
+    );
+    assert!(Message::field_at(2).is_none());
+}
@@ -0,0 +1,55 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::{chain_type, ArrayField, Field, NamedField, ProjectTo};
+
+#[derive(Field)]
+struct Block {
+    ctrl: u32,
+    regs: [u32; 4],
+}
+
+#[test]
+fn chain_through_array() {
+    let mut block = Block {
+        ctrl: 0,
+        regs: [1, 2, 3, 4],
+    };
+
+    let reg_2 = Block::fields().regs.chain(ArrayField::<_, _, 2>::NEW);
+
+    assert_eq!(*(&block).project_to(reg_2), 3);
+
+    *(&mut block).project_to(reg_2) = 10;
+    assert_eq!(block.regs, [1, 2, 10, 4]);
+    assert_eq!(reg_2.field_offset(), Block::fields().regs.field_offset() + 8);
+}
+
+chain_type!(Reg3 = Block => regs.3);
+
+#[test]
+fn named_elements() {
+    let block = Block {
+        ctrl: 0,
+        regs: [1, 2, 3, 4],
+    };
+
+    assert_eq!(*(&block).project_to(Reg3), 4);
+    assert!(ArrayField::<u8, 40, 0>::NEW.name().eq(["0"]));
+    assert!(ArrayField::<u8, 40, 31>::NEW.name().eq(["31"]));
+    assert!(ArrayField::<u8, 400, 307>::NEW.name().eq(["307"]));
+    assert_eq!(Reg3.display_path().to_string(), "regs.3");
+}
+
+#[test]
+fn pinned_elements() {
+    let mut block = Box::pin(Block {
+        ctrl: 0,
+        regs: [1, 2, 3, 4],
+    });
+
+    let reg_1 = Block::fields().regs.chain(ArrayField::<_, _, 1>::NEW);
+    let reg_1: &mut u32 = block.as_mut().project_to(reg_1);
+    *reg_1 = 20;
+
+    assert_eq!(block.regs, [1, 20, 3, 4]);
+}
@@ -0,0 +1,28 @@
+#![feature(raw_ref_op)]
+#![cfg(feature = "atomic")]
+
+use core::sync::atomic::Ordering;
+
+use gfp_core::{atomic::AtomicField, Field};
+
+#[derive(Field)]
+#[repr(C)]
+struct Counters {
+    flags: u8,
+    hits:  u32,
+}
+
+#[test]
+fn atomic_view() {
+    let mut counters = Counters {
+        flags: 0,
+        hits:  1,
+    };
+
+    let hits = AtomicField::new(Counters::fields().hits).unwrap();
+
+    hits.project(&mut counters).fetch_add(2, Ordering::Relaxed);
+
+    assert_eq!(counters.hits, 3);
+    assert_eq!(counters.flags, 0);
+}
@@ -0,0 +1,63 @@
+#![feature(raw_ref_op)]
+
+use core::{marker::PhantomPinned, pin::Pin};
+
+use gfp_core::{Field, PinToPin, ProjectAll, ProjectTo};
+use typsy::convert::Convert;
+
+#[derive(Field)]
+struct Timer {
+    ticks:   u64,
+    inner:   Inner,
+    _pinned: PhantomPinned,
+}
+
+#[derive(Field)]
+struct Inner {
+    count:   u8,
+    _pinned: PhantomPinned,
+}
+
+fn timer() -> Pin<Box<Timer>> {
+    Box::pin(Timer {
+        ticks:   0,
+        inner:   Inner {
+            count:   0,
+            _pinned: PhantomPinned,
+        },
+        _pinned: PhantomPinned,
+    })
+}
+
+#[test]
+fn unpin_fields() {
+    let mut timer = timer();
+
+    let ticks: &mut u64 = timer.as_mut().project_to(Timer::FIELDS.ticks);
+    *ticks += 1;
+
+    let count = Timer::FIELDS.inner.chain(Inner::FIELDS.count);
+    *timer.as_mut().project_to(count) += 2;
+
+    let count: &u8 = timer.as_ref().project_to(count);
+    assert_eq!(*count, 2);
+    assert_eq!(timer.ticks, 1);
+}
+
+#[test]
+fn project_all() {
+    let mut timer = timer();
+
+    let inner = unsafe { PinToPin::new_unchecked(Timer::FIELDS.inner) };
+    let fields = (Timer::FIELDS.ticks, inner).into_hlist();
+    let projection = timer.as_mut().project_all(fields);
+
+    let ticks: &mut u64 = projection.value;
+    let inner: Pin<&mut Inner> = projection.rest.value;
+
+    *ticks = 3;
+    *inner.project_to(Inner::FIELDS.count) = 4;
+
+    assert_eq!(timer.ticks, 3);
+    assert_eq!(timer.inner.count, 4);
+}
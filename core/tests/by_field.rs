@@ -0,0 +1,206 @@
+#![feature(raw_ref_op)]
+#![cfg(feature = "std")]
+
+#[cfg(feature = "alloc")]
+use gfp_core::{binary_search_by_field, dedup_by_field, sort_by_field};
+#[cfg(feature = "std")]
+use gfp_core::{group_by_field, join_by_field};
+use gfp_core::{
+    max_by_field, max_by_field_by, max_by_field_key, min_by_field,
+    min_by_field_by, min_by_field_key, Field,
+};
+
+#[derive(Field, Debug, Clone, PartialEq)]
+struct Entry {
+    level:   &'static str,
+    message: &'static str,
+    time:    u32,
+}
+
+fn entry(level: &'static str, message: &'static str, time: u32) -> Entry {
+    Entry {
+        level,
+        message,
+        time,
+    }
+}
+
+fn log() -> Vec<Entry> {
+    vec![
+        entry("warn", "disk", 3),
+        entry("info", "start", 1),
+        entry("warn", "disk", 4),
+        entry("error", "crash", 9),
+        entry("info", "stop", 2),
+    ]
+}
+
+#[cfg(feature = "alloc")]
+fn times(entries: &[Entry]) -> Vec<u32> {
+    entries.iter().map(|entry| entry.time).collect()
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn group() {
+    let groups = group_by_field(log(), Entry::FIELDS.level);
+
+    assert_eq!(groups.len(), 3);
+    assert_eq!(times(&groups["warn"]), [3, 4]);
+    assert_eq!(times(&groups["info"]), [1, 2]);
+    assert_eq!(groups["error"], [entry("error", "crash", 9)]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn group_empty() {
+    let groups = group_by_field(Vec::new(), Entry::FIELDS.time);
+
+    assert!(groups.is_empty());
+}
+
+#[cfg(feature = "std")]
+#[derive(Field)]
+struct Level {
+    name:     &'static str,
+    severity: u8,
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn join() {
+    let log = log();
+    let levels = [
+        Level {
+            name:     "error",
+            severity: 3,
+        },
+        Level {
+            name:     "warn",
+            severity: 2,
+        },
+        Level {
+            name:     "debug",
+            severity: 0,
+        },
+    ];
+
+    let level = Entry::FIELDS.level;
+    let joined = join_by_field(&log, level, &levels, Level::FIELDS.name)
+        .map(|(entry, level)| (entry.time, level.severity))
+        .collect::<Vec<_>>();
+
+    assert_eq!(joined, [(3, 2), (4, 2), (9, 3)]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn join_many_to_many() {
+    let log = log();
+    let level = Entry::FIELDS.level;
+
+    let joined = join_by_field(&log, level, &log, level)
+        .map(|(a, b)| (a.time, b.time))
+        .collect::<Vec<_>>();
+
+    assert_eq!(joined.len(), 9);
+    assert_eq!(joined[..4], [(3, 3), (3, 4), (1, 1), (1, 2)]);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn dedup() {
+    let mut log = log();
+    log.push(entry("info", "start", 5));
+
+    dedup_by_field(&mut log, Entry::FIELDS.message);
+    assert_eq!(times(&log), [3, 1, 4, 9, 2, 5]);
+
+    log.sort_by_key(|entry| entry.level);
+    dedup_by_field(&mut log, Entry::FIELDS.level);
+    assert_eq!(times(&log), [9, 1, 3]);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn sort_and_search() {
+    let mut log = log();
+    let time = Entry::FIELDS.time;
+
+    sort_by_field(&mut log, time);
+    assert_eq!(times(&log), [1, 2, 3, 4, 9]);
+
+    assert_eq!(binary_search_by_field(&log, time, &4), Ok(3));
+    assert_eq!(binary_search_by_field(&log, time, &0), Err(0));
+    assert_eq!(binary_search_by_field(&log, time, &5), Err(4));
+    assert_eq!(binary_search_by_field(&log, time, &10), Err(5));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn sort_is_stable() {
+    let mut log = log();
+    let level = Entry::FIELDS.level;
+
+    sort_by_field(&mut log, level);
+    assert_eq!(times(&log), [9, 1, 2, 3, 4]);
+
+    let warn = binary_search_by_field(&log, level, &"warn").unwrap();
+    assert!(warn == 3 || warn == 4);
+}
+
+#[test]
+fn max_min() {
+    let log = log();
+    let fields = Entry::fields();
+
+    assert_eq!(max_by_field(&log, fields.time).unwrap().time, 9);
+    assert_eq!(min_by_field(&log, fields.time).unwrap().time, 1);
+    assert_eq!(max_by_field(&[], fields.time), None);
+}
+
+#[test]
+fn max_min_ties() {
+    let log = log();
+    let fields = Entry::fields();
+
+    // like `Iterator::max`, the last of the greatest, and the first of the
+    // least
+    assert_eq!(max_by_field(&log, fields.message).unwrap().time, 2);
+    assert_eq!(max_by_field(&log, fields.level).unwrap().time, 4);
+    assert_eq!(min_by_field(&log, fields.level).unwrap().time, 9);
+    assert_eq!(min_by_field(&log, fields.message).unwrap().time, 9);
+}
+
+#[test]
+fn max_min_by_key() {
+    let log = log();
+    let fields = Entry::fields();
+
+    let longest = max_by_field_key(&log, fields.message, |m| m.len());
+    let shortest = min_by_field_key(&log, fields.message, |m| m.len());
+
+    assert_eq!(longest.unwrap().time, 9);
+    assert_eq!(shortest.unwrap().time, 3);
+}
+
+#[derive(Field)]
+struct Sample {
+    value: f64,
+}
+
+#[test]
+fn max_min_by_floats() {
+    let samples = [0.5, -1.0, 2.5, 0.0].map(|value| {
+        Sample {
+            value,
+        }
+    });
+    let value = Sample::FIELDS.value;
+
+    let max = max_by_field_by(&samples, value, f64::total_cmp).unwrap();
+    let min = min_by_field_by(&samples, value, f64::total_cmp).unwrap();
+
+    assert_eq!(max.value, 2.5);
+    assert_eq!(min.value, -1.0);
+}
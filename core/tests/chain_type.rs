@@ -0,0 +1,49 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::*;
+
+#[derive(Field)]
+struct Foo {
+    bar:  Bar,
+    pair: (u8, Tap),
+}
+
+#[derive(Field)]
+struct Bar(Tap, u32);
+
+#[derive(Field)]
+struct Tap {
+    r#val: u64,
+}
+
+impl Tap {
+    fn new(val: u64) -> Self {
+        Self {
+            val,
+        }
+    }
+}
+
+chain_type!(pub FooToVal = Foo => bar.0.val);
+chain_type!(FooToPair = Foo => pair.1.val);
+chain_type!(FooToBar = Foo => bar);
+
+fn val(foo: &mut Foo, field: FooToVal) -> &mut u64 {
+    foo.project_to(field)
+}
+
+#[test]
+fn chain_type() {
+    let mut foo = Foo {
+        bar:  Bar(Tap::new(1), 2),
+        pair: (3, Tap::new(4)),
+    };
+
+    *val(&mut foo, FooToVal) = 10;
+    assert_eq!(foo.bar.0.val, 10);
+    assert_eq!(*foo.project_to(FooToPair), 4);
+    assert_eq!(foo.project_to(FooToBar).1, 2);
+
+    assert_eq!(FooToVal.display_path().to_string(), "bar.0.val");
+    assert_eq!(FooToPair.display_path().to_string(), "pair.1.val");
+}
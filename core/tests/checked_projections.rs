@@ -0,0 +1,154 @@
+#![feature(raw_ref_op)]
+#![cfg(all(feature = "checked-projections", debug_assertions))]
+
+use core::mem::MaybeUninit;
+use gfp_core::{Field, Out, ProjectAll, ProjectTo};
+use typsy::convert::Convert;
+
+struct Pair {
+    a: u32,
+    b: u32,
+}
+
+/// Projects to `b`, or past the end of the parent
+#[derive(Clone, Copy)]
+struct Offset(usize);
+
+unsafe impl Field for Offset {
+    type Parent = Pair;
+    type Type = u32;
+
+    unsafe fn project_raw(&self, ptr: *const Pair) -> *const u32 {
+        ptr.cast::<u8>().wrapping_add(self.0).cast()
+    }
+
+    unsafe fn project_raw_mut(&self, ptr: *mut Pair) -> *mut u32 {
+        ptr.cast::<u8>().wrapping_add(self.0).cast()
+    }
+}
+
+fn b() -> Offset {
+    Offset(core::mem::size_of::<u32>())
+}
+
+fn past_the_end() -> Offset {
+    Offset(core::mem::size_of::<Pair>())
+}
+
+fn misaligned() -> Offset {
+    Offset(1)
+}
+
+#[test]
+fn in_bounds() {
+    let mut pair = Pair {
+        a: 1,
+        b: 2,
+    };
+
+    assert_eq!(*(&pair).project_to(b()), 2);
+    *(&mut pair).project_to(b()) = 3;
+    assert_eq!(pair.b, 3);
+    assert_eq!(pair.a, 1);
+}
+
+#[test]
+#[should_panic(expected = "is outside of the 8 bytes")]
+fn out_of_bounds() {
+    let pair = Pair {
+        a: 1,
+        b: 2,
+    };
+
+    (&pair).project_to(past_the_end());
+}
+
+#[test]
+#[should_panic(expected = "is outside of the 8 bytes")]
+fn out_of_bounds_mut() {
+    let mut pair = Pair {
+        a: 1,
+        b: 2,
+    };
+
+    (&mut pair).project_to(past_the_end());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+#[should_panic(expected = "is outside of the 8 bytes")]
+fn out_of_bounds_box() {
+    let pair = Box::new(Pair {
+        a: 1,
+        b: 2,
+    });
+
+    let _ = pair.project_to(past_the_end());
+}
+
+#[test]
+#[should_panic(expected = "is outside of the 8 bytes")]
+fn out_of_bounds_list() {
+    let mut pair = Pair {
+        a: 1,
+        b: 2,
+    };
+
+    let _ = (&mut pair).project_all((b(), past_the_end()).into_hlist());
+}
+
+#[test]
+#[should_panic(expected = "is outside of the 8 bytes")]
+fn out_of_bounds_out() {
+    let mut pair = MaybeUninit::<Pair>::uninit();
+
+    let _ = Out::from(&mut pair).project_to(past_the_end());
+}
+
+#[test]
+#[should_panic(expected = "is misaligned, it needs an alignment of 4")]
+fn misaligned_field() {
+    let pair = Pair {
+        a: 1,
+        b: 2,
+    };
+
+    (&pair).project_to(misaligned());
+}
+
+#[repr(C, packed)]
+struct Packed {
+    tag:   u8,
+    value: u32,
+}
+
+#[derive(Clone, Copy)]
+struct PackedValue;
+
+unsafe impl Field for PackedValue {
+    type Parent = Packed;
+    type Type = u32;
+
+    unsafe fn project_raw(&self, ptr: *const Packed) -> *const u32 {
+        &raw const (*ptr).value
+    }
+
+    unsafe fn project_raw_mut(&self, ptr: *mut Packed) -> *mut u32 {
+        &raw mut (*ptr).value
+    }
+}
+
+/// Places the `Packed` so that its `value` is never aligned
+#[repr(C, align(4))]
+struct Aligned(Packed);
+
+#[test]
+#[should_panic(expected = "is misaligned")]
+fn packed_parent() {
+    let aligned = Aligned(Packed {
+        tag:   0,
+        value: 1,
+    });
+
+    (&aligned.0).project_to(PackedValue);
+}
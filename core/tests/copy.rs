@@ -0,0 +1,72 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::*;
+use typsy::convert::Convert;
+
+#[derive(Field, Clone, Debug, PartialEq)]
+struct Document {
+    text:   String,
+    cursor: usize,
+    path:   String,
+}
+
+fn document() -> Document {
+    Document {
+        text:   "hello".to_string(),
+        cursor: 5,
+        path:   "a.txt".to_string(),
+    }
+}
+
+#[test]
+fn single_fields() {
+    let saved = document();
+    let mut doc = document();
+    doc.cursor = 0;
+    doc.text.clear();
+
+    copy_field(Document::FIELDS.cursor, &saved, &mut doc);
+    assert_eq!(doc.cursor, 5);
+    assert_eq!(doc.text, "");
+
+    clone_field_into(Document::FIELDS.text, &saved, &mut doc);
+    assert_eq!(doc, saved);
+}
+
+#[test]
+fn field_list() {
+    let undo = document();
+    let mut doc = document();
+    doc.text.push_str(" world");
+    doc.cursor = 11;
+    doc.path = "b.txt".to_string();
+
+    let fields = Document::fields();
+    let restore = (fields.text, fields.cursor).into_hlist();
+    clone_fields_into(restore, &undo, &mut doc);
+
+    assert_eq!(doc.text, "hello");
+    assert_eq!(doc.cursor, 5);
+    assert_eq!(doc.path, "b.txt");
+}
+
+#[test]
+fn field_to_slice() {
+    let mut docs = vec![document(), document(), document()];
+    docs[1].cursor = 1;
+    docs[2].cursor = 2;
+
+    let mut cursors = [usize::MAX; 3];
+    copy_field_to_slice(&docs, Document::FIELDS.cursor, &mut cursors);
+
+    assert_eq!(cursors, [5, 1, 2]);
+}
+
+#[test]
+#[should_panic(expected = "one element for each parent")]
+fn field_to_short_slice() {
+    let docs = vec![document(), document()];
+
+    let mut cursors = [0; 1];
+    copy_field_to_slice(&docs, Document::FIELDS.cursor, &mut cursors);
+}
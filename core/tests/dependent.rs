@@ -0,0 +1,84 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::{BorrowDependent, Field};
+use self_cell::self_cell;
+
+#[derive(Field)]
+struct Request<'a> {
+    method: &'a str,
+    path:   Path<'a>,
+}
+
+#[derive(Field)]
+struct Path<'a> {
+    segments: Vec<&'a str>,
+}
+
+fn parse(line: &str) -> Request<'_> {
+    let (method, path) = line.split_once(' ').unwrap();
+
+    Request {
+        method,
+        path: Path {
+            segments: path.split('/').filter(|s| !s.is_empty()).collect(),
+        },
+    }
+}
+
+self_cell!(
+    struct OwnedRequest {
+        owner: String,
+
+        #[covariant]
+        dependent: Request,
+    }
+);
+
+gfp_core::borrow_dependent! {
+    impl['a] OwnedRequest => Request<'a> { borrow_dependent }
+}
+
+/// A type with a borrow method like the ones generated by `ouroboros`
+struct Cached<T> {
+    value:   T,
+    summary: Summary,
+}
+
+#[derive(Field)]
+struct Summary {
+    len: usize,
+}
+
+impl<T> Cached<T> {
+    fn borrow_summary(&self) -> &Summary {
+        &self.summary
+    }
+}
+
+gfp_core::borrow_dependent! {
+    impl['a, T: 'a] Cached<T> => Summary { borrow_summary }
+}
+
+#[test]
+fn self_cell() {
+    let request =
+        OwnedRequest::new("GET /users/7".to_string(), |line| parse(line));
+
+    assert_eq!(*request.project_dependent(Request::FIELDS.method), "GET");
+
+    let segments = Request::FIELDS.path.chain(Path::FIELDS.segments);
+    assert_eq!(*request.project_dependent(segments), ["users", "7"]);
+}
+
+#[test]
+fn generic() {
+    let cached = Cached {
+        value:   vec![1, 2, 3],
+        summary: Summary {
+            len: 3,
+        },
+    };
+
+    assert_eq!(cached.dependent().len, cached.value.len());
+    assert_eq!(*cached.project_dependent(Summary::FIELDS.len), 3);
+}
@@ -0,0 +1,140 @@
+#![feature(raw_ref_op)]
+
+use core::alloc::Layout;
+
+use gfp_core::{Field, FieldDescriptor, OffsetError, ProjectTo};
+
+#[derive(Field)]
+struct Foo {
+    x: u32,
+    y: u8,
+}
+
+#[test]
+fn derived_descriptor() {
+    let fields = Foo::fields();
+
+    assert_eq!(
+        fields.y.descriptor(),
+        FieldDescriptor::new::<Foo, u8>(core::mem::offset_of!(Foo, y))
+    );
+    assert_eq!(fields.x.descriptor().range(), fields.x.range());
+}
+
+#[test]
+#[should_panic]
+fn out_of_bounds() {
+    FieldDescriptor::new::<Foo, u32>(core::mem::size_of::<Foo>());
+}
+
+const OFFSETS: [usize; 2] = [
+    foo_fields::X::<Foo>::DESCRIPTOR.offset(),
+    foo_fields::Y::<Foo>::DESCRIPTOR.offset(),
+];
+
+const fn project_y(foo: &Foo) -> &u8 {
+    unsafe { &*Foo::FIELDS.y.project_raw(foo) }
+}
+
+#[test]
+fn const_projection() {
+    let foo = Foo {
+        x: 1, y: 2
+    };
+
+    assert_eq!(
+        OFFSETS,
+        [
+            Foo::fields().x.field_offset(),
+            Foo::fields().y.field_offset()
+        ]
+    );
+    assert_eq!(*project_y(&foo), 2);
+}
+
+const PROJECTED_Y: FieldDescriptor = {
+    let foo = core::mem::MaybeUninit::<Foo>::uninit();
+    let ptr = foo.as_ptr();
+
+    unsafe {
+        FieldDescriptor::from_pointers(ptr, Foo::FIELDS.y.project_raw(ptr))
+    }
+};
+
+#[test]
+fn from_pointers() {
+    let foo = Foo {
+        x: 1,
+        y: 2,
+    };
+
+    assert_eq!(PROJECTED_Y, Foo::FIELDS.y.descriptor());
+    assert_eq!(
+        unsafe { FieldDescriptor::from_pointers(&foo, &foo.x) },
+        Foo::FIELDS.x.descriptor()
+    );
+}
+
+#[repr(C)]
+struct Header {
+    tag: u8,
+    len: u32,
+}
+
+#[test]
+fn checked_offsets() {
+    let len = FieldDescriptor::from_offset_checked::<Header, u32>(4).unwrap();
+    assert_eq!(len, FieldDescriptor::new::<Header, u32>(4));
+    assert_eq!(usize::from(len), 4);
+    assert_eq!(len.layout(), Layout::new::<u32>());
+
+    assert_eq!(
+        FieldDescriptor::from_offset_checked::<Header, u32>(2),
+        Err(OffsetError::Misaligned {
+            offset: 2,
+            align:  4,
+        })
+    );
+    assert_eq!(
+        FieldDescriptor::from_offset_checked::<Header, u32>(6),
+        Err(OffsetError::OutOfBounds {
+            offset:      6,
+            size:        4,
+            parent_size: 8,
+        })
+    );
+
+    // a `u64` at offset 0 is only aligned if the parent is
+    let parent = Layout::from_size_align(8, 4).unwrap();
+    let field = Layout::new::<u64>();
+    assert!(FieldDescriptor::from_layouts(parent, 0, field).is_err());
+}
+
+#[derive(Field)]
+struct Tagged<T> {
+    tag:   u8,
+    value: T,
+}
+
+/// The descriptors of generic parents are checked for each parent that is
+/// projected through
+#[test]
+fn generic_descriptors() {
+    fn check<T: Default>() {
+        let value = tagged_fields::Value::<Tagged<T>>::DESCRIPTOR;
+        assert_eq!(value, Tagged::<T>::FIELDS.value.descriptor());
+        assert_eq!(value.offset(), core::mem::offset_of!(Tagged<T>, value));
+        assert_eq!(value.size(), core::mem::size_of::<T>());
+
+        let tagged = Tagged {
+            tag:   1,
+            value: T::default(),
+        };
+        assert_eq!(*(&tagged).project_to(Tagged::<T>::FIELDS.tag), 1);
+    }
+
+    check::<u8>();
+    check::<u64>();
+    check::<[u16; 3]>();
+    check::<String>();
+}
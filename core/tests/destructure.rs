@@ -0,0 +1,161 @@
+#![feature(raw_ref_op)]
+
+#[cfg(feature = "alloc")]
+use gfp_core::{DestructureBox, ExtractField};
+use gfp_core::{Destructure, Field};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+type Log = Rc<RefCell<Vec<&'static str>>>;
+
+struct Noisy(&'static str, Log);
+
+impl Drop for Noisy {
+    fn drop(&mut self) {
+        self.1.borrow_mut().push(self.0)
+    }
+}
+
+#[derive(Field)]
+struct Request {
+    method: Noisy,
+    body:   Noisy,
+    trace:  Noisy,
+}
+
+fn request(log: &Log) -> Request {
+    Request {
+        method: Noisy("method", log.clone()),
+        body:   Noisy("body", log.clone()),
+        trace:  Noisy("trace", log.clone()),
+    }
+}
+
+#[test]
+fn drops_remaining_fields() {
+    let log = Log::default();
+    let mut parts = Destructure::new(request(&log));
+
+    let body = parts.take(Request::FIELDS.body);
+    assert!(parts.is_taken(Request::FIELDS.body));
+    assert!(!parts.is_taken(Request::FIELDS.trace));
+
+    drop(parts);
+    assert_eq!(*log.borrow(), ["method", "trace"]);
+
+    drop(body);
+    assert_eq!(*log.borrow(), ["method", "trace", "body"]);
+}
+
+#[test]
+fn take_everything() {
+    let log = Log::default();
+    let mut parts = Destructure::new(request(&log));
+
+    let trace = parts.take(Request::FIELDS.trace);
+    let method = parts.take(Request::FIELDS.method);
+    let body = parts.take(Request::FIELDS.body);
+    drop(parts);
+    assert!(log.borrow().is_empty());
+
+    drop((method, body, trace));
+    assert_eq!(*log.borrow(), ["method", "body", "trace"]);
+}
+
+#[test]
+#[should_panic(expected = "already moved out")]
+fn take_twice() {
+    let log = Log::default();
+    let mut parts = Destructure::new(request(&log));
+
+    let _body = parts.take(Request::FIELDS.body);
+    let _body = parts.take(Request::FIELDS.body);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn extract_from_box() {
+    let log = Log::default();
+    let boxed = Box::new(request(&log));
+
+    let (body, rest) = boxed.extract(Request::FIELDS.body);
+    assert!(rest.is_taken(Request::FIELDS.body));
+    assert!(log.borrow().is_empty());
+
+    drop(rest);
+    assert_eq!(*log.borrow(), ["method", "trace"]);
+
+    drop(body);
+    assert_eq!(*log.borrow(), ["method", "trace", "body"]);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn destructure_box() {
+    let log = Log::default();
+    let mut parts = DestructureBox::new(Box::new(request(&log)));
+
+    let trace = parts.take(Request::FIELDS.trace);
+    let method = parts.take(Request::FIELDS.method);
+    drop(parts);
+    assert_eq!(*log.borrow(), ["body"]);
+
+    drop((method, trace));
+    assert_eq!(*log.borrow(), ["body", "method", "trace"]);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+#[should_panic(expected = "already moved out")]
+fn extract_twice() {
+    let log = Log::default();
+    let boxed = Box::new(request(&log));
+
+    let (_body, mut rest) = boxed.extract(Request::FIELDS.body);
+
+    let _body = rest.take(Request::FIELDS.body);
+}
+
+struct Counted(Rc<Cell<usize>>);
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1)
+    }
+}
+
+#[derive(Field)]
+struct Counters {
+    a: Counted,
+    b: Counted,
+    c: Counted,
+}
+
+#[test]
+fn drops_each_field_once() {
+    // every subset of the fields is moved out, the rest are dropped in place
+    for taken in 0..1 << 3 {
+        let counts = [(); 3].map(|()| Rc::new(Cell::new(0)));
+        let counters = Counters {
+            a: Counted(counts[0].clone()),
+            b: Counted(counts[1].clone()),
+            c: Counted(counts[2].clone()),
+        };
+
+        let mut parts = Destructure::new(counters);
+        let a = (taken & 1 != 0).then(|| parts.take(Counters::FIELDS.a));
+        let b = (taken & 2 != 0).then(|| parts.take(Counters::FIELDS.b));
+        let c = (taken & 4 != 0).then(|| parts.take(Counters::FIELDS.c));
+
+        drop(parts);
+        for (i, count) in counts.iter().enumerate() {
+            let expected = (taken & 1 << i == 0) as usize;
+            assert_eq!(count.get(), expected);
+        }
+
+        drop((a, b, c));
+        assert!(counts.iter().all(|count| count.get() == 1));
+    }
+}
@@ -0,0 +1,85 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::*;
+
+#[derive(Field)]
+struct Player {
+    name:  String,
+    stats: Stats,
+    level: u8,
+}
+
+#[derive(Field)]
+struct Stats {
+    health: u32,
+    armor:  u32,
+}
+
+#[derive(Field)]
+struct Pair<T>(T, T);
+
+fn assert_disjoint<A, B>(_: A, _: B)
+where
+    A: Disjoint<B>,
+    B: Field<Parent = A::Parent>,
+{
+}
+
+fn both_mut<A, B>(
+    parent: &mut A::Parent,
+    a: A,
+    b: B,
+) -> (&mut A::Type, &mut B::Type)
+where
+    A: Disjoint<B>,
+    B: Field<Parent = A::Parent>,
+{
+    let parent = parent as *mut A::Parent;
+
+    // Safety
+    // * `A: Disjoint<B>`, so the fields don't alias
+    unsafe {
+        let a = &mut *a.project_raw_mut(parent);
+        let b = &mut *b.project_raw_mut(parent);
+        (a, b)
+    }
+}
+
+#[test]
+fn siblings() {
+    assert_disjoint(Player::FIELDS.name, Player::FIELDS.stats);
+    assert_disjoint(Player::FIELDS.stats, Player::FIELDS.name);
+    assert_disjoint(Player::FIELDS.name, Player::FIELDS.level);
+    assert_disjoint(Stats::FIELDS.health, Stats::FIELDS.armor);
+    assert_disjoint(Pair::<u8>::FIELDS.0, Pair::<u8>::FIELDS.1);
+}
+
+#[test]
+fn paths() {
+    let health = Player::FIELDS.stats.chain(Stats::FIELDS.health);
+    let name = (Player::FIELDS.name,);
+
+    assert_disjoint(health, Player::FIELDS.name);
+    assert_disjoint(Player::FIELDS.level, health);
+    assert_disjoint(health, name);
+    assert_disjoint(
+        (Player::FIELDS.stats, Stats::FIELDS.armor),
+        Player::FIELDS.level,
+    );
+
+    let mut player = Player {
+        name:  "ferris".to_string(),
+        stats: Stats {
+            health: 10,
+            armor:  3,
+        },
+        level: 1,
+    };
+
+    let (name, health) = both_mut(&mut player, Player::FIELDS.name, health);
+    name.push('!');
+    *health += 1;
+
+    assert_eq!(player.name, "ferris!");
+    assert_eq!(player.stats.health, 11);
+}
@@ -0,0 +1,55 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::*;
+
+#[derive(Field)]
+struct Job {
+    #[field(display = "Retry count")]
+    retries: u32,
+    #[field(display = "Timeout (ms)")]
+    timeout: u64,
+    name:    String,
+}
+
+#[derive(Field)]
+struct Point(#[field(display = "X")] i32, i32);
+
+#[derive(Field)]
+struct Plain {
+    value: u32,
+}
+
+#[test]
+fn display_in_lookup() {
+    let retries = Job::lookup_field("retries").unwrap();
+    assert_eq!(retries.name(), "retries");
+    assert_eq!(retries.display_name(), "Retry count");
+
+    assert_eq!(
+        Job::lookup_field("timeout").unwrap().display_name(),
+        "Timeout (ms)"
+    );
+    assert_eq!(Job::lookup_field("name").unwrap().display_name(), "name");
+
+    assert_eq!(Point::lookup_field("0").unwrap().display_name(), "X");
+    assert_eq!(Point::lookup_field("1").unwrap().display_name(), "1");
+}
+
+#[test]
+fn display_in_struct_info() {
+    let info = Job::STRUCT_INFO;
+    let labels = (0..info.fields().len())
+        .map(|index| info.display_name(index))
+        .collect::<Vec<_>>();
+    assert_eq!(labels, ["Retry count", "Timeout (ms)", "name"]);
+
+    assert_eq!(Point::STRUCT_INFO.display_name(0), "X");
+    assert_eq!(Point::STRUCT_INFO.display_name(1), "1");
+    assert_eq!(Plain::STRUCT_INFO.display_name(0), "value");
+}
+
+#[test]
+#[should_panic]
+fn display_out_of_range() {
+    Plain::STRUCT_INFO.display_name(1);
+}
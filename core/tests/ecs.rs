@@ -0,0 +1,65 @@
+#![feature(raw_ref_op)]
+#![cfg(feature = "alloc")]
+
+use gfp_core::{
+    ecs::{ComponentFields, World},
+    Field,
+};
+
+#[derive(Field, Debug, PartialEq)]
+struct Health {
+    current: u32,
+    max:     u32,
+}
+
+#[derive(Field)]
+struct Name(String);
+
+#[test]
+fn component_fields() {
+    let mut world = World::new();
+    let player = world.spawn();
+    let rock = world.spawn();
+
+    let health = Health {
+        current: 10,
+        max:     20,
+    };
+    world.insert(player, health);
+    world.insert(rock, Name("rock".to_string()));
+
+    *world.get_field_mut(player, Health::FIELDS.current).unwrap() += 5;
+    assert_eq!(world.get_field(player, Health::FIELDS.current), Some(&15));
+    assert_eq!(world.get_field(rock, Health::FIELDS.current), None);
+
+    assert_eq!(world.replace_field(player, Health::FIELDS.max, 30), Ok(20));
+    assert_eq!(world.replace_field(rock, Health::FIELDS.max, 30), Err(30));
+
+    assert_eq!(
+        world.remove::<Health>(player),
+        Some(Health {
+            current: 15,
+            max:     30,
+        })
+    );
+    assert_eq!(world.get_field(rock, Name::FIELDS._0).unwrap(), "rock");
+}
+
+#[test]
+fn any_field() {
+    let mut world = World::new();
+    let player = world.spawn();
+
+    let health = Health {
+        current: 1,
+        max:     2,
+    };
+    world.insert(player, health);
+
+    let max = Health::field_of("max".parse().unwrap());
+    assert_eq!(world.get_any_field::<_, u32>(player, max), Some(&2));
+    assert_eq!(world.get_any_field::<_, u8>(player, max), None);
+
+    *world.get_any_field_mut::<_, u32>(player, max).unwrap() = 3;
+    assert_eq!(world.get_field(player, Health::FIELDS.max), Some(&3));
+}
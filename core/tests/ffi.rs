@@ -0,0 +1,53 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::{Field, FieldDescriptor, HasStructInfo};
+
+#[derive(Field)]
+#[repr(C)]
+struct SensorReading {
+    id:    u16,
+    value: f32,
+    flags: u8,
+}
+
+#[derive(Field)]
+#[repr(C)]
+union Word {
+    bytes: [u8; 4],
+    value: u32,
+}
+
+#[test]
+fn struct_info() {
+    let info = SensorReading::STRUCT_INFO;
+
+    assert_eq!(info.name(), "SensorReading");
+    assert_eq!(info.size(), 12);
+    assert_eq!(info.align(), 4);
+    let names: Vec<_> = info.fields().iter().map(|&(name, _)| name).collect();
+    assert_eq!(names, ["id", "value", "flags"]);
+    assert_eq!(
+        info.fields()[1].1,
+        FieldDescriptor::new::<SensorReading, f32>(4)
+    );
+
+    assert_eq!(Word::STRUCT_INFO.fields()[1].1.offset(), 0);
+}
+
+#[test]
+#[cfg(feature = "ffi-export")]
+fn c_header() {
+    use gfp_core::ffi::CHeader;
+
+    let header = CHeader::new()
+        .defines(&SensorReading::STRUCT_INFO)
+        .table(&SensorReading::STRUCT_INFO)
+        .table(&Word::STRUCT_INFO)
+        .finish();
+
+    assert!(header.contains("#define SENSOR_READING_SIZE 12\n"));
+    assert!(header.contains("#define SENSOR_READING_FLAGS_OFFSET 8\n"));
+    assert!(header.contains("static const struct gfp_field word_fields[]"));
+    assert!(header.contains("    { \"value\", 4, 4 },\n"));
+    assert_eq!(header.matches("struct gfp_field {").count(), 1);
+}
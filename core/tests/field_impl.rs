@@ -0,0 +1,40 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::{Field, ProjectTo};
+
+mod external {
+    pub struct Config {
+        pub name:    String,
+        pub retries: u32,
+        pub verbose: bool,
+    }
+}
+
+use external::Config;
+
+gfp_core::field_impl! {
+    unsafe mod config_fields for Config {
+        pub retries: u32,
+        pub verbose: bool,
+    }
+}
+
+#[test]
+fn foreign_fields() {
+    let mut config = Config {
+        name:    "test".to_string(),
+        retries: 3,
+        verbose: false,
+    };
+
+    *(&mut config).project_to(config_fields::FIELDS.retries) += 1;
+    *(&mut config).project_to(config_fields::FIELDS.verbose) = true;
+
+    assert_eq!(config.name, "test");
+    assert_eq!(config.retries, 4);
+    assert!(config.verbose);
+    assert_eq!(
+        config_fields::FIELDS.retries.field_offset(),
+        core::mem::offset_of!(Config, retries)
+    );
+}
@@ -0,0 +1,124 @@
+#![feature(raw_ref_op)]
+#![cfg(feature = "alloc")]
+
+use gfp_core::{ordered_index_by, Field, OrderedFieldIndex};
+#[cfg(feature = "std")]
+use gfp_core::{index_by, position_index_by, FieldIndex};
+
+#[derive(Field, Debug, PartialEq)]
+struct Host {
+    name: String,
+    port: u16,
+}
+
+fn host(name: &str, port: u16) -> Host {
+    Host {
+        name: name.to_string(),
+        port,
+    }
+}
+
+fn hosts() -> Vec<Host> {
+    vec![host("a", 80), host("b", 443), host("c", 80)]
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn index() {
+    let by_name = index_by(hosts(), Host::FIELDS.name);
+    assert_eq!(by_name.len(), 3);
+    assert_eq!(by_name["b"], host("b", 443));
+
+    // the last parent with a key wins
+    let by_port = index_by(hosts(), Host::FIELDS.port);
+    assert_eq!(by_port.len(), 2);
+    assert_eq!(by_port[&80], host("c", 80));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn position_index() {
+    let hosts = hosts();
+    let by_name = position_index_by(&hosts, Host::FIELDS.name);
+
+    assert_eq!(by_name["a"], 0);
+    assert_eq!(by_name["c"], 2);
+    assert_eq!(by_name.get("d"), None);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn field_index() {
+    let mut index = FieldIndex::new(Host::FIELDS.name);
+    index.extend(hosts());
+
+    assert_eq!(index.len(), 3);
+    assert_eq!(index.get("a"), Some(&host("a", 80)));
+    assert!(!index.contains_key("d"));
+
+    let old = index.insert(host("a", 8080));
+    assert_eq!(old, Some(host("a", 80)));
+    assert_eq!(index.get("a").unwrap().port, 8080);
+
+    assert_eq!(index.remove("b"), Some(host("b", 443)));
+    assert_eq!(index.len(), 2);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn modify_rekeys() {
+    let mut index = FieldIndex::new(Host::FIELDS.name);
+    index.extend(hosts());
+
+    let replaced = index.modify("a", |host| host.port = 81);
+    assert_eq!(replaced, None);
+    assert_eq!(index.get("a").unwrap().port, 81);
+
+    let replaced = index.modify("a", |host| host.name = "c".to_string());
+    assert_eq!(replaced, Some(host("c", 80)));
+    assert_eq!(index.get("c"), Some(&host("c", 81)));
+    assert!(!index.contains_key("a"));
+    assert_eq!(index.len(), 2);
+
+    assert_eq!(index.modify("z", |host| host.port = 0), None);
+}
+
+#[test]
+fn ordered_index() {
+    let by_port = ordered_index_by(hosts(), Host::FIELDS.port);
+
+    let ports = by_port.keys().copied().collect::<Vec<_>>();
+    assert_eq!(ports, [80, 443]);
+    assert_eq!(by_port[&80], host("c", 80));
+}
+
+#[test]
+fn ordered_field_index() {
+    let mut index = OrderedFieldIndex::new(Host::FIELDS.port);
+    index.extend(vec![host("a", 80), host("b", 443), host("c", 22)]);
+    index.insert(host("d", 8080));
+
+    let ports = index.values().map(|host| host.port).collect::<Vec<_>>();
+    assert_eq!(ports, [22, 80, 443, 8080]);
+
+    let names = index.range(80..1024).map(|host| host.name.as_str());
+    assert_eq!(names.collect::<Vec<_>>(), ["a", "b"]);
+    let names = index.range(443..).rev().map(|host| host.name.as_str());
+    assert_eq!(names.collect::<Vec<_>>(), ["d", "b"]);
+
+    assert_eq!(index.first(), Some(&host("c", 22)));
+    assert_eq!(index.last(), Some(&host("d", 8080)));
+    assert_eq!(index.get(&443), Some(&host("b", 443)));
+}
+
+#[test]
+fn ordered_modify_rekeys() {
+    let mut index = OrderedFieldIndex::new(Host::FIELDS.port);
+    index.extend(hosts());
+
+    assert_eq!(index.len(), 2);
+    assert_eq!(index.modify(&80, |host| host.port = 8080), None);
+    assert_eq!(index.last(), Some(&host("c", 8080)));
+    assert_eq!(index.remove(&443), Some(host("b", 443)));
+    assert_eq!(index.len(), 1);
+}
@@ -0,0 +1,52 @@
+#![feature(raw_ref_op)]
+#![cfg(feature = "alloc")]
+
+use gfp_core::*;
+
+#[derive(Field)]
+struct Form {
+    name:    String,
+    email:   String,
+    address: Address,
+}
+
+#[derive(Field)]
+struct Address {
+    street: String,
+    zip:    u32,
+}
+
+#[test]
+fn field_map() {
+    let mut labels = FieldMap::new();
+
+    assert_eq!(labels.insert(Form::FIELDS.name, "Name"), None);
+    assert_eq!(labels.insert(Form::FIELDS.email, "E-mail"), None);
+    assert_eq!(labels.insert(Form::FIELDS.name, "Full name"), Some("Name"));
+
+    let zip = Form::FIELDS.address.chain(Address::FIELDS.zip);
+    labels.insert(zip, "Zip code");
+
+    assert_eq!(labels.len(), 3);
+    assert_eq!(labels.get(Form::FIELDS.name), Some(&"Full name"));
+    assert_eq!(labels.get(zip), Some(&"Zip code"));
+    assert!(!labels.contains(Form::FIELDS.address));
+
+    *labels.get_mut(Form::FIELDS.email).unwrap() = "Email";
+    assert_eq!(labels.remove(Form::FIELDS.email), Some("Email"));
+    assert!(!labels.contains(Form::FIELDS.email));
+
+    let mut paths = labels
+        .iter()
+        .map(|(path, label)| (path.to_string(), *label))
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    assert_eq!(
+        paths,
+        [
+            ("address.zip".to_string(), "Zip code"),
+            ("name".to_string(), "Full name"),
+        ]
+    );
+}
@@ -0,0 +1,66 @@
+#![feature(raw_ref_op)]
+#![cfg(feature = "alloc")]
+
+use gfp_core::*;
+
+#[derive(Field)]
+struct Settings {
+    volume: u8,
+    title:  String,
+    size:   (u32, u32),
+}
+
+fn settings() -> Settings {
+    Settings {
+        volume: 5,
+        title:  "untitled".to_string(),
+        size:   (640, 480),
+    }
+}
+
+#[test]
+fn dyn_field_set() {
+    let mut settings = settings();
+
+    let mut builder = FieldSetBuilder::new();
+    builder.extend(
+        ["title", "volume"]
+            .iter()
+            .map(|name| Settings::field_of(name.parse().unwrap())),
+    );
+    let set = builder.build().unwrap();
+
+    let mut fields = set.project_mut(&mut settings).into_iter();
+    let title = fields.next().unwrap();
+    let volume = fields.next().unwrap();
+    assert_eq!(title.name(), "title");
+    assert!(volume.is::<u8>());
+
+    let title = title.downcast::<u8>().err().unwrap();
+    let title = title.downcast::<String>().ok().unwrap();
+    let volume = volume.downcast::<u8>().ok().unwrap();
+
+    title.push_str(" (1)");
+    *volume += 1;
+
+    assert_eq!(settings.title, "untitled (1)");
+    assert_eq!(settings.volume, 6);
+
+    let fields = set.project(&settings);
+    assert_eq!(fields[1].name(), "volume");
+}
+
+#[test]
+fn overlapping_fields() {
+    let size = Settings::field_of(SettingsField::Size);
+    let width = Settings::FIELDS.size.chain(At0::<(u32, u32)>::NEW);
+
+    let err = FieldSetBuilder::new()
+        .with(size)
+        .with(AnyField::new("size.0", width))
+        .build()
+        .unwrap_err();
+
+    assert_eq!(err.fields(), ("size", "size.0"));
+    assert_eq!(err.to_string(), "`size.0` overlaps `size`");
+}
@@ -0,0 +1,93 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::{Field, ProjectTo, ProjectableGuard};
+
+use std::cell::{Ref, RefCell};
+
+struct Shared<'a, T>(Ref<'a, T>);
+
+unsafe impl<'a, P, T: 'a> ProjectableGuard<T> for Shared<'a, P> {
+    type Mapped = Shared<'a, T>;
+    type Target = P;
+
+    unsafe fn map_raw(
+        self,
+        project: impl FnOnce(*mut P) -> *mut T,
+    ) -> Shared<'a, T> {
+        Shared(Ref::map(self.0, |parent| {
+            &*project(parent as *const P as *mut P)
+        }))
+    }
+}
+
+gfp_core::projectable_guard! { impl['a, P] Shared<'a, P> }
+
+struct FooGuard(Ref<'static, Foo>);
+
+unsafe impl<T: 'static> ProjectableGuard<T> for FooGuard {
+    type Mapped = Shared<'static, T>;
+    type Target = Foo;
+
+    unsafe fn map_raw(
+        self,
+        project: impl FnOnce(*mut Foo) -> *mut T,
+    ) -> Shared<'static, T> {
+        Shared(Ref::map(self.0, |parent| {
+            &*project(parent as *const Foo as *mut Foo)
+        }))
+    }
+}
+
+gfp_core::projectable_guard! { impl FooGuard }
+
+#[derive(Field)]
+struct Foo {
+    x: u8,
+    y: Bar,
+}
+
+#[derive(Field)]
+struct Bar {
+    z: u32,
+}
+
+#[test]
+fn shared_guard() {
+    let foo = RefCell::new(Foo {
+        x: 1,
+        y: Bar {
+            z: 2,
+        },
+    });
+
+    let z = Shared(foo.borrow())
+        .project_to(Foo::fields().y)
+        .project_to(Bar::fields().z);
+
+    assert_eq!(*z.0, 2);
+    assert!(foo.try_borrow_mut().is_err());
+    assert_eq!(*Shared(foo.borrow()).project_to(Foo::fields().x).0, 1);
+
+    drop(z);
+    assert!(foo.try_borrow_mut().is_ok());
+}
+
+#[test]
+fn non_generic_guard() {
+    let foo: &'static RefCell<Foo> = Box::leak(Box::new(RefCell::new(Foo {
+        x: 1,
+        y: Bar {
+            z: 2,
+        },
+    })));
+
+    let z = FooGuard(foo.borrow())
+        .project_to(Foo::fields().y)
+        .project_to(Bar::fields().z);
+
+    assert_eq!(*z.0, 2);
+    assert!(foo.try_borrow_mut().is_err());
+
+    drop(z);
+    assert!(foo.try_borrow_mut().is_ok());
+}
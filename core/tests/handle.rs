@@ -0,0 +1,94 @@
+#![feature(raw_ref_op)]
+#![cfg(feature = "alloc")]
+
+use std::{cell::Cell, rc::Rc, sync::Arc, thread};
+
+use gfp_core::{ArcProjectExt, Field, OwnedFieldHandle};
+
+#[derive(Field)]
+struct Server {
+    name:   String,
+    config: Config,
+}
+
+#[derive(Field)]
+struct Config {
+    port:  u16,
+    hosts: Vec<String>,
+}
+
+#[derive(Field)]
+struct Client {
+    hosts: Vec<String>,
+}
+
+#[derive(Field)]
+struct Cache {
+    hits:  Cell<u32>,
+    owner: Rc<str>,
+}
+
+fn server() -> Arc<Server> {
+    Arc::new(Server {
+        name:   "api".to_string(),
+        config: Config {
+            port:  8080,
+            hosts: vec!["a".to_string()],
+        },
+    })
+}
+
+fn is_static_send_sync<T: Send + Sync + 'static>(_: &T) {
+}
+
+#[test]
+fn project_owned() {
+    let server = server();
+
+    let port = server.clone().project_owned(Server::FIELDS.config);
+    let port = port.project(Config::FIELDS.port);
+    is_static_send_sync(&port);
+
+    let clone = port.clone();
+    assert_eq!(thread::spawn(move || *clone).join().unwrap(), 8080);
+    assert_eq!(format!("{:?}", port), "8080");
+
+    // the handle keeps the data alive
+    assert_eq!(Arc::strong_count(&server), 2);
+    drop(port);
+    assert_eq!(Arc::strong_count(&server), 1);
+}
+
+#[test]
+fn different_parents() {
+    let client = Arc::new(Client {
+        hosts: vec!["b".to_string(), "c".to_string()],
+    });
+
+    let server_hosts = Server::FIELDS.config.chain(Config::FIELDS.hosts);
+    let handles: Vec<OwnedFieldHandle<Vec<String>>> = vec![
+        server().project_owned(server_hosts).erase(),
+        client.project_owned(Client::FIELDS.hosts).erase(),
+    ];
+
+    let lens = handles.iter().map(|hosts| hosts.len()).collect::<Vec<_>>();
+    assert_eq!(lens, [1, 2]);
+}
+
+#[test]
+fn local_parents() {
+    let cache = Arc::new(Cache {
+        hits:  Cell::new(0),
+        owner: Rc::from("main"),
+    });
+
+    // handles into parents which aren't `Send` or `Sync` stay on this thread
+    let hits = cache.clone().project_owned(Cache::FIELDS.hits);
+    hits.set(hits.get() + 1);
+
+    let owner = cache.project_owned(Cache::FIELDS.owner);
+    assert_eq!(&**owner, "main");
+    drop(owner);
+
+    assert_eq!(hits.get(), 1);
+}
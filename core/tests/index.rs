@@ -0,0 +1,15 @@
+use gfp_core::{Index, TryProjectTo};
+
+#[test]
+fn slice_index() {
+    let mut values = [1, 2, 3];
+
+    let slice: &mut [i32] = &mut values;
+    *slice.try_project_to(Index(2)).unwrap() = 10;
+
+    let slice: &mut [i32] = &mut values;
+    assert!(slice.try_project_to(Index(3)).is_none());
+
+    assert_eq!(values, [1, 2, 10]);
+    assert_eq!(unsafe { *Index(1).project_raw(&values[..]) }, 2);
+}
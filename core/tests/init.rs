@@ -0,0 +1,59 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::{Field, InitTracker};
+use std::{cell::RefCell, rc::Rc};
+
+type Log = Rc<RefCell<Vec<&'static str>>>;
+
+struct Noisy(&'static str, Log);
+
+impl Drop for Noisy {
+    fn drop(&mut self) {
+        self.1.borrow_mut().push(self.0)
+    }
+}
+
+#[derive(Field, Debug)]
+struct Handshake {
+    version: u8,
+    name:    String,
+}
+
+#[derive(Field)]
+struct Pair {
+    first:  Noisy,
+    second: Noisy,
+}
+
+#[test]
+fn assume_init() {
+    let mut tracker = InitTracker::<Handshake>::new();
+    assert!(!tracker.is_complete());
+
+    tracker.write(Handshake::FIELDS.name, "client".to_string());
+    assert!(tracker.is_init(Handshake::FIELDS.name));
+    assert_eq!(tracker.get(Handshake::FIELDS.version), None);
+    assert_eq!(tracker.missing().collect::<Vec<_>>(), ["version"]);
+
+    tracker.get_mut(Handshake::FIELDS.name).unwrap().push('!');
+
+    let mut tracker = tracker.try_assume_init().unwrap_err();
+    tracker.write(Handshake::FIELDS.version, 2);
+
+    let handshake = tracker.try_assume_init().unwrap();
+    assert_eq!(handshake.version, 2);
+    assert_eq!(handshake.name, "client!");
+}
+
+#[test]
+fn drops_written_fields() {
+    let log = Log::default();
+    let mut tracker = InitTracker::<Pair>::new();
+
+    tracker.write(Pair::FIELDS.second, Noisy("old", log.clone()));
+    tracker.write(Pair::FIELDS.second, Noisy("new", log.clone()));
+    assert_eq!(*log.borrow(), ["old"]);
+
+    drop(tracker);
+    assert_eq!(*log.borrow(), ["old", "new"]);
+}
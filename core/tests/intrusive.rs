@@ -0,0 +1,150 @@
+#![feature(raw_ref_op)]
+
+use core::{cell::Cell, marker::PhantomPinned, pin::Pin, ptr::NonNull};
+
+use gfp_core::{Adapter, Field, NodeIter, NodeIterMut, PinToPin};
+
+#[derive(Default)]
+struct Link {
+    prev: Cell<Option<NonNull<Link>>>,
+    next: Cell<Option<NonNull<Link>>>,
+}
+
+#[derive(Field)]
+struct Entry {
+    key:     u32,
+    by_age:  Link,
+    by_size: Link,
+    _pinned: PhantomPinned,
+}
+
+fn entry(key: u32) -> Entry {
+    Entry {
+        key,
+        by_age: Link::default(),
+        by_size: Link::default(),
+        _pinned: PhantomPinned,
+    }
+}
+
+/// Link `a` before `b`
+fn link(a: NonNull<Link>, b: NonNull<Link>) {
+    unsafe {
+        a.as_ref().next.set(Some(b));
+        b.as_ref().prev.set(Some(a));
+    }
+}
+
+/// The keys of the entries in the list which starts at `first`
+fn walk<F>(adapter: Adapter<F>, first: NonNull<Entry>) -> Vec<u32>
+where
+    F: Field<Parent = Entry, Type = Link>,
+{
+    let mut keys = Vec::new();
+    let mut link = Some(unsafe { adapter.link_ptr(first) });
+
+    while let Some(ptr) = link {
+        let entry = unsafe { adapter.value(ptr) };
+        keys.push(entry.key);
+        link = adapter.link(entry).next.get();
+    }
+
+    keys
+}
+
+#[test]
+fn round_trip() {
+    let adapter = Adapter::new(Entry::FIELDS.by_size);
+    let mut entry = entry(3);
+
+    let node = NonNull::from(&mut entry);
+    let link = unsafe { adapter.link_ptr(node) };
+    assert_eq!(link.as_ptr(), adapter.link_mut(&mut entry) as *mut Link);
+
+    let link = unsafe { adapter.link_ptr(NonNull::from(&mut entry)) };
+    assert_eq!(unsafe { adapter.value_ptr(link) }, NonNull::from(&entry));
+    assert_eq!(unsafe { adapter.value_mut(link) }.key, 3);
+}
+
+#[test]
+fn two_lists() {
+    let by_age = Adapter::new(Entry::FIELDS.by_age);
+    let by_size = Adapter::new(Entry::FIELDS.by_size);
+
+    let entries = [entry(1), entry(2), entry(3)];
+    let node = |index: usize| NonNull::from(&entries[index]);
+
+    unsafe {
+        link(by_age.link_ptr(node(0)), by_age.link_ptr(node(1)));
+        link(by_age.link_ptr(node(1)), by_age.link_ptr(node(2)));
+
+        link(by_size.link_ptr(node(2)), by_size.link_ptr(node(0)));
+        link(by_size.link_ptr(node(0)), by_size.link_ptr(node(1)));
+    }
+
+    assert_eq!(walk(by_age, node(0)), [1, 2, 3]);
+    assert_eq!(walk(by_size, node(2)), [3, 1, 2]);
+
+    let last = unsafe { by_age.link_ptr(node(2)) };
+    let prev = unsafe { last.as_ref() }.prev.get().unwrap();
+    assert_eq!(unsafe { by_age.value(prev) }.key, 2);
+}
+
+#[test]
+fn pinned() {
+    let by_age = unsafe { PinToPin::new_unchecked(Entry::FIELDS.by_age) };
+    let adapter = Adapter::new(by_age);
+
+    let mut entry = Box::pin(entry(7));
+
+    let link: Pin<&Link> = adapter.link_pin(entry.as_ref());
+    assert!(link.next.get().is_none());
+    let _: Pin<&mut Link> = adapter.link_pin_mut(entry.as_mut());
+
+    let node = unsafe { NonNull::from(entry.as_mut().get_unchecked_mut()) };
+    let link = unsafe { adapter.link_ptr(node) };
+
+    let entry: Pin<&mut Entry> = unsafe { adapter.value_pin_mut(link) };
+    assert_eq!(entry.key, 7);
+    assert_eq!(unsafe { adapter.value_pin(link) }.key, 7);
+}
+
+#[derive(Field)]
+struct Node {
+    value: u32,
+    next:  Option<NonNull<Node>>,
+}
+
+#[test]
+fn node_iter() {
+    let mut nodes = [1, 2, 3].map(|value| {
+        Node {
+            value,
+            next: None,
+        }
+    });
+
+    let base = nodes.as_mut_ptr();
+    for index in 0..2 {
+        unsafe {
+            (*base.add(index)).next = NonNull::new(base.add(index + 1));
+        }
+    }
+
+    let first = NonNull::new(base);
+
+    let mut iter = unsafe { NodeIterMut::new(first, Node::FIELDS.next) };
+    for node in &mut iter {
+        node.value *= 10;
+        // relinking doesn't change what is yielded next
+        node.next = None;
+    }
+    assert!(iter.next().is_none());
+
+    let iter = unsafe { NodeIter::new(first, Node::FIELDS.next) };
+    assert!(iter.map(|node| node.value).eq([10]));
+
+    let empty = unsafe { NodeIter::new(None, Node::FIELDS.next) };
+    assert_eq!(empty.count(), 0);
+    assert!(nodes.iter().map(|node| node.value).eq([10, 20, 30]));
+}
@@ -0,0 +1,139 @@
+#![feature(raw_ref_op)]
+
+#[cfg(feature = "alloc")]
+use gfp_core::collect_field;
+use gfp_core::{
+    extend_field, fold_field, mean_field, project_iter, project_iter_mut,
+    sum_field, Field, SelectExt,
+};
+use typsy::convert::Convert;
+
+#[derive(Field)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[test]
+fn column_mutation() {
+    let mut points = [
+        Point {
+            x: 1, y: 2
+        },
+        Point {
+            x: 3, y: 4
+        },
+    ];
+
+    let fields = Point::fields();
+
+    for y in project_iter_mut(&mut points, fields.y) {
+        *y *= 10;
+    }
+
+    let ys: Vec<i32> = project_iter(&points, fields.y).copied().collect();
+    assert_eq!(ys, [20, 40]);
+    assert_eq!(project_iter(&points, fields.x).rev().next(), Some(&3));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn collect_column() {
+    let points = vec![
+        Point {
+            x: 1, y: 2
+        },
+        Point {
+            x: 3, y: 4
+        },
+    ];
+
+    let fields = Point::fields();
+
+    let mut xs = collect_field(&points, fields.x);
+    assert_eq!(xs, [1, 3]);
+
+    extend_field(&mut xs, points.iter().rev(), fields.y);
+    assert_eq!(xs, [1, 3, 4, 2]);
+}
+
+#[derive(Field)]
+struct Line {
+    start: Point,
+    end:   Point,
+    label: String,
+}
+
+fn lines() -> Vec<Line> {
+    vec![
+        Line {
+            start: Point {
+                x: 0, y: 0
+            },
+            end:   Point {
+                x: 1, y: 1
+            },
+            label: "a".to_string(),
+        },
+        Line {
+            start: Point {
+                x: 2, y: 3
+            },
+            end:   Point {
+                x: 4, y: 5
+            },
+            label: "b".to_string(),
+        },
+    ]
+}
+
+#[test]
+fn select() {
+    let lines = lines();
+    let line = Line::fields();
+    let point = Point::fields();
+
+    let columns = (line.label, line.end.chain(point.y)).into_hlist();
+    let rows = lines.iter().select(columns);
+    assert_eq!(rows.size_hint(), (2, Some(2)));
+
+    let rows = rows
+        .map(|typsy::hlist_pat!(label, end_y)| format!("{}{}", label, end_y))
+        .collect::<Vec<_>>();
+    assert_eq!(rows, ["a1", "b5"]);
+}
+
+#[test]
+fn select_cloned() {
+    let lines = lines();
+    let line = Line::fields();
+    let point = Point::fields();
+
+    let columns = (line.label, line.start.chain(point.x)).into_hlist();
+    let mut rows = lines.iter().select_cloned(columns);
+
+    let typsy::hlist_pat!(label, start_x) = rows.next().unwrap();
+    assert_eq!((label, start_x), ("a".to_string(), 0));
+
+    let typsy::hlist_pat!(label, start_x) = rows.next().unwrap();
+    assert_eq!((label, start_x), ("b".to_string(), 2));
+
+    assert!(rows.next().is_none());
+}
+
+#[test]
+fn aggregate() {
+    let lines = lines();
+    let line = Line::fields();
+    let point = Point::fields();
+    let end_y = line.end.chain(point.y);
+
+    let sum: i32 = sum_field(&lines, end_y);
+    assert_eq!(sum, 6);
+    assert_eq!(mean_field(&lines, end_y), Some(3.0));
+    assert_eq!(mean_field(&lines[..0], end_y), None);
+
+    let labels =
+        fold_field(&lines, line.label, String::new(), |acc, label| acc + label);
+    assert_eq!(labels, "ab");
+}
@@ -0,0 +1,127 @@
+#![feature(raw_ref_op)]
+#![cfg(feature = "serde_json")]
+
+use gfp_core::{
+    json_patch::{apply_json_patch, JsonPatchError, JsonPatchErrorKind},
+    Field,
+};
+use serde_json::Value;
+
+#[derive(Field, Debug, PartialEq)]
+struct Account {
+    email:   String,
+    limits:  Limits,
+    backups: Vec<String>,
+}
+
+#[derive(Field, Debug, PartialEq)]
+struct Limits {
+    storage: u64,
+    expires: Option<u32>,
+}
+
+fn account() -> Account {
+    Account {
+        email:   "a@example.com".to_string(),
+        limits:  Limits {
+            storage: 100,
+            expires: Some(30),
+        },
+        backups: Vec::new(),
+    }
+}
+
+fn patch(account: &mut Account, json: &str) -> Result<(), JsonPatchError> {
+    let patch = serde_json::from_str::<Vec<Value>>(json).unwrap();
+    apply_json_patch(account, &patch)
+}
+
+#[test]
+fn add_replace_remove() {
+    let mut account = account();
+
+    let json = r#"[
+        { "op": "replace", "path": "/email", "value": "b@example.com" },
+        { "op": "add", "path": "/limits/storage", "value": 200 },
+        { "op": "remove", "path": "/limits/expires" },
+        { "op": "add", "path": "/backups", "value": ["daily"] }
+    ]"#;
+    patch(&mut account, json).unwrap();
+
+    assert_eq!(account.email, "b@example.com");
+    assert_eq!(account.limits.storage, 200);
+    assert_eq!(account.limits.expires, None);
+    assert_eq!(account.backups, ["daily"]);
+}
+
+#[test]
+fn replace_nested() {
+    let mut account = account();
+
+    let json = r#"[
+        { "op": "replace", "path": "/limits", "value": { "expires": 7 } }
+    ]"#;
+    patch(&mut account, json).unwrap();
+
+    assert_eq!(account.limits.storage, 100);
+    assert_eq!(account.limits.expires, Some(7));
+}
+
+#[test]
+fn stops_at_first_error() {
+    let mut account = account();
+
+    let json = r#"[
+        { "op": "replace", "path": "/limits/storage", "value": 1 },
+        { "op": "replace", "path": "/limits/quota", "value": 2 },
+        { "op": "replace", "path": "/email", "value": "c@example.com" }
+    ]"#;
+    let err = patch(&mut account, json).unwrap_err();
+
+    assert_eq!(err.op(), 1);
+    match err.kind() {
+        JsonPatchErrorKind::Path(err) => assert_eq!(err.name(), "quota"),
+        kind => panic!("unexpected error: {}", kind),
+    }
+
+    assert_eq!(account.limits.storage, 1);
+    assert_eq!(account.email, "a@example.com");
+}
+
+#[test]
+fn errors() {
+    let kind = |json: &str| {
+        let mut account = account();
+        patch(&mut account, json).unwrap_err().kind().to_string()
+    };
+
+    assert_eq!(
+        kind(r#"[{ "op": "remove", "path": "/email" }]"#),
+        "only `Option` fields can be removed"
+    );
+    assert_eq!(
+        kind(r#"[{ "op": "move", "from": "/email", "path": "/email" }]"#),
+        "`move` operations aren't supported"
+    );
+    assert_eq!(
+        kind(r#"[{ "op": "replace", "path": "/email" }]"#),
+        "the operation is malformed"
+    );
+    assert_eq!(
+        kind(r#"[{ "op": "replace", "path": "email", "value": "" }]"#),
+        "JSON pointers to fields must start with `/`"
+    );
+
+    let mut account = account();
+
+    let json = r#"[{ "op": "replace", "path": "/backups", "value": "a" }]"#;
+    let err = patch(&mut account, json).unwrap_err();
+    assert!(matches!(err.kind(), JsonPatchErrorKind::Value(_)));
+
+    let json = r#"[{ "op": "add", "path": "/limits/storage", "value": -1 }]"#;
+    let err = patch(&mut account, json).unwrap_err();
+    assert!(matches!(err.kind(), JsonPatchErrorKind::Value(_)));
+
+    assert!(account.backups.is_empty());
+    assert_eq!(account.limits.storage, 100);
+}
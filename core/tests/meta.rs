@@ -0,0 +1,56 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::*;
+
+#[derive(Field)]
+struct Request {
+    #[field(meta(unit = "ms", max = 5000, sensitive = false))]
+    timeout: u64,
+    #[field(meta(sensitive), display = "API key")]
+    api_key: String,
+    body:    Vec<u8>,
+}
+
+#[derive(Field)]
+struct Sample(#[field(meta(unit = "°C", scale = 0.5))] i16);
+
+#[test]
+fn meta_values() {
+    let timeout = Request::lookup_field("timeout").unwrap();
+    assert_eq!(timeout.meta("unit"), Some("ms"));
+    assert_eq!(timeout.meta("max"), Some("5000"));
+    assert_eq!(timeout.meta("sensitive"), Some("false"));
+    assert_eq!(timeout.meta("scale"), None);
+
+    let api_key = Request::lookup_field("api_key").unwrap();
+    assert_eq!(api_key.meta("sensitive"), Some(""));
+    assert_eq!(api_key.display_name(), "API key");
+
+    let sample = Sample::lookup_field("0").unwrap();
+    assert_eq!(sample.meta("unit"), Some("°C"));
+    assert_eq!(sample.meta("scale"), Some("0.5"));
+}
+
+#[test]
+fn meta_entries() {
+    let timeout = Request::lookup_field("timeout").unwrap();
+    assert_eq!(
+        timeout.meta_entries(),
+        [("unit", "ms"), ("max", "5000"), ("sensitive", "false")]
+    );
+
+    let body = Request::lookup_field("body").unwrap();
+    assert!(body.meta_entries().is_empty());
+}
+
+#[test]
+fn sensitive_fields() {
+    let sensitive = Request::field_names()
+        .iter()
+        .filter_map(|name| Request::lookup_field(name))
+        .filter(|field| field.meta("sensitive") == Some(""))
+        .map(|field| field.name())
+        .collect::<Vec<_>>();
+
+    assert_eq!(sensitive, ["api_key"]);
+}
@@ -0,0 +1,101 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::{migrate_fields, Field, FieldLookup};
+
+#[derive(Field)]
+struct UserV1 {
+    name:     String,
+    age:      u8,
+    nickname: String,
+    scores:   Vec<u32>,
+}
+
+#[derive(Field)]
+struct UserV2 {
+    name:   String,
+    age:    u16,
+    scores: Vec<u32>,
+    email:  Option<String>,
+}
+
+struct NotClone;
+
+#[derive(Field)]
+struct Handle {
+    id:    u32,
+    inner: NotClone,
+}
+
+fn user() -> UserV1 {
+    UserV1 {
+        name:     "ferris".to_string(),
+        age:      7,
+        nickname: "crab".to_string(),
+        scores:   vec![1, 2],
+    }
+}
+
+#[test]
+fn migrate() {
+    let old = user();
+    let mut new = UserV2 {
+        name:   String::new(),
+        age:    0,
+        scores: Vec::new(),
+        email:  Some("a@example.com".to_string()),
+    };
+
+    let migration = migrate_fields(&old, &mut new);
+
+    assert_eq!(new.name, "ferris");
+    assert_eq!(new.scores, [1, 2]);
+    // the types of `age` differ, and `email` is new
+    assert_eq!(new.age, 0);
+    assert_eq!(new.email.as_deref(), Some("a@example.com"));
+
+    assert_eq!(migration.copied(), 2);
+    assert!(migration.unmatched_from().eq(["age", "nickname"]));
+    assert!(migration.unmatched_to().eq(["age", "email"]));
+    assert!(!migration.is_complete());
+}
+
+#[test]
+fn same_version() {
+    let old = user();
+    let mut new = UserV1 {
+        name:     String::new(),
+        age:      0,
+        nickname: String::new(),
+        scores:   Vec::new(),
+    };
+
+    let migration = migrate_fields(&old, &mut new);
+
+    assert_eq!(migration.copied(), 4);
+    assert!(migration.is_complete());
+    assert_eq!(new.nickname, "crab");
+    assert_eq!(
+        format!("{:?}", migration),
+        "Migration { copied: 4, unmatched_from: [], unmatched_to: [] }"
+    );
+}
+
+#[test]
+fn not_clone() {
+    assert!(Handle::lookup_field("id").unwrap().is_clone());
+    assert!(!Handle::lookup_field("inner").unwrap().is_clone());
+
+    let from = Handle {
+        id:    1,
+        inner: NotClone,
+    };
+    let mut to = Handle {
+        id:    2,
+        inner: NotClone,
+    };
+
+    let migration = migrate_fields(&from, &mut to);
+
+    assert_eq!(to.id, 1);
+    assert!(migration.unmatched_to().eq(["inner"]));
+}
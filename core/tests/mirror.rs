@@ -0,0 +1,91 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::{Field, Mirror, MirrorField};
+use typsy::{convert::Convert, hlist::Nil};
+
+#[derive(Field)]
+struct Order {
+    id:       u64,
+    customer: Customer,
+    total:    f64,
+    notes:    String,
+}
+
+#[derive(Field)]
+struct Customer {
+    name: String,
+}
+
+#[derive(Field, Default)]
+struct OrderDto {
+    order_id:      u64,
+    customer_name: String,
+    total:         f64,
+}
+
+fn order() -> Order {
+    Order {
+        id:       1,
+        customer: Customer {
+            name: "ferris".to_string(),
+        },
+        total:    9.5,
+        notes:    "fragile".to_string(),
+    }
+}
+
+#[test]
+fn single_field() {
+    let total = MirrorField::new(Order::FIELDS.total, OrderDto::FIELDS.total);
+
+    let mut order = order();
+    let mut dto = OrderDto::default();
+    assert!(!total.is_synced(&order, &dto));
+
+    total.copy_a_to_b(&order, &mut dto);
+    assert_eq!(dto.total, 9.5);
+    assert!(total.is_synced(&order, &dto));
+
+    dto.total = 3.0;
+    total.copy_b_to_a(&dto, &mut order);
+    assert_eq!(order.total, 3.0);
+}
+
+#[test]
+fn mirror() {
+    let name = Order::FIELDS.customer.chain(Customer::FIELDS.name);
+    let mirror = Mirror::new(
+        (
+            MirrorField::new(Order::FIELDS.id, OrderDto::FIELDS.order_id),
+            MirrorField::new(name, OrderDto::FIELDS.customer_name),
+            MirrorField::new(Order::FIELDS.total, OrderDto::FIELDS.total),
+        )
+            .into_hlist(),
+    );
+
+    let mut order = order();
+    let mut dto = OrderDto::default();
+
+    mirror.copy_a_to_b(&order, &mut dto);
+    assert_eq!(dto.order_id, 1);
+    assert_eq!(dto.customer_name, "ferris");
+    assert!(mirror.is_synced(&order, &dto));
+
+    dto.customer_name = "crab".to_string();
+    assert!(!mirror.is_synced(&order, &dto));
+
+    mirror.copy_b_to_a(&dto, &mut order);
+    assert_eq!(order.customer.name, "crab");
+    assert_eq!(order.notes, "fragile");
+    assert!(mirror.is_synced(&order, &dto));
+}
+
+#[test]
+fn empty() {
+    let mirror = Mirror::new(Nil);
+    let mut dto = OrderDto::default();
+
+    mirror.copy_a_to_b(&order(), &mut dto);
+    assert!(mirror.is_synced(&order(), &dto));
+    assert_eq!(dto.order_id, 0);
+}
@@ -0,0 +1,36 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::*;
+
+#[derive(Field)]
+struct Foo {
+    bar:  Bar,
+    pair: (u8, u16),
+}
+
+#[derive(Field)]
+struct Bar(Tap, u32);
+
+#[derive(Field)]
+struct Tap {
+    r#val: u64,
+}
+
+#[test]
+fn names() {
+    assert!(Foo::FIELDS.bar.name().eq(["bar"]));
+    assert!(Bar::FIELDS._1.name().eq(["1"]));
+    assert!(Tap::FIELDS.val.name().eq(["val"]));
+
+    let path = Foo::FIELDS.bar.chain(Bar::FIELDS._0).chain(Tap::FIELDS.val);
+    assert!(path.name().eq(["bar", "0", "val"]));
+}
+
+#[test]
+fn display_path() {
+    let path = Foo::FIELDS.bar.chain(Bar::FIELDS._0).chain(Tap::FIELDS.val);
+    assert_eq!(path.display_path().to_string(), "bar.0.val");
+
+    let pair = Foo::FIELDS.pair.chain(At1::<(u8, u16)>::NEW);
+    assert_eq!(format!("{:?}", pair.display_path()), "pair.1");
+}
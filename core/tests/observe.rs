@@ -0,0 +1,83 @@
+#![feature(raw_ref_op)]
+#![cfg(feature = "observe")]
+
+use gfp_core::{
+    Field, ProjectTo,
+    observe::{
+        ObserverAlreadySet, Projection, ProjectionObserver,
+        set_projection_observer,
+    },
+};
+use std::{rc::Rc, sync::Mutex};
+
+#[derive(Field)]
+struct Outer {
+    tag:   u8,
+    inner: Inner,
+}
+
+#[derive(Field)]
+struct Inner {
+    a: u16,
+    b: u64,
+}
+
+struct Recorder(Mutex<Vec<Projection>>);
+
+impl ProjectionObserver for Recorder {
+    fn observe(&self, projection: &Projection) {
+        self.0.lock().unwrap().push(*projection)
+    }
+}
+
+static RECORDER: Recorder = Recorder(Mutex::new(Vec::new()));
+
+fn take() -> Vec<Projection> {
+    std::mem::take(&mut *RECORDER.0.lock().unwrap())
+}
+
+#[test]
+fn observes_projections() {
+    set_projection_observer(&RECORDER).unwrap();
+    assert_eq!(set_projection_observer(&RECORDER), Err(ObserverAlreadySet));
+
+    let mut outer = Outer {
+        tag:   1,
+        inner: Inner {
+            a: 2,
+            b: 3,
+        },
+    };
+    let parent = &outer as *const Outer as *const u8;
+    let b_offset = &outer.inner.b as *const u64 as usize - parent as usize;
+
+    take();
+    assert_eq!(
+        *(&outer).project_to(Outer::FIELDS.inner.chain(Inner::FIELDS.b)),
+        3
+    );
+    *(&mut outer).project_to(Outer::FIELDS.tag) = 4;
+
+    let events = take();
+    assert_eq!(events.len(), 2);
+
+    assert_eq!(events[0].parent(), parent);
+    assert_eq!(events[0].offset(), b_offset);
+    assert_eq!(events[0].parent_type(), std::any::type_name::<Outer>());
+    assert_eq!(events[0].field_type(), "u64");
+    assert!(events[0].path().contains("Chain"));
+
+    assert_eq!(events[1].parent(), parent);
+    assert_eq!(events[1].field_type(), "u8");
+
+    let rc = Rc::new(Inner {
+        a: 5,
+        b: 6,
+    });
+    assert_eq!(*rc.clone().project_to(Inner::FIELDS.a), 5);
+
+    let events = take();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].parent(), &*rc as *const Inner as *const u8);
+    assert_eq!(events[0].field_type(), "u16");
+}
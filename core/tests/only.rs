@@ -0,0 +1,87 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::*;
+
+#[derive(Field)]
+#[field(only(retries, name))]
+struct Config {
+    name:     String,
+    buffer:   [u8; 64],
+    retries:  u32,
+    checksum: u64,
+}
+
+#[derive(Field)]
+#[field(only(0, 2))]
+struct Triple(u8, String, u16);
+
+#[derive(Field)]
+#[repr(C)]
+#[field(only(b), require_repr_c)]
+struct Packed {
+    a: u8,
+    b: u32,
+}
+
+fn config() -> Config {
+    Config {
+        name:     "api".to_string(),
+        buffer:   [0; 64],
+        retries:  3,
+        checksum: 0,
+    }
+}
+
+#[test]
+fn listed_fields() {
+    let mut config = config();
+
+    *(&mut config).project_to(Config::FIELDS.retries) += 1;
+    assert_eq!(config.retries, 4);
+    assert_eq!((&config).project_to(Config::FIELDS.name), "api");
+
+    let typsy::hlist_pat!(name, retries) =
+        (&config).project_all(Config::ALL_FIELDS);
+    assert_eq!(name, "api");
+    assert_eq!(*retries, 4);
+
+    // the other fields are still there, they just don't have field types
+    assert_eq!(config.buffer, [0; 64]);
+    assert_eq!(config.checksum, 0);
+}
+
+#[test]
+fn unlisted_fields_are_left_out() {
+    assert_eq!(Config::FIELD_COUNT, 2);
+    assert_eq!(Config::field_names(), ["name", "retries"]);
+    assert!(Config::lookup_field("buffer").is_none());
+    assert!(Config::lookup_field("checksum").is_none());
+
+    let names = Config::STRUCT_INFO
+        .fields()
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["name", "retries"]);
+}
+
+#[test]
+fn tuple_fields_keep_their_index() {
+    let mut triple = Triple(1, "b".to_string(), 3);
+
+    *(&mut triple).project_to(Triple::FIELDS.2) = 4;
+    assert_eq!(triple.2, 4);
+    assert_eq!(triple.1, "b");
+    assert_eq!(*(&triple).project_to(Triple::FIELDS.0), 1);
+
+    assert_eq!(Triple::FIELD_COUNT, 2);
+    assert_eq!(Triple::field_names(), ["0", "2"]);
+}
+
+#[test]
+fn repr_c_with_only() {
+    let packed = Packed { a: 1, b: 2 };
+    assert_eq!(packed.a, 1);
+    assert_eq!(*(&packed).project_to(Packed::FIELDS.b), 2);
+    assert_eq!(Packed::FIELDS.b.descriptor().offset(), 4);
+}
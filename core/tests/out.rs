@@ -0,0 +1,98 @@
+#![feature(raw_ref_op)]
+
+use core::mem::MaybeUninit;
+
+use gfp_core::{Field, Out, ProjectAll, ProjectTo};
+use typsy::convert::Convert;
+
+#[derive(Field)]
+struct Header {
+    len:  u16,
+    kind: u8,
+}
+
+#[derive(Field)]
+struct Packet {
+    header:  Header,
+    payload: Vec<u8>,
+}
+
+fn write_header(mut out: Out<'_, Header>, len: u16) -> &mut Header {
+    out.reborrow().project_to(Header::FIELDS.len).write(len);
+    out.reborrow().project_to(Header::FIELDS.kind).write(1);
+
+    // every field was written
+    unsafe { &mut *out.as_mut_ptr() }
+}
+
+#[test]
+fn project_to() {
+    let mut packet = MaybeUninit::<Packet>::uninit();
+    let mut out = Out::from(&mut packet);
+
+    let header = out.reborrow().project_to(Packet::FIELDS.header);
+    let header = write_header(header, 3);
+    header.kind = 2;
+
+    let payload = out.project_to(Packet::FIELDS.payload);
+    payload.write(vec![1, 2, 3]);
+
+    let packet = unsafe { packet.assume_init() };
+
+    assert_eq!(packet.header.len, 3);
+    assert_eq!(packet.header.kind, 2);
+    assert_eq!(packet.payload, [1, 2, 3]);
+}
+
+#[test]
+fn from_maybe_uninit() {
+    let mut header = MaybeUninit::<Header>::uninit();
+
+    let fields = Header::fields();
+    let typsy::hlist_pat!(len, kind) =
+        (&mut header).project_all((fields.len, fields.kind).into_hlist());
+
+    let len: &mut MaybeUninit<u16> = len;
+    Out::from(len).write(8);
+    Out::from(kind).write(3);
+
+    let header = unsafe { header.assume_init() };
+
+    assert_eq!(header.len, 8);
+    assert_eq!(header.kind, 3);
+}
+
+#[test]
+fn project_all() {
+    let mut packet = MaybeUninit::<Packet>::uninit();
+
+    let fields = Packet::fields();
+    let len = fields.header.chain(Header::FIELDS.len);
+    let kind = fields.header.chain(Header::FIELDS.kind);
+    let fields = (len, kind, fields.payload).into_hlist();
+
+    let typsy::hlist_pat!(len, kind, payload) =
+        Out::from(&mut packet).project_all(fields);
+
+    len.write(0);
+    kind.write(4);
+    payload.write(Vec::new());
+
+    let packet = unsafe { packet.assume_init() };
+
+    assert_eq!(packet.header.len, 0);
+    assert_eq!(packet.header.kind, 4);
+    assert!(packet.payload.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Found overlapping fields")]
+fn project_all_overlapping() {
+    let mut packet = MaybeUninit::<Packet>::uninit();
+
+    let fields = Packet::fields();
+    let len = fields.header.chain(Header::FIELDS.len);
+    let fields = (fields.header, len).into_hlist();
+
+    let _ = Out::from(&mut packet).project_all(fields);
+}
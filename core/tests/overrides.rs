@@ -0,0 +1,134 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::{apply_overrides, set_field_from_str, Field, OverrideErrorKind};
+use std::{net::SocketAddr, str::FromStr};
+
+#[derive(Field)]
+struct Config {
+    name:    String,
+    server:  Server,
+    retries: Option<u8>,
+    tags:    Vec<String>,
+    level:   Level,
+}
+
+#[derive(Field)]
+struct Server {
+    addr:    SocketAddr,
+    port:    u16,
+    verbose: bool,
+}
+
+#[derive(Debug, PartialEq)]
+enum Level {
+    Info,
+    Debug,
+}
+
+impl FromStr for Level {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            _ => Err(()),
+        }
+    }
+}
+
+fn config() -> Config {
+    Config {
+        name:    "api".to_string(),
+        server:  Server {
+            addr:    "127.0.0.1:80".parse().unwrap(),
+            port:    80,
+            verbose: false,
+        },
+        retries: None,
+        tags:    Vec::new(),
+        level:   Level::Info,
+    }
+}
+
+#[test]
+fn overrides() {
+    let mut config = config();
+
+    let overrides = [
+        "server.port=8080",
+        "server.verbose=true",
+        "server.addr=0.0.0.0:443",
+        "retries=3",
+        "name=",
+    ];
+    apply_overrides(&mut config, &overrides).unwrap();
+
+    assert_eq!(config.server.port, 8080);
+    assert!(config.server.verbose);
+    assert_eq!(config.server.addr, "0.0.0.0:443".parse().unwrap());
+    assert_eq!(config.retries, Some(3));
+    assert_eq!(config.name, "");
+}
+
+#[test]
+fn any_from_str() {
+    let mut config = config();
+
+    apply_overrides(&mut config, &["level=debug"]).unwrap();
+    assert_eq!(config.level, Level::Debug);
+
+    let err = apply_overrides(&mut config, &["level=trace"]).unwrap_err();
+    assert!(matches!(err.kind(), OverrideErrorKind::InvalidValue { .. }));
+    assert_eq!(config.level, Level::Debug);
+}
+
+#[test]
+fn owned_overrides() {
+    let mut config = config();
+
+    let overrides = vec!["name=x=y".to_string()];
+    apply_overrides(&mut config, overrides).unwrap();
+
+    assert_eq!(config.name, "x=y");
+}
+
+#[test]
+fn set_field() {
+    let mut config = config();
+
+    set_field_from_str(&mut config, "server.port", "1").unwrap();
+    assert_eq!(config.server.port, 1);
+
+    let err = set_field_from_str(&mut config, "server", "1").unwrap_err();
+    assert!(matches!(err, OverrideErrorKind::Unsupported { .. }));
+}
+
+#[test]
+fn errors() {
+    let mut config = config();
+
+    let overrides = ["server.port=1", "server.port", "name=b"];
+    let err = apply_overrides(&mut config, &overrides).unwrap_err();
+    assert_eq!(err.index(), 1);
+    assert_eq!(*err.kind(), OverrideErrorKind::Malformed);
+    // the overrides before the error are already applied
+    assert_eq!(config.server.port, 1);
+    assert_eq!(config.name, "api");
+
+    let err = apply_overrides(&mut config, &["server.host=a"]).unwrap_err();
+    match err.kind() {
+        OverrideErrorKind::Path(err) => assert_eq!(err.name(), "host"),
+        kind => panic!("unexpected error: {}", kind),
+    }
+
+    let err = apply_overrides(&mut config, &["server.port=-1"]).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "override 0 failed: the value isn't a valid `u16`"
+    );
+    assert_eq!(config.server.port, 1);
+
+    let err = apply_overrides(&mut config, &["tags=a"]).unwrap_err();
+    assert!(matches!(err.kind(), OverrideErrorKind::Unsupported { .. }));
+}
@@ -0,0 +1,68 @@
+#![feature(raw_ref_op)]
+
+#[cfg(feature = "alloc")]
+use std::{rc::Rc, sync::Arc, thread};
+
+use gfp_core::{Field, OwnedProjection};
+
+#[derive(Field)]
+struct Config {
+    name:   String,
+    limits: Limits,
+}
+
+#[derive(Field, Debug)]
+struct Limits {
+    memory: u64,
+    cpus:   u8,
+}
+
+fn config() -> Config {
+    Config {
+        name:   "api".to_string(),
+        limits: Limits {
+            memory: 512,
+            cpus:   4,
+        },
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn projects_further() {
+    let limits = OwnedProjection::new(Rc::new(config()), Config::FIELDS.limits);
+    assert_eq!(limits.cpus, 4);
+    assert_eq!(format!("{:?}", limits), "Limits { memory: 512, cpus: 4 }");
+
+    let cpus = limits.project(Limits::FIELDS.cpus);
+    assert_eq!(*cpus, 4);
+    assert_eq!(cpus.owner().name, "api");
+    assert_eq!(cpus.into_owner().limits.memory, 512);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn shared_owners() {
+    let rc = Rc::new(config());
+    let name = OwnedProjection::new(rc.clone(), Config::FIELDS.name);
+    let clone = name.clone();
+    drop(name);
+    assert_eq!(*clone, "api");
+    assert_eq!(Rc::strong_count(&rc), 2);
+
+    let memory = Config::FIELDS.limits.chain(Limits::FIELDS.memory);
+    let memory = OwnedProjection::new(Arc::new(config()), memory);
+    let memory = thread::spawn(move || *memory).join().unwrap();
+    assert_eq!(memory, 512);
+}
+
+#[test]
+fn other_owners() {
+    let names = ["a".to_string(), "b".to_string()];
+    let names = OwnedProjection::from_owner(&names[..]);
+    assert_eq!(names.len(), 2);
+
+    let config = config();
+    let limits = OwnedProjection::new(&config, Config::FIELDS.limits);
+    assert_eq!(limits.memory, 512);
+}
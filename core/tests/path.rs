@@ -0,0 +1,130 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::*;
+
+#[derive(Field)]
+struct Config {
+    name:    String,
+    network: Network,
+}
+
+#[derive(Field)]
+struct Network {
+    port:    u16,
+    address: Address,
+}
+
+#[derive(Field)]
+struct Address(u8, u8, u8, u8);
+
+#[derive(Field)]
+struct Wrapper<T>(T);
+
+fn config() -> Config {
+    Config {
+        name:    "server".to_string(),
+        network: Network {
+            port:    8080,
+            address: Address(127, 0, 0, 1),
+        },
+    }
+}
+
+#[test]
+fn parse_path() {
+    let config = config();
+
+    let port: FieldPath<Config> = "network.port".parse().unwrap();
+    assert!(port.is::<u16>());
+    assert_eq!(*config.project_to(port.downcast::<u16>().unwrap()), 8080);
+    assert!(port.downcast::<u32>().is_none());
+
+    let last: FieldPath<Config> = "network.address.3".parse().unwrap();
+    assert_eq!(*config.project_to(last.downcast::<u8>().unwrap()), 1);
+    assert_eq!(
+        last.descriptor().offset(),
+        Config::FIELDS
+            .network
+            .chain(Network::FIELDS.address)
+            .chain(Address::FIELDS._3)
+            .field_offset()
+    );
+}
+
+#[test]
+fn unknown_segment() {
+    let err = "network.host".parse::<FieldPath<Config>>().unwrap_err();
+    assert_eq!(err.segment(), 1);
+    assert_eq!(err.name(), "host");
+    assert!(err.parent().ends_with("Network"));
+
+    let err = "name.len".parse::<FieldPath<Config>>().unwrap_err();
+    assert_eq!(err.segment(), 1);
+    assert!(err.parent().ends_with("String"));
+
+    let err = "".parse::<FieldPath<Config>>().unwrap_err();
+    assert_eq!(err.segment(), 0);
+}
+
+#[test]
+fn generic_parent() {
+    assert!("0".parse::<FieldPath<Wrapper<Address>>>().is_ok());
+
+    // fields of a generic type can't be looked into
+    assert!("0.1".parse::<FieldPath<Wrapper<Address>>>().is_err());
+}
+
+#[test]
+fn owned_path() {
+    use std::collections::{BTreeSet, HashSet};
+
+    let address =
+        FieldPath::of(Config::FIELDS.network.chain(Network::FIELDS.address));
+    assert_eq!(address.segments(), ["network", "address"]);
+    assert_eq!(address.to_string(), "network.address");
+    assert_eq!(address, "network.address".parse().unwrap());
+
+    let paths = ["network.port", "name", "network.address.0", "network.port"];
+    let sorted = paths
+        .iter()
+        .map(|path| path.parse::<FieldPath<Config>>().unwrap())
+        .collect::<BTreeSet<_>>()
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(sorted, ["name", "network.address.0", "network.port"]);
+
+    let mut set = HashSet::new();
+    assert!(set.insert(address));
+    assert!(!set.insert(FieldPath::of(
+        Config::FIELDS.network.chain(Network::FIELDS.address)
+    )));
+}
+
+#[test]
+fn json_pointer() {
+    let config = config();
+
+    let port = FieldPath::<Config>::from_json_pointer("/network/port").unwrap();
+    assert_eq!(port, "network.port".parse().unwrap());
+    assert_eq!(*config.project_to(port.downcast::<u16>().unwrap()), 8080);
+
+    let last = FieldPath::<Config>::from_json_pointer("/network/address/3");
+    let last = last.unwrap();
+    assert_eq!(last.json_pointer().to_string(), "/network/address/3");
+    assert_eq!(FieldPath::from_json_pointer("/network/address/3"), Ok(last));
+
+    let err = FieldPath::<Config>::from_json_pointer("/network/host");
+    let err = err.unwrap_err();
+    assert_eq!(err.segment(), 1);
+    assert_eq!(err.name(), "host");
+
+    // escaped segments never name a field
+    let err = FieldPath::<Config>::from_json_pointer("/network/a~1b");
+    assert_eq!(err.unwrap_err().name(), "a~1b");
+
+    for pointer in ["", "network/port", "#/network/port"] {
+        let err = FieldPath::<Config>::from_json_pointer(pointer);
+        assert!(err.unwrap_err().is_invalid_pointer());
+    }
+}
@@ -0,0 +1,64 @@
+#![feature(raw_ref_op)]
+#![cfg(feature = "pin-cell")]
+
+use core::{marker::PhantomPinned, pin::Pin};
+
+use gfp_core::{Field, PinMutField, PinToPin, PinToPtr, ProjectTo};
+use pin_cell::PinCell;
+
+struct Timer {
+    ticks:   u32,
+    _pinned: PhantomPinned,
+}
+
+#[derive(Field)]
+struct Task {
+    future:  Timer,
+    polls:   u32,
+    name:    String,
+    _pinned: PhantomPinned,
+}
+
+fn task() -> Pin<Box<PinCell<Task>>> {
+    Box::pin(PinCell::new(Task {
+        future:  Timer {
+            ticks:   0,
+            _pinned: PhantomPinned,
+        },
+        polls:   0,
+        name:    "task".to_string(),
+        _pinned: PhantomPinned,
+    }))
+}
+
+#[test]
+fn pinned_field() {
+    let task = task();
+
+    let future = unsafe { PinToPin::new_unchecked(Task::FIELDS.future) };
+    let mut timer = task.as_ref().borrow_mut().project_to(future);
+
+    let pinned: Pin<&mut Timer> = PinMutField::as_mut(&mut timer);
+    unsafe { pinned.get_unchecked_mut().ticks += 1 };
+    assert_eq!(timer.ticks, 1);
+}
+
+#[test]
+fn unpinned_fields() {
+    let task = task();
+
+    let mut polls = task.as_ref().borrow_mut().project_to(Task::FIELDS.polls);
+    *PinMutField::as_mut(&mut polls) += 1;
+    assert_eq!(format!("{:?}", polls), "1");
+
+    let guard = PinMutField::into_inner(polls);
+    assert_eq!(guard.polls, 1);
+    drop(guard);
+
+    let name = PinToPtr::new(Task::FIELDS.name);
+    let mut name = task.as_ref().borrow_mut().project_to(name);
+    PinMutField::as_mut(&mut name).push('!');
+    drop(name);
+
+    assert_eq!(task.borrow().name, "task!");
+}
@@ -0,0 +1,62 @@
+#![feature(raw_ref_op)]
+
+use core::{marker::PhantomPinned, mem::MaybeUninit, pin::Pin, ptr};
+
+use gfp_core::{Field, PinToPin, PinToPtr};
+
+#[derive(Field)]
+struct Parser {
+    buffer:  [u8; 4],
+    cursor:  *const u8,
+    _pinned: PhantomPinned,
+}
+
+impl Parser {
+    /// Initialize the parser in place, with `cursor` pointing into `buffer`
+    fn init(mut slot: Pin<&mut MaybeUninit<Self>>, data: [u8; 4]) {
+        let fields = Self::fields();
+
+        let buffer_field = unsafe { PinToPin::new_unchecked(fields.buffer) };
+        let buffer = buffer_field.project_uninit(slot.as_mut());
+        let buffer = unsafe { buffer.get_unchecked_mut().write(data) };
+        let cursor = buffer.as_ptr();
+
+        PinToPtr::new(fields.cursor)
+            .project_uninit(slot.as_mut())
+            .write(cursor);
+        PinToPtr::new(fields._pinned)
+            .project_uninit(slot.as_mut())
+            .write(PhantomPinned);
+    }
+
+    fn peek(self: Pin<&Self>) -> u8 {
+        unsafe { *self.cursor }
+    }
+}
+
+#[test]
+fn init_in_place() {
+    let mut slot = Box::pin(MaybeUninit::<Parser>::uninit());
+    Parser::init(slot.as_mut(), [1, 2, 3, 4]);
+
+    let parser = slot.as_ref();
+    let parser = unsafe { parser.map_unchecked(|p| p.assume_init_ref()) };
+
+    assert!(ptr::eq(parser.cursor, &parser.buffer[0]));
+    assert_eq!(parser.peek(), 1);
+}
+
+#[test]
+fn slots_are_fields() {
+    let mut slot = Box::pin(MaybeUninit::<Parser>::uninit());
+    let start = slot.as_ptr() as usize;
+
+    let fields = Parser::fields();
+    let buffer = unsafe { PinToPin::new_unchecked(fields.buffer) };
+    let buffer = buffer.project_uninit(slot.as_mut()).as_ptr() as usize;
+    let cursor = PinToPtr::new(fields.cursor);
+    let cursor = cursor.project_uninit(slot.as_mut()).as_ptr() as usize;
+
+    assert_eq!(buffer - start, fields.buffer.field_offset());
+    assert_eq!(cursor - start, fields.cursor.field_offset());
+}
@@ -0,0 +1,109 @@
+#![feature(raw_ref_op)]
+
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+use gfp_core::{Field, PinProjectExt, PinToPin};
+
+/// Ready with `value` after being polled `delay` times
+struct Countdown {
+    delay: u8,
+    value: u32,
+}
+
+impl Countdown {
+    fn new(delay: u8, value: u32) -> Self {
+        Self {
+            delay,
+            value,
+        }
+    }
+}
+
+impl Future for Countdown {
+    type Output = u32;
+
+    fn poll(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<u32> {
+        if self.delay == 0 {
+            Poll::Ready(self.value)
+        } else {
+            self.delay -= 1;
+            Poll::Pending
+        }
+    }
+}
+
+#[derive(Field)]
+struct Task {
+    main:    Countdown,
+    cleanup: Option<Countdown>,
+}
+
+#[test]
+fn poll_field() {
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut task = Task {
+        main:    Countdown::new(1, 2),
+        cleanup: None,
+    };
+    let mut task = Pin::new(&mut task);
+
+    let main = unsafe { PinToPin::new_unchecked(Task::FIELDS.main) };
+
+    assert_eq!(task.as_mut().poll_field(main, &mut cx), Poll::Pending);
+    assert_eq!(task.as_mut().poll_field(main, &mut cx), Poll::Ready(2));
+}
+
+#[test]
+fn poll_option_field() {
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut task = Task {
+        main:    Countdown::new(0, 0),
+        cleanup: Some(Countdown::new(1, 3)),
+    };
+    let mut pin = Pin::new(&mut task);
+
+    let cleanup = unsafe { PinToPin::new_unchecked(Task::FIELDS.cleanup) };
+
+    assert_eq!(
+        pin.as_mut().poll_option_field(cleanup, &mut cx),
+        Poll::Pending
+    );
+    assert_eq!(
+        pin.as_mut().poll_option_field(cleanup, &mut cx),
+        Poll::Ready(Some(3))
+    );
+    assert_eq!(
+        pin.as_mut().poll_option_field(cleanup, &mut cx),
+        Poll::Ready(None)
+    );
+    assert!(task.cleanup.is_none());
+}
+
+#[test]
+fn option_field() {
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut task = Task {
+        main:    Countdown::new(0, 0),
+        cleanup: None,
+    };
+    let mut pin = Pin::new(&mut task);
+
+    let cleanup = unsafe { PinToPin::new_unchecked(Task::FIELDS.cleanup) };
+
+    assert!(pin.as_mut().project_option_field(cleanup).is_none());
+
+    pin.as_mut()
+        .set_option_field(cleanup, Some(Countdown::new(1, 4)));
+
+    let future = pin.as_mut().project_option_field(cleanup).unwrap();
+    assert_eq!(future.poll(&mut cx), Poll::Pending);
+
+    let future = pin.as_mut().take_option_field(cleanup).unwrap();
+    assert_eq!(future.delay, 0);
+    assert_eq!(future.value, 4);
+    assert!(task.cleanup.is_none());
+}
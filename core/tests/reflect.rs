@@ -0,0 +1,62 @@
+#![feature(raw_ref_op)]
+#![cfg(feature = "bevy_reflect")]
+
+use bevy_reflect::Reflect;
+use gfp_core::{reflect, Field, FieldPath};
+
+#[derive(Field, Reflect)]
+struct Player {
+    stats: Stats,
+    items: Vec<u32>,
+}
+
+#[derive(Field, Reflect)]
+struct Stats {
+    speed: f32,
+    jump:  f32,
+}
+
+fn player() -> Player {
+    let stats = Stats {
+        speed: 1.0,
+        jump:  2.0,
+    };
+
+    Player {
+        stats,
+        items: vec![3, 4],
+    }
+}
+
+#[test]
+fn field_paths() {
+    let mut player = player();
+
+    *reflect::path_mut::<_, f32>(&mut player, "stats.jump").unwrap() = 5.0;
+    assert_eq!(player.stats.jump, 5.0);
+
+    let speed = reflect::path::<_, f32>(&player, ".stats.speed").unwrap();
+    assert_eq!(*speed, 1.0);
+}
+
+#[test]
+fn reflection_fallback() {
+    let mut player = player();
+
+    *reflect::path_mut::<_, u32>(&mut player, "items[1]").unwrap() = 6;
+    assert_eq!(player.items, [3, 6]);
+
+    assert!(reflect::path::<_, u32>(&player, "stats.speed").is_err());
+    assert!(reflect::path::<_, f32>(&player, "stats.nope").is_err());
+}
+
+#[test]
+fn to_parsed_path() {
+    let jump = Player::FIELDS.stats.chain(Stats::FIELDS.jump);
+
+    let path = reflect::to_parsed_path(&FieldPath::of(jump));
+    assert_eq!(path.to_string(), "stats.jump");
+
+    let path = reflect::field_to_parsed_path(&jump);
+    assert_eq!(path.to_string(), "stats.jump");
+}
@@ -0,0 +1,49 @@
+#![feature(raw_ref_op)]
+#![cfg(feature = "registers")]
+
+use gfp_core::{
+    registers::{Bits, Volatile},
+    Field,
+};
+
+#[derive(Field)]
+#[repr(C)]
+struct Timer {
+    count:  u16,
+    reload: u16,
+    ctrl:   u32,
+}
+
+const ENABLE: Bits<u32> = Bits::new(0, 1);
+const PRESCALE: Bits<u32> = Bits::new(8, 4);
+
+#[test]
+fn volatile_registers() {
+    let mut block = Timer {
+        count:  0,
+        reload: 0,
+        ctrl:   0xffff_0000,
+    };
+
+    let timer = unsafe { Volatile::new(&mut block as *mut Timer) };
+
+    timer.project(Timer::FIELDS.reload).write(100);
+    timer.project(Timer::FIELDS.count).modify(|count| count + 1);
+
+    let ctrl = timer.project(Timer::FIELDS.ctrl);
+    ctrl.bits(ENABLE).write(1);
+    ctrl.bits(PRESCALE).write(0x1f);
+    assert_eq!(ctrl.bits(PRESCALE).read(), 0xf);
+
+    assert_eq!(block.count, 1);
+    assert_eq!(block.reload, 100);
+    assert_eq!(block.ctrl, 0xffff_0f01);
+}
+
+#[test]
+fn bits() {
+    assert_eq!(PRESCALE.mask(), 0xf00);
+    assert_eq!(PRESCALE.get(0x1234), 0x2);
+    assert_eq!(PRESCALE.set(0x1234, 0x5), 0x1534);
+    assert_eq!(Bits::<u64>::new(0, 64).mask(), u64::MAX);
+}
@@ -0,0 +1,69 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::{Field, Registry, RegistryEntry, ResolveError};
+
+#[derive(Field)]
+struct Config {
+    net: Net,
+    id:  u8,
+}
+
+#[derive(Field)]
+struct Net {
+    port: u16,
+}
+
+static TYPES: [RegistryEntry; 2] = [
+    RegistryEntry::of::<Config>("Config"),
+    RegistryEntry::of::<Net>("Net"),
+];
+
+static REGISTRY: Registry = Registry::new(&TYPES);
+
+#[test]
+fn resolve() {
+    let net = Net {
+        port: 80,
+    };
+    let config = Config {
+        net,
+        id: 3,
+    };
+
+    let port = REGISTRY.resolve("Config.net.port").unwrap();
+    assert!(port.is::<u16>());
+    assert_eq!(port.root().name(), "Config");
+    assert_eq!(port.segments(), ["net", "port"]);
+
+    let ptr = unsafe { port.project_raw((&config as *const Config).cast()) };
+    assert_eq!(unsafe { *ptr.cast::<u16>() }, 80);
+
+    let id = REGISTRY.resolve("Config.id").unwrap();
+    assert!(id.is::<u8>());
+    let ptr = unsafe { id.project_raw((&config as *const Config).cast()) };
+    assert_eq!(unsafe { *ptr }, 3);
+}
+
+#[test]
+fn field_names() {
+    assert_eq!(REGISTRY.get("Config").unwrap().field_names(), ["net", "id"]);
+    assert_eq!(REGISTRY.get("Net").unwrap().field_names(), ["port"]);
+    assert_eq!(REGISTRY.entries().len(), 2);
+}
+
+#[test]
+fn errors() {
+    assert_eq!(
+        REGISTRY.resolve("Nope.port").unwrap_err(),
+        ResolveError::UnknownType
+    );
+    assert_eq!(
+        REGISTRY.resolve("Config").unwrap_err(),
+        ResolveError::NoFields
+    );
+
+    match REGISTRY.resolve("Config.net.host") {
+        Err(ResolveError::Path(err)) => assert_eq!(err.segment(), 1),
+        other => panic!("unexpected {:?}", other),
+    }
+}
@@ -0,0 +1,103 @@
+#![feature(raw_ref_op)]
+
+use std::{cell::RefCell, marker::PhantomPinned, panic, pin::Pin, rc::Rc};
+
+use gfp_core::{Field, PinProjectExt, PinToPtr};
+use typsy::{convert::Convert, hlist::Nil};
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+/// Logs its name when it is dropped, and panics if `panics` is set
+struct Noisy {
+    name:   String,
+    log:    Log,
+    panics: bool,
+}
+
+impl Drop for Noisy {
+    fn drop(&mut self) {
+        self.log.borrow_mut().push(self.name.clone());
+
+        if self.panics {
+            panic!("{} panicked", self.name)
+        }
+    }
+}
+
+#[derive(Field)]
+struct State {
+    name:    Noisy,
+    buffer:  Noisy,
+    waiter:  Noisy,
+    _pinned: PhantomPinned,
+}
+
+fn state(log: &Log, prefix: &str, panics: bool) -> State {
+    let noisy = |field: &str, panics: bool| {
+        Noisy {
+            name: format!("{}.{}", prefix, field),
+            log: log.clone(),
+            panics,
+        }
+    };
+
+    State {
+        name:    noisy("name", false),
+        buffer:  noisy("buffer", panics),
+        waiter:  noisy("waiter", false),
+        _pinned: PhantomPinned,
+    }
+}
+
+#[test]
+fn moves_unpinned_fields() {
+    let log = Log::default();
+    let mut pin = Box::pin(state(&log, "a", false));
+
+    let fields = (PinToPtr::new(State::FIELDS.buffer),).into_hlist();
+    let typsy::hlist_pat!(buffer) = unsafe {
+        pin.as_mut()
+            .project_replace(state(&log, "b", false), fields)
+    };
+
+    assert_eq!(*log.borrow(), ["a.name", "a.waiter"]);
+    assert_eq!(buffer.name, "a.buffer");
+    assert_eq!(pin.buffer.name, "b.buffer");
+
+    drop(buffer);
+    let Nil =
+        unsafe { pin.as_mut().project_replace(state(&log, "c", false), Nil) };
+    drop(pin);
+
+    let log = log.borrow();
+    assert_eq!(log[2..6], ["a.buffer", "b.name", "b.buffer", "b.waiter"]);
+    assert_eq!(log[6..], ["c.name", "c.buffer", "c.waiter"]);
+}
+
+#[test]
+fn panicking_drop() {
+    let log = Log::default();
+    let mut pin = Box::pin(state(&log, "a", true));
+
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| unsafe {
+        pin.as_mut().project_replace(state(&log, "b", false), Nil)
+    }));
+
+    // the rest of the fields are still dropped, and the value is replaced
+    assert!(result.is_err());
+    assert_eq!(*log.borrow(), ["a.name", "a.buffer", "a.waiter"]);
+    assert_eq!(pin.name.name, "b.name");
+}
+
+#[test]
+#[should_panic(expected = "the field was already moved out")]
+fn same_field_twice() {
+    let log = Log::default();
+    let mut pin: Pin<Box<State>> = Box::pin(state(&log, "a", false));
+
+    let name = PinToPtr::new(State::FIELDS.name);
+    let _ = unsafe {
+        pin.as_mut()
+            .project_replace(state(&log, "b", false), (name, name).into_hlist())
+    };
+}
@@ -0,0 +1,43 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::*;
+
+#[derive(Field)]
+struct State {
+    conn: Result<Conn, String>,
+}
+
+#[derive(Field)]
+struct Conn {
+    addr: (u8, u16),
+}
+
+fn port(state: &State) -> Result<u16, &String> {
+    let port = State::FIELDS
+        .conn
+        .ok_then(Conn::FIELDS.addr)
+        .chain(At1::<(u8, u16)>::NEW);
+
+    Ok(*state.project_ok(port)?)
+}
+
+#[test]
+fn project_ok() {
+    let mut state = State {
+        conn: Ok(Conn {
+            addr: (1, 80),
+        }),
+    };
+
+    assert_eq!(port(&state), Ok(80));
+
+    let addr = State::FIELDS.conn.ok_then(Conn::FIELDS.addr);
+    (&mut state).project_ok(addr).unwrap().0 = 2;
+    assert_eq!(state.conn.as_ref().unwrap().addr, (2, 80));
+
+    state.conn = Err("refused".to_string());
+    assert_eq!(port(&state), Err(&"refused".to_string()));
+
+    (&mut state).project_ok(addr).unwrap_err().push('!');
+    assert_eq!(state.conn.err().unwrap(), "refused!");
+}
@@ -0,0 +1,64 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::{Field, ScopedSet};
+use std::panic::{self, AssertUnwindSafe};
+
+#[derive(Field, Debug)]
+struct Config {
+    log_level: String,
+    retries:   u32,
+}
+
+fn config() -> Config {
+    Config {
+        log_level: "info".to_string(),
+        retries:   3,
+    }
+}
+
+#[test]
+fn restores_on_drop() {
+    let mut config = config();
+
+    {
+        let level = "trace".to_string();
+        let mut guard = Config::FIELDS.log_level.scoped_set(&mut config, level);
+
+        assert_eq!(guard.log_level, "trace");
+        assert_eq!(guard.old(), "info");
+
+        guard.retries = 0;
+    }
+
+    assert_eq!(config.log_level, "info");
+    assert_eq!(config.retries, 0);
+}
+
+#[test]
+fn nested() {
+    let mut config = config();
+
+    {
+        let mut outer = Config::FIELDS.retries.scoped_set(&mut config, 1);
+        {
+            let inner = Config::FIELDS.retries.scoped_set(&mut outer, 2);
+            assert_eq!(inner.retries, 2);
+        }
+        assert_eq!(outer.retries, 1);
+    }
+
+    assert_eq!(config.retries, 3);
+}
+
+#[test]
+fn restores_on_unwind() {
+    let mut config = config();
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let _guard = Config::FIELDS.retries.scoped_set(&mut config, 10);
+        panic!("test failed");
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(config.retries, 3);
+}
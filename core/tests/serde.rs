@@ -0,0 +1,170 @@
+#![feature(raw_ref_op)]
+#![cfg(feature = "serde")]
+
+use gfp_core::{
+    serde::{deserialize_into, SerializeFields},
+    Field,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use typsy::{convert::Convert, hlist::Nil};
+
+#[derive(Field)]
+struct Server {
+    host:    String,
+    limits:  Limits,
+    api_key: String,
+    tags:    Vec<String>,
+    mode:    Mode,
+    pid:     Pid,
+}
+
+#[derive(Field)]
+struct Limits {
+    connections: u32,
+    timeout:     Option<f64>,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum Mode {
+    Serving,
+    Draining,
+}
+
+/// Doesn't implement `Deserialize`
+#[derive(Debug, PartialEq)]
+struct Pid(u32);
+
+fn server() -> Server {
+    Server {
+        host:    "localhost".to_string(),
+        limits:  Limits {
+            connections: 16,
+            timeout:     None,
+        },
+        api_key: "secret".to_string(),
+        tags:    vec!["local".to_string()],
+        mode:    Mode::Serving,
+        pid:     Pid(1),
+    }
+}
+
+#[test]
+fn subset() {
+    let server = server();
+    let fields = Server::fields();
+    let fields = (fields.host, fields.api_key).into_hlist();
+
+    let json = serde_json::to_string(&SerializeFields::new(&server, fields));
+
+    assert_eq!(json.unwrap(), r#"{"host":"localhost","api_key":"secret"}"#);
+}
+
+#[test]
+fn nested_paths() {
+    let server = server();
+    let limits = Server::FIELDS.limits;
+    let fields = (
+        limits.chain(Limits::FIELDS.timeout),
+        limits.chain(Limits::FIELDS.connections),
+    )
+        .into_hlist();
+
+    let json = serde_json::to_string(&SerializeFields::new(&server, fields));
+    let json = json.unwrap();
+
+    assert_eq!(json, r#"{"limits.timeout":null,"limits.connections":16}"#);
+}
+
+#[test]
+fn empty() {
+    let server = server();
+
+    let json = serde_json::to_string(&SerializeFields::new(&server, Nil));
+
+    assert_eq!(json.unwrap(), "{}");
+}
+
+fn patch(server: &mut Server, json: &str) -> Result<(), serde_json::Error> {
+    let value = serde_json::from_str::<Value>(json).unwrap();
+    deserialize_into(server, value)
+}
+
+#[test]
+fn patch_fields() {
+    let mut server = server();
+
+    let json = r#"{ "host": "example.com", "limits": { "timeout": 2.5 } }"#;
+    patch(&mut server, json).unwrap();
+
+    assert_eq!(server.host, "example.com");
+    assert_eq!(server.limits.connections, 16);
+    assert_eq!(server.limits.timeout, Some(2.5));
+    assert_eq!(server.api_key, "secret");
+}
+
+#[test]
+fn patch_nothing() {
+    let mut server = server();
+
+    patch(&mut server, "{}").unwrap();
+    patch(&mut server, r#"{ "limits": {} }"#).unwrap();
+
+    assert_eq!(server.host, "localhost");
+    assert_eq!(server.limits.connections, 16);
+}
+
+#[test]
+fn patch_option_to_none() {
+    let mut server = server();
+    server.limits.timeout = Some(1.0);
+
+    patch(&mut server, r#"{ "limits": { "timeout": null } }"#).unwrap();
+
+    assert_eq!(server.limits.timeout, None);
+}
+
+#[test]
+fn patch_any_deserialize() {
+    let mut server = server();
+
+    let json = r#"{ "tags": ["a", "b"], "mode": "draining" }"#;
+    patch(&mut server, json).unwrap();
+
+    assert_eq!(server.tags, ["a", "b"]);
+    assert_eq!(server.mode, Mode::Draining);
+}
+
+#[test]
+fn patch_unknown_field() {
+    let mut server = server();
+
+    let err = patch(&mut server, r#"{ "limits": { "retries": 3 } }"#);
+    let err = err.unwrap_err().to_string();
+
+    assert!(err.contains("has no field named `retries`"), "{}", err);
+}
+
+#[test]
+fn patch_unsupported_type() {
+    let mut server = server();
+
+    let err = patch(&mut server, r#"{ "host": "a", "pid": 2 }"#);
+    let err = err.unwrap_err().to_string();
+
+    assert!(err.contains("`pid` has the type"), "{}", err);
+    // the fields before the error are already updated
+    assert_eq!(server.host, "a");
+    assert_eq!(server.pid, Pid(1));
+}
+
+#[test]
+fn patch_wrong_type() {
+    let mut server = server();
+
+    let json = r#"{ "limits": { "connections": "16" } }"#;
+    assert!(patch(&mut server, json).is_err());
+    assert!(patch(&mut server, r#"{ "limits": 16 }"#).is_err());
+    assert_eq!(server.limits.connections, 16);
+}
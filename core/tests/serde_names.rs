@@ -0,0 +1,68 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::{Field, FieldLookup, FieldPath, NamedField};
+use serde::Serialize;
+
+#[derive(Field, Serialize)]
+#[field(serde_names)]
+#[serde(rename_all = "camelCase")]
+struct Request {
+    request_id: u64,
+    #[serde(rename = "user")]
+    user_name:  String,
+    retry:      Retry,
+}
+
+#[derive(Field, Serialize)]
+#[field(serde_names)]
+#[serde(rename_all = "SCREAMING-KEBAB-CASE")]
+struct Retry {
+    max_attempts: u8,
+    #[serde(rename(serialize = "backoff", deserialize = "delay"))]
+    backoff_ms:   u32,
+}
+
+#[derive(Field, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct Plain {
+    some_field: u8,
+}
+
+#[test]
+fn renamed_names() {
+    let fields = Request::fields();
+    assert_eq!(fields.request_id.display_path().to_string(), "requestId");
+    assert_eq!(fields.user_name.display_path().to_string(), "user");
+
+    let backoff = fields.retry.chain(Retry::FIELDS.backoff_ms);
+    assert_eq!(backoff.display_path().to_string(), "retry.backoff");
+
+    assert_eq!(Request::field_names(), ["requestId", "user", "retry"]);
+    assert_eq!(Retry::field_names(), ["MAX-ATTEMPTS", "backoff"]);
+    assert!(Request::lookup_field("user_name").is_none());
+
+    // without `serde_names`, the Rust names are used
+    assert_eq!(Plain::field_names(), ["some_field"]);
+}
+
+#[test]
+fn paths_match_serialized_keys() {
+    let request = Request {
+        request_id: 1,
+        user_name:  "ferris".to_string(),
+        retry:      Retry {
+            max_attempts: 3,
+            backoff_ms:   100,
+        },
+    };
+
+    let json = serde_json::to_value(&request).unwrap();
+    let keys = json.as_object().unwrap().keys().collect::<Vec<_>>();
+    let mut names = Request::field_names().to_vec();
+    names.sort_unstable();
+    assert_eq!(keys, names);
+
+    let path = "retry.MAX-ATTEMPTS".parse::<FieldPath<Request>>().unwrap();
+    assert_eq!(json["retry"]["MAX-ATTEMPTS"], 3);
+    assert!(path.is::<u8>());
+}
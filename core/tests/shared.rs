@@ -0,0 +1,78 @@
+#![feature(raw_ref_op)]
+#![cfg(feature = "shared-memory")]
+
+use core::sync::atomic::Ordering;
+
+use gfp_core::{
+    shared::{AtomicPolicy, Shared, VolatilePolicy},
+    Field,
+};
+
+#[derive(Field)]
+#[repr(C)]
+struct RingHeader {
+    head:     u32,
+    tail:     u32,
+    capacity: u32,
+    stats:    Stats,
+}
+
+#[derive(Field)]
+#[repr(C)]
+struct Stats {
+    dropped: u32,
+}
+
+fn header() -> RingHeader {
+    RingHeader {
+        head:     0,
+        tail:     0,
+        capacity: 16,
+        stats:    Stats {
+            dropped: 0,
+        },
+    }
+}
+
+#[test]
+fn policies() {
+    let mut mapping = header();
+    let shared: Shared<RingHeader, AtomicPolicy> =
+        unsafe { Shared::new(&mut mapping as *mut RingHeader) };
+
+    shared
+        .field(RingHeader::FIELDS.tail)
+        .fetch_add(3, Ordering::Release);
+    let tail = shared
+        .field(RingHeader::FIELDS.tail)
+        .load(Ordering::Acquire);
+    assert_eq!(tail, 3);
+
+    let capacity = RingHeader::FIELDS.capacity;
+    let capacity = shared.field_as::<VolatilePolicy, _>(capacity);
+    assert_eq!(capacity.read(), 16);
+    capacity.write(32);
+
+    let volatile = shared.with_policy::<VolatilePolicy>();
+    volatile
+        .field(RingHeader::FIELDS.head)
+        .modify(|head| head + 1);
+
+    assert_eq!(mapping.head, 1);
+    assert_eq!(mapping.tail, 3);
+    assert_eq!(mapping.capacity, 32);
+}
+
+#[test]
+fn nested() {
+    let mut mapping = header();
+    let shared: Shared<RingHeader, AtomicPolicy> =
+        unsafe { Shared::new(&mut mapping as *mut RingHeader) };
+
+    let stats = shared.project(RingHeader::FIELDS.stats);
+    stats
+        .field(Stats::FIELDS.dropped)
+        .fetch_add(1, Ordering::Relaxed);
+
+    assert_eq!(mapping.stats.dropped, 1);
+}
@@ -0,0 +1,93 @@
+#![feature(raw_ref_op)]
+#![cfg(feature = "alloc")]
+
+use gfp_core::{split, Field};
+
+#[derive(Default, Field)]
+struct Foo {
+    x: u8,
+    y: Bar,
+    z: u128,
+}
+
+#[derive(Default, Field)]
+struct Bar {
+    a: u16,
+    b: u32,
+}
+
+#[test]
+fn disjoint() {
+    let mut value = Foo::default();
+
+    let foo = Foo::fields();
+    let bar = Bar::fields();
+
+    split(&mut value, |splitter| {
+        let x = splitter.claim(foo.x).unwrap();
+        let y_a = splitter.claim(foo.y.chain(bar.a).dynamic()).unwrap();
+        let z = splitter.claim(foo.z).unwrap();
+
+        *x = 1;
+        *y_a = 2;
+        *z = 3;
+    });
+
+    assert_eq!(value.x, 1);
+    assert_eq!(value.y.a, 2);
+    assert_eq!(value.z, 3);
+}
+
+#[test]
+fn overlapping() {
+    let mut value = Foo::default();
+
+    let foo = Foo::fields();
+    let bar = Bar::fields();
+
+    split(&mut value, |splitter| {
+        assert!(splitter.claim(foo.y.chain(bar.b)).is_some());
+        assert!(splitter.claim(foo.y).is_none());
+        assert!(splitter.claim(foo.y.chain(bar.b)).is_none());
+        assert!(splitter.claim(foo.y.chain(bar.a)).is_some());
+    });
+}
+
+#[derive(Default, Field)]
+struct Counters {
+    a: u32,
+    b: u32,
+    c: u32,
+}
+
+#[test]
+fn claim_in_a_loop() {
+    let mut value = Counters::default();
+
+    let fields = Counters::fields();
+    let names = ["c", "a", "c"];
+
+    split(&mut value, |splitter| {
+        let mut claimed = Vec::new();
+
+        for name in names {
+            let field = match name {
+                "a" => fields.a.dynamic(),
+                "b" => fields.b.dynamic(),
+                _ => fields.c.dynamic(),
+            };
+
+            if let Some(counter) = splitter.claim(field) {
+                claimed.push(counter);
+            }
+        }
+
+        assert_eq!(claimed.len(), 2);
+
+        for counter in claimed {
+            *counter += 1;
+        }
+    });
+
+    assert_eq!((value.a, value.b, value.c), (1, 0, 1));
+}
@@ -0,0 +1,92 @@
+#![feature(raw_ref_op)]
+
+use core::{
+    cmp::Reverse,
+    num::{Saturating, Wrapping},
+    ops::Range,
+    task::Poll,
+};
+
+use gfp_core::{
+    std_fields::{
+        OptionSome, PollReady, RangeEnd, RangeInclusiveEnd,
+        RangeInclusiveStart, RangeStart, ResultErr, ResultOk, ReverseValue,
+        SaturatingValue, WrappingValue,
+    },
+    Field, ProjectTo, TryProjectTo,
+};
+
+#[derive(Field)]
+struct Span {
+    bytes:    Range<usize>,
+    count:    Wrapping<u8>,
+    total:    Saturating<u8>,
+    priority: Reverse<u32>,
+}
+
+#[test]
+fn chain_through_std_types() {
+    let mut span = Span {
+        bytes:    3..7,
+        count:    Wrapping(255),
+        total:    Saturating(250),
+        priority: Reverse(2),
+    };
+
+    let fields = Span::fields();
+
+    assert_eq!(*(&span).project_to(fields.bytes.chain(RangeStart::NEW)), 3);
+    assert_eq!(*(&span).project_to(fields.bytes.chain(RangeEnd::NEW)), 7);
+
+    *(&mut span).project_to(fields.count.chain(WrappingValue::NEW)) = 1;
+    assert_eq!(span.count, Wrapping(1));
+}
+
+#[test]
+fn newtype_values() {
+    let mut span = Span {
+        bytes:    0..0,
+        count:    Wrapping(0),
+        total:    Saturating(250),
+        priority: Reverse(2),
+    };
+
+    let fields = Span::fields();
+
+    *(&mut span).project_to(fields.total.chain(SaturatingValue::NEW)) += 5;
+    assert_eq!(span.total, Saturating(255));
+
+    let priority = fields.priority.chain(ReverseValue::NEW);
+    assert_eq!(*(&span).project_to(priority), 2);
+    *(&mut span).project_to(priority) = 3;
+    assert_eq!(span.priority, Reverse(3));
+}
+
+#[test]
+fn range_inclusive_bounds() {
+    let range = 2..=9;
+
+    assert_eq!((&range).try_project_to(RangeInclusiveStart), Some(&2));
+    assert_eq!((&range).try_project_to(RangeInclusiveEnd), Some(&9));
+}
+
+#[test]
+fn variants() {
+    let mut some = Some(1);
+    *(&mut some).try_project_to(OptionSome).unwrap() = 2;
+    assert_eq!(some, Some(2));
+    assert_eq!((&None::<u8>).try_project_to(OptionSome), None);
+
+    let mut ok = Ok::<u8, &str>(1);
+    assert_eq!((&ok).try_project_to(ResultOk), Some(&1));
+    assert_eq!((&mut ok).try_project_to(ResultErr), None);
+
+    let err = Err::<u8, &str>("bad");
+    assert_eq!((&err).try_project_to(ResultErr), Some(&"bad"));
+    assert_eq!((&err).try_project_to(ResultOk), None);
+
+    let mut ready = Poll::Ready(3);
+    *(&mut ready).try_project_to(PollReady).unwrap() += 1;
+    assert_eq!(ready, Poll::Ready(4));
+    assert_eq!((&Poll::<u8>::Pending).try_project_to(PollReady), None);
+}
@@ -0,0 +1,99 @@
+#![feature(raw_ref_op)]
+#![cfg(feature = "futures")]
+
+use core::{
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+use futures_core::Stream;
+use futures_sink::Sink;
+use gfp_core::{Field, PinStreamExt, PinToPin};
+
+struct Counter(u32);
+
+impl Stream for Counter {
+    type Item = u32;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+    ) -> Poll<Option<u32>> {
+        self.0 += 1;
+        Poll::Ready(Some(self.0))
+    }
+}
+
+struct Collect(Vec<u32>);
+
+impl Sink<u32> for Collect {
+    type Error = ();
+
+    fn poll_ready(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+    ) -> Poll<Result<(), ()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: u32) -> Result<(), ()> {
+        self.0.push(item);
+        Ok(())
+    }
+
+    fn poll_flush(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+    ) -> Poll<Result<(), ()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+    ) -> Poll<Result<(), ()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[derive(Field)]
+struct Pipe {
+    source: Counter,
+    sink:   Collect,
+}
+
+#[test]
+fn stream_and_sink_fields() {
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut pipe = Pipe {
+        source: Counter(0),
+        sink:   Collect(Vec::new()),
+    };
+    let mut pin = Pin::new(&mut pipe);
+
+    let source = unsafe { PinToPin::new_unchecked(Pipe::FIELDS.source) };
+    let sink = unsafe { PinToPin::new_unchecked(Pipe::FIELDS.sink) };
+
+    for _ in 0..2 {
+        let item = match pin.as_mut().poll_next_field(source, &mut cx) {
+            Poll::Ready(Some(item)) => item,
+            _ => unreachable!(),
+        };
+
+        assert_eq!(
+            pin.as_mut().poll_ready_field(sink, &mut cx),
+            Poll::Ready(Ok(()))
+        );
+        pin.as_mut().start_send_field(sink, item).unwrap();
+    }
+
+    assert_eq!(
+        pin.as_mut().poll_flush_field::<_, u32>(sink, &mut cx),
+        Poll::Ready(Ok(()))
+    );
+    assert_eq!(
+        pin.as_mut().poll_close_field::<_, u32>(sink, &mut cx),
+        Poll::Ready(Ok(()))
+    );
+    assert_eq!(pipe.sink.0, [1, 2]);
+}
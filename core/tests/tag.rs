@@ -0,0 +1,53 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::*;
+
+#[derive(Field)]
+struct Message {
+    #[field(tag = 2)]
+    id:    u64,
+    #[field(tag = 1)]
+    body:  String,
+    cache: Vec<u8>,
+}
+
+#[derive(Field)]
+struct Pair(#[field(tag = 7)] u8, u16);
+
+#[derive(Field)]
+struct Untagged {
+    value: u32,
+}
+
+/// The tagged fields of `P`, in the order of their tags
+fn tagged<P: FieldLookup>() -> Vec<(u32, &'static str)> {
+    let mut tags = P::field_names()
+        .iter()
+        .filter_map(|name| P::lookup_field(name))
+        .filter_map(|field| Some((field.tag()?, field.name())))
+        .collect::<Vec<_>>();
+    tags.sort();
+    tags
+}
+
+#[test]
+fn by_tag() {
+    let id = Message::field_by_tag(2).unwrap();
+    assert_eq!(id.name(), "id");
+    assert_eq!(id.descriptor(), Message::FIELDS.id.descriptor());
+
+    assert_eq!(Message::field_by_tag(1).unwrap().name(), "body");
+    assert!(Message::field_by_tag(0).is_none());
+    assert!(Message::field_by_tag(3).is_none());
+
+    assert_eq!(Pair::field_by_tag(7).unwrap().name(), "0");
+    assert!(Untagged::field_by_tag(0).is_none());
+}
+
+#[test]
+fn tags_in_lookup() {
+    assert_eq!(Message::lookup_field("cache").unwrap().tag(), None);
+    assert_eq!(tagged::<Message>(), [(1, "body"), (2, "id")]);
+    assert_eq!(tagged::<Pair>(), [(7, "0")]);
+    assert!(tagged::<Untagged>().is_empty());
+}
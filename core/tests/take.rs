@@ -0,0 +1,91 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::{take_set, Field, TakeField};
+use std::panic::{self, AssertUnwindSafe};
+use typsy::convert::Convert;
+
+#[derive(Debug, Default, PartialEq)]
+enum State {
+    #[default]
+    Idle,
+    Running(u32),
+}
+
+#[derive(Field)]
+struct Machine {
+    state: State,
+    ticks: u32,
+}
+
+fn step(state: State) -> State {
+    match state {
+        State::Idle => State::Running(0),
+        State::Running(n) => State::Running(n + 1),
+    }
+}
+
+#[test]
+fn with_taken() {
+    let mut machine = Machine {
+        state: State::Idle,
+        ticks: 0,
+    };
+
+    Machine::FIELDS.state.with_taken(&mut machine, step);
+    Machine::FIELDS.state.with_taken(&mut machine, step);
+
+    assert_eq!(machine.state, State::Running(1));
+    assert_eq!(machine.ticks, 0);
+}
+
+#[test]
+fn with_taken_or_default() {
+    let mut machine = Machine {
+        state: State::Running(3),
+        ticks: 0,
+    };
+
+    Machine::FIELDS
+        .state
+        .with_taken_or_default(&mut machine, step);
+    assert_eq!(machine.state, State::Running(4));
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        Machine::FIELDS
+            .state
+            .with_taken_or_default(&mut machine, |_| panic!("failed"))
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(machine.state, State::Idle);
+}
+
+#[test]
+fn take_set_leaves_defaults() {
+    let mut machine = Machine {
+        state: State::Running(2),
+        ticks: 5,
+    };
+
+    let fields = Machine::fields();
+    let fields = (fields.ticks, fields.state).into_hlist();
+    let typsy::hlist_pat!(ticks, state) = take_set(&mut machine, fields);
+
+    assert_eq!(ticks, 5);
+    assert_eq!(state, State::Running(2));
+    assert_eq!(machine.state, State::Idle);
+    assert_eq!(machine.ticks, 0);
+}
+
+#[test]
+#[should_panic(expected = "Found overlapping fields")]
+fn take_set_overlapping() {
+    let mut machine = Machine {
+        state: State::Idle,
+        ticks: 0,
+    };
+
+    let fields = Machine::fields();
+    let fields = (fields.ticks, fields.ticks).into_hlist();
+    let _ = take_set(&mut machine, fields);
+}
@@ -51,7 +51,7 @@ struct MyType<T> {
 
 #[test]
 pub fn generic_test() {
-    impl<T> MyType_fields::z<MyType<T>> {
+    impl<T> my_type_fields::Z<MyType<T>> {
         pub fn pin(self) -> PinToPin<Self> {
             unsafe { PinToPin::new_unchecked(self) }
         }
@@ -103,3 +103,55 @@ fn test_chain() {
 
     assert_eq!(*my_type.project_to(foo.y.chain(bar.c).chain(quaz.r)), 5);
 }
+
+#[test]
+fn test_tuple_chain() {
+    use gfp_core::{At0, At1};
+
+    let mut my_type = Quaz {
+        q: (3, 4),
+        r: 5,
+    };
+
+    let quaz = Quaz::fields();
+
+    assert_eq!(*my_type.project_to(quaz.q.chain(At0::<(u16, u32)>::NEW)), 3);
+
+    *(&mut my_type).project_to(quaz.q.chain(At1::<(u16, u32)>::NEW)) = 10;
+    assert_eq!(my_type.q, (3, 10));
+}
+
+#[test]
+fn test_field_ext() {
+    use gfp_core::FieldExt;
+
+    let mut foo = Foo::default();
+
+    let foo_fields = Foo::fields();
+    let bar = Bar::fields();
+
+    *(&mut foo).field_mut(foo_fields.y.chain(bar.b)) = 7;
+    *foo.field_mut(foo_fields.x) = 2;
+
+    assert_eq!(*(&foo).field(foo_fields.y.chain(bar.b)), 7);
+    assert_eq!(*foo.field(foo_fields.x), 2);
+}
+
+#[test]
+fn test_tuple_path() {
+    use gfp_core::{At1, IntoChain};
+
+    let mut my_type = Foo::default();
+
+    let foo = Foo::fields();
+    let bar = Bar::fields();
+    let quaz = Quaz::fields();
+
+    let path = (foo.y, bar.c, quaz.q, At1::<(u16, u32)>::NEW);
+
+    *(&mut my_type).project_to(path) = 10;
+    assert_eq!(my_type.y.c.q, (0, 10));
+
+    assert_eq!(*my_type.project_to(path.into_chain()), 10);
+    assert_eq!(path.field_offset(), path.into_chain().field_offset());
+}
@@ -68,6 +68,104 @@ fn pin() {
     assert_eq!(value.y.a, 10);
 }
 
+#[test]
+fn pin_project_ext() {
+    use gfp_core::{PinProjectExt, PinToPin, PinToPtr};
+    use std::pin::Pin;
+
+    let foo = Foo::fields();
+    let bar = Bar::fields();
+
+    let mut value = Foo::default();
+    let mut value_ref = Pin::new(&mut value);
+
+    let mut z: Pin<&mut u128> = value_ref
+        .as_mut()
+        .project(unsafe { PinToPin::new_unchecked(foo.z) });
+    *z = 3;
+
+    let typsy::hlist_pat!(mut x, y_a) = value_ref.project_set(
+        (
+            unsafe { PinToPin::new_unchecked(foo.x) },
+            PinToPtr::new(foo.y.chain(bar.a)),
+        )
+            .into_hlist(),
+    );
+
+    *x = 1;
+    *y_a = 10;
+
+    assert_eq!(value.x, 1);
+    assert_eq!(value.y.a, 10);
+    assert_eq!(value.z, 3);
+}
+
+#[test]
+#[allow(clippy::field_reassign_with_default)]
+fn pin_shared() {
+    use gfp_core::{PinToPin, PinToPtr};
+    use std::pin::Pin;
+
+    let foo = Foo::fields();
+    let bar = Bar::fields();
+
+    let mut value = Foo::default();
+    value.x = 1;
+    value.y.a = 10;
+
+    let value_ref = Pin::new(&value);
+
+    let typsy::hlist_pat!(x, y_a) = value_ref.project_all(
+        (
+            unsafe { PinToPin::new_unchecked(foo.x) },
+            PinToPtr::new(foo.y.chain(bar.a)),
+        )
+            .into_hlist(),
+    );
+
+    let x: Pin<&u8> = x;
+    let y_a: &u16 = y_a;
+
+    assert_eq!(*x, 1);
+    assert_eq!(*y_a, 10);
+}
+
+#[test]
+fn uninit() {
+    use std::mem::MaybeUninit;
+
+    let foo = Foo::fields();
+
+    let mut value = MaybeUninit::<Foo>::uninit();
+
+    let typsy::hlist_pat!(x, y, z) =
+        (&mut value).project_all((foo.x, foo.y, foo.z).into_hlist());
+
+    x.write(1);
+    z.write(3);
+    let y = y.write(Bar::default());
+    y.a = 10;
+
+    let value = unsafe { value.assume_init() };
+
+    assert_eq!(value.x, 1);
+    assert_eq!(value.y.a, 10);
+    assert_eq!(value.z, 3);
+}
+
+#[test]
+#[should_panic(expected = "Found overlapping fields")]
+fn uninit_overlapping() {
+    use std::mem::MaybeUninit;
+
+    let foo = Foo::fields();
+    let bar = Bar::fields();
+
+    let mut value = MaybeUninit::<Foo>::uninit();
+
+    let _ = (&mut value).project_all((foo.y, foo.y.chain(bar.a)).into_hlist());
+}
+
 #[test]
 #[cfg(feature = "alloc")]
 #[allow(clippy::field_reassign_with_default)]
@@ -0,0 +1,76 @@
+#![feature(raw_ref_op)]
+#![cfg(feature = "tokio")]
+
+use std::{sync::Arc, thread};
+
+use gfp_core::{Field, ProjectTo};
+use tokio::sync::{Mutex, RwLock};
+
+#[derive(Field)]
+struct State {
+    hits:   u64,
+    stats:  Stats,
+    labels: Vec<String>,
+}
+
+#[derive(Field)]
+struct Stats {
+    errors: u32,
+}
+
+fn state() -> State {
+    State {
+        hits:   0,
+        stats:  Stats {
+            errors: 0,
+        },
+        labels: vec!["a".to_string()],
+    }
+}
+
+#[test]
+fn mutex() {
+    let state = Arc::new(Mutex::new(state()));
+
+    let guard = state.clone().try_lock_owned().unwrap();
+    let mut hits = guard.project_to(State::FIELDS.hits);
+
+    // the mapped guard is `'static` and `Send`, so it can move to a thread
+    thread::spawn(move || *hits += 1).join().unwrap();
+
+    let guard = state.clone().try_lock_owned().unwrap();
+    let mut errors = guard
+        .project_to(State::FIELDS.stats)
+        .project_to(Stats::FIELDS.errors);
+    *errors = 2;
+    drop(errors);
+
+    let state = state.try_lock().unwrap();
+    assert_eq!(state.hits, 1);
+    assert_eq!(state.stats.errors, 2);
+}
+
+#[test]
+fn rw_lock() {
+    let state = Arc::new(RwLock::new(state()));
+
+    let guard = state.clone().try_write_owned().unwrap();
+    let mut labels = guard.project_to(State::FIELDS.labels);
+    thread::spawn(move || labels.push("b".to_string()))
+        .join()
+        .unwrap();
+
+    let guard = state.clone().try_read_owned().unwrap();
+    let labels = guard.project_to(State::FIELDS.labels);
+    let len = thread::spawn(move || labels.len()).join().unwrap();
+    assert_eq!(len, 2);
+
+    let guard = state.clone().try_write_owned().unwrap();
+    let mut errors = guard
+        .project_to(State::FIELDS.stats)
+        .project_to(Stats::FIELDS.errors);
+    *errors += 1;
+    drop(errors);
+
+    assert_eq!(state.try_read().unwrap().stats.errors, 1);
+}
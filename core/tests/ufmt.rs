@@ -0,0 +1,53 @@
+#![feature(raw_ref_op)]
+#![cfg(feature = "ufmt")]
+
+use gfp_core::*;
+use std::{convert::Infallible, fmt::Display};
+use ufmt::{uDisplay, uWrite, Formatter};
+
+#[derive(Field)]
+struct Config {
+    network: Network,
+}
+
+#[derive(Field)]
+struct Network {
+    port: u16,
+}
+
+struct Buf(String);
+
+impl uWrite for Buf {
+    type Error = Infallible;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Infallible> {
+        self.0.push_str(s);
+        Ok(())
+    }
+}
+
+/// Check that the `ufmt` output is the same as the `Display` output
+fn assert_same<T: uDisplay + Display>(value: T) {
+    let mut buf = Buf(String::new());
+    uDisplay::fmt(&value, &mut Formatter::new(&mut buf)).unwrap();
+    assert_eq!(buf.0, value.to_string());
+}
+
+#[test]
+fn field_path() {
+    let path = "network.port".parse::<FieldPath<Config>>().unwrap();
+    assert_same(path);
+}
+
+#[test]
+fn errors() {
+    let unknown = "network.host".parse::<FieldPath<Config>>().unwrap_err();
+    assert_same(unknown);
+
+    let out_of_bounds = FieldDescriptor::from_offset_checked::<u32, u16>(4);
+    assert_same(out_of_bounds.unwrap_err());
+    let misaligned = FieldDescriptor::from_offset_checked::<u32, u16>(1);
+    assert_same(misaligned.unwrap_err());
+
+    assert_same("host".parse::<ConfigField>().unwrap_err());
+}
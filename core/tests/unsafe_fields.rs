@@ -0,0 +1,66 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::{Disjoint, Field, NamedField, ProjectTo, chain_type};
+
+struct Foo {
+    bar: u32,
+    tap: Tap,
+}
+
+struct Tap {
+    value: u8,
+    count: u64,
+}
+
+gfp_core::unsafe_fields! {
+    Foo { bar: u32, tap: Tap }
+}
+
+gfp_core::unsafe_fields! {
+    Tap {
+        value: u8,
+        count: u64,
+    }
+}
+
+#[repr(packed)]
+struct Bytes {
+    a: u8,
+    b: [u8; 3],
+}
+
+gfp_core::unsafe_fields! { Bytes { a: u8, b: [u8; 3] } }
+
+chain_type!(FooToCount = Foo => tap.count);
+
+fn assert_disjoint<A: Disjoint<B>, B: Field<Parent = A::Parent>>(_: A, _: B) {
+}
+
+#[test]
+fn fields() {
+    let mut foo = Foo {
+        bar: 1,
+        tap: Tap {
+            value: 2,
+            count: 3,
+        },
+    };
+
+    *(&mut foo).project_to(Foo::FIELDS.bar) += 1;
+    *(&mut foo).project_to(FooToCount) += 1;
+
+    assert_eq!(foo.bar, 2);
+    assert_eq!(foo.tap.count, 4);
+    assert_eq!(
+        *(&foo).project_to(Foo::fields().tap.chain(Tap::FIELDS.value)),
+        2
+    );
+    assert_eq!(FooToCount.display_path().to_string(), "tap.count");
+    assert_disjoint(Foo::FIELDS.bar, FooToCount);
+
+    let bytes = Bytes {
+        a: 1,
+        b: [2, 3, 4],
+    };
+    assert_eq!(*(&bytes).project_to(Bytes::FIELDS.b), [2, 3, 4]);
+}
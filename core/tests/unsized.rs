@@ -0,0 +1,45 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::{Field, ProjectTo};
+
+#[cfg(feature = "alloc")]
+use std::{fmt::Debug, rc::Rc};
+
+#[derive(Field)]
+struct Packet<T: ?Sized> {
+    tag:  u8,
+    len:  u16,
+    data: T,
+}
+
+#[derive(Field)]
+struct Header<T: ?Sized>(u32, T);
+
+#[test]
+fn slice_tail() {
+    let mut packet = Packet {
+        tag:  1,
+        len:  3,
+        data: [1_u8, 2, 3],
+    };
+
+    let fields = Packet::<[u8]>::fields();
+
+    let packet: &mut Packet<[u8]> = &mut packet;
+    *packet.project_to(fields.len) += 1;
+
+    let packet: &Packet<[u8]> = packet;
+    assert_eq!(*packet.project_to(fields.tag), 1);
+    assert_eq!(*packet.project_to(fields.len), 4);
+    assert_eq!(packet.data, [1, 2, 3]);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn trait_object_tail() {
+    let header: Rc<Header<dyn Debug>> = Rc::new(Header(7, "tail"));
+
+    let field = Header::<dyn Debug>::fields().0;
+
+    assert_eq!(*header.project_to(field), 7);
+}
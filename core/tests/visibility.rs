@@ -0,0 +1,72 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::{chain_type, FieldLookup, FieldPath, NamedField, ProjectTo};
+
+mod account {
+    use gfp_core::{Field, ProjectTo};
+
+    #[derive(Field)]
+    #[field(aliases)]
+    pub struct Account {
+        pub name:           String,
+        pub(crate) limit:   u32,
+        pub(super) balance: i64,
+        secret:             u64,
+    }
+
+    impl Account {
+        pub fn new(name: &str) -> Self {
+            Account {
+                name:    name.to_string(),
+                limit:   10,
+                balance: 0,
+                secret:  42,
+            }
+        }
+
+        pub fn secret(&self) -> u64 {
+            *self.project_to(account_fields::Secret::<Account>::INIT)
+        }
+    }
+
+    // private fields can be projected to in the module of the parent
+    pub fn secret_alias(account: &Account) -> u64 {
+        *account.project_to(AccountSecret::INIT)
+    }
+}
+
+use account::{account_fields, Account};
+
+chain_type!(AccountName = Account => name);
+
+#[test]
+fn visible_fields() {
+    let mut account = Account::new("ferris");
+
+    *(&mut account).project_to(account_fields::Limit::<Account>::INIT) += 1;
+    *(&mut account).project_to(account::AccountBalance::INIT) -= 5;
+
+    assert_eq!(*(&account).project_to(AccountName), "ferris");
+    assert_eq!(AccountName.display_path().to_string(), "name");
+    assert_eq!(account.limit, 11);
+    assert_eq!(account.balance, -5);
+}
+
+#[test]
+fn private_fields() {
+    let account = Account::new("ferris");
+
+    assert_eq!(account.secret(), 42);
+    assert_eq!(account::secret_alias(&account), 42);
+}
+
+#[test]
+fn private_fields_by_name() {
+    assert_eq!(Account::field_names(), ["name"]);
+    assert!(FieldPath::<Account>::parse("name").is_ok());
+
+    for name in ["limit", "balance", "secret"] {
+        let err = FieldPath::<Account>::parse(name).unwrap_err();
+        assert_eq!(err.segment(), 0);
+    }
+}
@@ -0,0 +1,608 @@
+//! Parsing for the `#[field(...)]` attributes
+
+use syn::ext::IdentExt;
+
+/// How the generated module and field types are named
+#[derive(Clone, Copy)]
+pub enum Naming {
+    /// `Foo_fields::bar` and `Foo_fields::_0`
+    Legacy,
+    /// `foo_fields::Bar` and `foo_fields::Field0`
+    Idiomatic,
+}
+
+/// The options which are given on the parent type
+pub struct ParentOptions {
+    pub naming:         Naming,
+    pub require_repr_c: bool,
+    pub aliases:        bool,
+    pub with:           bool,
+    pub serde_names:    bool,
+    pub only:           Option<Vec<syn::Member>>,
+}
+
+impl ParentOptions {
+    pub fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut options = Self {
+            naming:         Naming::Idiomatic,
+            require_repr_c: false,
+            aliases:        false,
+            with:           false,
+            serde_names:    false,
+            only:           None,
+        };
+
+        for meta in field_metas(attrs)? {
+            match meta {
+                syn::Meta::NameValue(meta) if meta.path.is_ident("naming") => {
+                    options.naming = match lit_str(&meta.lit)?.as_str() {
+                        "legacy" => Naming::Legacy,
+                        "idiomatic" => Naming::Idiomatic,
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                meta.lit,
+                                "expected `legacy` or `idiomatic`",
+                            ))
+                        },
+                    }
+                },
+                syn::Meta::Path(path) if path.is_ident("require_repr_c") => {
+                    if !has_repr_c(attrs)? {
+                        return Err(syn::Error::new_spanned(
+                            path,
+                            "`require_repr_c` needs a `#[repr(C)]` parent",
+                        ))
+                    }
+
+                    options.require_repr_c = true;
+                },
+                syn::Meta::Path(path) if path.is_ident("aliases") => {
+                    options.aliases = true;
+                },
+                syn::Meta::Path(path) if path.is_ident("with") => {
+                    options.with = true;
+                },
+                syn::Meta::Path(path) if path.is_ident("serde_names") => {
+                    options.serde_names = true;
+                },
+                syn::Meta::List(list) if list.path.is_ident("only") => {
+                    let only = options.only.get_or_insert_with(Vec::new);
+
+                    for nested in list.nested {
+                        only.push(only_member(nested)?);
+                    }
+                },
+                meta => {
+                    return Err(syn::Error::new_spanned(meta, "unknown option"))
+                },
+            }
+        }
+
+        Ok(options)
+    }
+
+    /// Check that every field listed in `only` is a field of the parent
+    pub fn check_only(
+        &self,
+        fields: &syn::punctuated::Punctuated<syn::Field, syn::Token![,]>,
+    ) -> syn::Result<()> {
+        let only = match &self.only {
+            Some(only) => only,
+            None => return Ok(()),
+        };
+
+        let members = fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                match &field.ident {
+                    Some(ident) => syn::Member::Named(ident.clone()),
+                    None => syn::Member::Unnamed(i.into()),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        match only.iter().find(|member| !members.contains(member)) {
+            Some(member) => {
+                Err(syn::Error::new_spanned(
+                    member,
+                    "`only` lists a field which doesn't exist",
+                ))
+            },
+            None => Ok(()),
+        }
+    }
+
+    /// Check if `member` gets a field type, which is every field unless the
+    /// fields are listed with `only`
+    pub fn includes(&self, member: &syn::Member) -> bool {
+        self.only
+            .as_ref()
+            .map_or(true, |only| only.contains(member))
+    }
+}
+
+/// The options which are given on a field
+pub struct FieldOptions {
+    pub offset:  Option<syn::LitInt>,
+    pub flatten: Option<syn::Path>,
+    pub take:    Option<syn::Path>,
+    pub with:    Option<syn::Path>,
+    pub tag:     Option<syn::LitInt>,
+    pub display: Option<syn::LitStr>,
+    pub meta:    Vec<(String, String)>,
+}
+
+impl FieldOptions {
+    pub fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut options = Self {
+            offset:  None,
+            flatten: None,
+            take:    None,
+            with:    None,
+            tag:     None,
+            display: None,
+            meta:    Vec::new(),
+        };
+
+        for meta in field_metas(attrs)? {
+            match meta {
+                syn::Meta::NameValue(meta) if meta.path.is_ident("offset") => {
+                    options.offset = match meta.lit {
+                        syn::Lit::Int(offset) => Some(offset),
+                        lit => {
+                            return Err(syn::Error::new_spanned(
+                                lit,
+                                "expected an integer literal",
+                            ))
+                        },
+                    }
+                },
+                syn::Meta::NameValue(meta) if meta.path.is_ident("tag") => {
+                    options.tag = match meta.lit {
+                        syn::Lit::Int(tag) => {
+                            tag.base10_parse::<u32>()?;
+                            Some(tag)
+                        },
+                        lit => {
+                            return Err(syn::Error::new_spanned(
+                                lit,
+                                "expected an integer literal",
+                            ))
+                        },
+                    }
+                },
+                syn::Meta::NameValue(meta) if meta.path.is_ident("display") => {
+                    options.display = match meta.lit {
+                        syn::Lit::Str(display) => Some(display),
+                        lit => {
+                            return Err(syn::Error::new_spanned(
+                                lit,
+                                "expected a string literal",
+                            ))
+                        },
+                    }
+                },
+                syn::Meta::List(list) if list.path.is_ident("meta") => {
+                    for nested in list.nested {
+                        let (key, value) = meta_entry(nested)?;
+                        let name = key.unraw().to_string();
+                        let used = options
+                            .meta
+                            .iter()
+                            .any(|(other, _)| *other == name);
+
+                        if used {
+                            return Err(syn::Error::new_spanned(
+                                key,
+                                "the key is used by another entry",
+                            ))
+                        }
+
+                        options.meta.push((name, value));
+                    }
+                },
+                syn::Meta::Path(path) if path.is_ident("flatten") => {
+                    options.flatten = Some(path);
+                },
+                syn::Meta::Path(path) if path.is_ident("take") => {
+                    options.take = Some(path);
+                },
+                syn::Meta::Path(path) if path.is_ident("with") => {
+                    options.with = Some(path);
+                },
+                meta => {
+                    return Err(syn::Error::new_spanned(meta, "unknown option"))
+                },
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+/// The names of the fields as `serde` sees them, from the
+/// `#[serde(rename_all = "...")]` on the parent and the
+/// `#[serde(rename = "...")]` on the fields
+pub struct SerdeNames {
+    rename_all: Option<RenameRule>,
+}
+
+/// The case conversions of `#[serde(rename_all = "...")]`
+#[derive(Clone, Copy)]
+enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl SerdeNames {
+    pub fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut rename_all = None;
+
+        for meta in serde_metas(attrs)? {
+            if let Some(lit) = serialize_name(&meta, "rename_all")? {
+                rename_all = Some(RenameRule::parse(&lit)?);
+            }
+        }
+
+        Ok(Self {
+            rename_all,
+        })
+    }
+
+    /// The name of `field`, which has the attributes `attrs`, when it is
+    /// serialized
+    pub fn name(
+        &self,
+        attrs: &[syn::Attribute],
+        field: &syn::Ident,
+    ) -> syn::Result<String> {
+        for meta in serde_metas(attrs)? {
+            if let Some(lit) = serialize_name(&meta, "rename")? {
+                return Ok(lit.value())
+            }
+        }
+
+        let name = field.unraw().to_string();
+
+        Ok(match self.rename_all {
+            Some(rule) => rule.apply(&name),
+            None => name,
+        })
+    }
+}
+
+impl RenameRule {
+    fn parse(lit: &syn::LitStr) -> syn::Result<Self> {
+        Ok(match lit.value().as_str() {
+            "lowercase" => RenameRule::Lower,
+            "UPPERCASE" => RenameRule::Upper,
+            "PascalCase" => RenameRule::Pascal,
+            "camelCase" => RenameRule::Camel,
+            "snake_case" => RenameRule::Snake,
+            "SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnake,
+            "kebab-case" => RenameRule::Kebab,
+            "SCREAMING-KEBAB-CASE" => RenameRule::ScreamingKebab,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    lit,
+                    "unknown `rename_all` rule",
+                ))
+            },
+        })
+    }
+
+    /// Convert the `snake_case` name of a field, like `serde` does
+    fn apply(self, name: &str) -> String {
+        match self {
+            RenameRule::Lower | RenameRule::Snake => name.to_string(),
+            RenameRule::Upper | RenameRule::ScreamingSnake => {
+                name.to_ascii_uppercase()
+            },
+            RenameRule::Pascal => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+
+                for c in name.chars() {
+                    if c == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(c.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(c);
+                    }
+                }
+
+                pascal
+            },
+            RenameRule::Camel => {
+                let pascal = RenameRule::Pascal.apply(name);
+                let mut chars = pascal.chars();
+
+                match chars.next() {
+                    Some(first) => {
+                        first.to_ascii_lowercase().to_string() + chars.as_str()
+                    },
+                    None => pascal,
+                }
+            },
+            RenameRule::Kebab => name.replace('_', "-"),
+            RenameRule::ScreamingKebab => {
+                name.replace('_', "-").to_ascii_uppercase()
+            },
+        }
+    }
+}
+
+/// The name of the type alias for `field` of `parent`, i.e. `FooBar` for
+/// `Foo::bar` and `FooField0` for `Foo::0`
+pub fn alias(parent: &syn::Ident, field: &str) -> syn::Ident {
+    let name = format!("{}{}", parent.unraw(), to_camel_case(field));
+    syn::Ident::new(&name, parent.span())
+}
+
+/// The name of the variant for `field` in the field enum, i.e. `Bar` for
+/// `Foo::bar` and `Field0` for `Foo::0`
+pub fn variant(field: &str, span: proc_macro2::Span) -> syn::Ident {
+    syn::Ident::new(&to_camel_case(field), span)
+}
+
+impl Naming {
+    /// The name of the module which holds the field types
+    pub fn module(self, parent: &syn::Ident) -> syn::Ident {
+        let name = parent.unraw().to_string();
+
+        let name = match self {
+            Naming::Legacy => format!("{}_fields", name),
+            Naming::Idiomatic => format!("{}_fields", to_snake_case(&name)),
+        };
+
+        syn::Ident::new(&name, parent.span())
+    }
+
+    /// The name of the field type for a named field
+    pub fn marker(self, field: &syn::Ident) -> syn::Ident {
+        match self {
+            Naming::Legacy => field.clone(),
+            Naming::Idiomatic => {
+                let name = to_camel_case(&field.unraw().to_string());
+                syn::Ident::new(&name, field.span())
+            },
+        }
+    }
+
+    /// The name of the field type for the field at `index` of a tuple struct
+    pub fn tuple_marker(
+        self,
+        index: usize,
+        span: proc_macro2::Span,
+    ) -> syn::Ident {
+        match self {
+            Naming::Legacy => quote::format_ident!("_{}", index, span = span),
+            Naming::Idiomatic => {
+                quote::format_ident!("Field{}", index, span = span)
+            },
+        }
+    }
+}
+
+/// A field listed in `only`, by name or by index
+fn only_member(nested: syn::NestedMeta) -> syn::Result<syn::Member> {
+    match nested {
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) => {
+            match path.get_ident() {
+                Some(ident) => Ok(syn::Member::Named(ident.clone())),
+                None => {
+                    Err(syn::Error::new_spanned(path, "expected a field name"))
+                },
+            }
+        },
+        syn::NestedMeta::Lit(syn::Lit::Int(index)) => {
+            Ok(syn::Member::Unnamed(syn::Index {
+                index: index.base10_parse()?,
+                span:  index.span(),
+            }))
+        },
+        nested => {
+            Err(syn::Error::new_spanned(
+                nested,
+                "expected a field name or index",
+            ))
+        },
+    }
+}
+
+/// An entry of `meta`, either `key = value` or a `key` without a value, which
+/// has the value `""`
+fn meta_entry(nested: syn::NestedMeta) -> syn::Result<(syn::Ident, String)> {
+    let (path, value) = match nested {
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) => (path, String::new()),
+        syn::NestedMeta::Meta(syn::Meta::NameValue(meta)) => {
+            let value = match &meta.lit {
+                syn::Lit::Str(lit) => lit.value(),
+                syn::Lit::Int(lit) => lit.base10_digits().to_string(),
+                syn::Lit::Float(lit) => lit.base10_digits().to_string(),
+                syn::Lit::Bool(lit) => lit.value.to_string(),
+                lit => {
+                    return Err(syn::Error::new_spanned(
+                        lit,
+                        "expected a string, number, or bool",
+                    ))
+                },
+            };
+
+            (meta.path, value)
+        },
+        nested => {
+            return Err(syn::Error::new_spanned(
+                nested,
+                "expected `key` or `key = value`",
+            ))
+        },
+    };
+
+    match path.get_ident() {
+        Some(key) => Ok((key.clone(), value)),
+        None => Err(syn::Error::new_spanned(path, "expected a key")),
+    }
+}
+
+/// Collect the items of all `#[field(...)]` attributes
+fn field_metas(attrs: &[syn::Attribute]) -> syn::Result<Vec<syn::Meta>> {
+    let mut metas = Vec::new();
+
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("field")) {
+        match attr.parse_meta()? {
+            syn::Meta::List(list) => {
+                for nested in list.nested {
+                    match nested {
+                        syn::NestedMeta::Meta(meta) => metas.push(meta),
+                        syn::NestedMeta::Lit(lit) => {
+                            return Err(syn::Error::new_spanned(
+                                lit,
+                                "expected an option",
+                            ))
+                        },
+                    }
+                }
+            },
+            meta => {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    "expected `#[field(...)]`",
+                ))
+            },
+        }
+    }
+
+    Ok(metas)
+}
+
+/// Collect the items of all `#[serde(...)]` attributes
+fn serde_metas(attrs: &[syn::Attribute]) -> syn::Result<Vec<syn::Meta>> {
+    let mut metas = Vec::new();
+
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("serde")) {
+        if let syn::Meta::List(list) = attr.parse_meta()? {
+            metas.extend(list.nested.into_iter().filter_map(|nested| {
+                match nested {
+                    syn::NestedMeta::Meta(meta) => Some(meta),
+                    syn::NestedMeta::Lit(_) => None,
+                }
+            }));
+        }
+    }
+
+    Ok(metas)
+}
+
+/// The serialized name in a `serde` option named `option`, which is either
+/// `option = "..."` or `option(serialize = "...", deserialize = "...")`
+fn serialize_name(
+    meta: &syn::Meta,
+    option: &str,
+) -> syn::Result<Option<syn::LitStr>> {
+    let lit = match meta {
+        syn::Meta::NameValue(meta) if meta.path.is_ident(option) => &meta.lit,
+        syn::Meta::List(list) if list.path.is_ident(option) => {
+            let serialize = list.nested.iter().find_map(|nested| {
+                match nested {
+                    syn::NestedMeta::Meta(syn::Meta::NameValue(meta))
+                        if meta.path.is_ident("serialize") =>
+                    {
+                        Some(&meta.lit)
+                    },
+                    _ => None,
+                }
+            });
+
+            match serialize {
+                Some(lit) => lit,
+                // only the deserialized name is changed
+                None => return Ok(None),
+            }
+        },
+        _ => return Ok(None),
+    };
+
+    match lit {
+        syn::Lit::Str(lit) => Ok(Some(lit.clone())),
+        _ => Err(syn::Error::new_spanned(lit, "expected a string literal")),
+    }
+}
+
+/// Checks for `C` in any of the `#[repr(...)]` attributes
+fn has_repr_c(attrs: &[syn::Attribute]) -> syn::Result<bool> {
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("repr")) {
+        if let syn::Meta::List(list) = attr.parse_meta()? {
+            let is_c = list.nested.iter().any(|nested| {
+                matches!(
+                    nested,
+                    syn::NestedMeta::Meta(syn::Meta::Path(path))
+                        if path.is_ident("C")
+                )
+            });
+
+            if is_c {
+                return Ok(true)
+            }
+        }
+    }
+
+    Ok(false)
+}
+
+fn lit_str(lit: &syn::Lit) -> syn::Result<String> {
+    match lit {
+        syn::Lit::Str(lit) => Ok(lit.value()),
+        _ => Err(syn::Error::new_spanned(lit, "expected a string literal")),
+    }
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    let mut prev_lower = false;
+
+    for c in name.chars() {
+        if c.is_uppercase() {
+            if prev_lower {
+                snake.push('_');
+            }
+
+            snake.extend(c.to_lowercase());
+            prev_lower = false;
+        } else {
+            snake.push(c);
+            prev_lower = c.is_lowercase() || c.is_numeric();
+        }
+    }
+
+    snake
+}
+
+fn to_camel_case(name: &str) -> String {
+    let mut camel = String::new();
+
+    for word in name.split('_') {
+        let mut chars = word.chars();
+
+        if let Some(first) = chars.next() {
+            camel.extend(first.to_uppercase());
+            camel.extend(chars);
+        }
+    }
+
+    // field names like `_0` don't have a leading letter
+    if camel.chars().next().map_or(true, |c| !c.is_alphabetic()) {
+        camel.insert_str(0, "Field");
+    }
+
+    camel
+}
@@ -1,16 +1,21 @@
 extern crate proc_macro;
 
+mod attr;
+
 use proc_macro_roids::*;
 
 use proc_macro::TokenStream;
 use quote::quote;
+use syn::ext::IdentExt;
 
 /// This macro generates a number of field types and automatically derives
 /// `gfp_core::Field` for them. It will also generate a type to make accessing
 /// these field types easier.
 ///
-/// The field types will be generated in a module named `{$type}_fields` and the
-/// type that holds all of the field types will be called `{$type}::Fields`.
+/// The field types will be generated in a module named
+/// `{$type_in_snake_case}_fields`, and named after their field in
+/// `CamelCase`, e.g. `person_fields::Name` or `pair_fields::Field0`, and the
+/// type that holds all of the field types will be called `{$type}Fields`.
 ///
 /// For `unions`, getting the field types is `unsafe` because you can cause
 /// aliasing of unique references and because accessing union fields is
@@ -20,9 +25,128 @@ use quote::quote;
 /// defined use raw pointers to initialized fields so UB is not possible.
 ///
 ///  * note: unit structs don't generate any extra code (i.e. `struct Foo;`)
+///  * note: if the last field of a struct is unsized (a slice, `str`, a trait
+///    object, or a `?Sized` type parameter), no field type is generated for it
+///    and the other field types don't get a `DESCRIPTOR`
 ///
 /// `enums` are not supported.
 ///
+/// The field types can be given their old names with
+/// `#[field(naming = "legacy")]` on the parent, then they are generated in a
+/// module named `{$type}_fields`, and named like their field, e.g.
+/// `Person_fields::name` or `Pair_fields::_0`. The default is
+/// `#[field(naming = "idiomatic")]`. Two fields which would get the same
+/// idiomatic name, like `fooBar` and `foo_bar`, are rejected, because the
+/// enum of the fields below uses those names too.
+///
+/// ```compile_fail
+/// # #![feature(raw_ref_op)]
+/// # mod test {
+/// # use gfp_core::Field;
+/// #[derive(Field)]
+/// #[allow(non_snake_case)]
+/// struct Point {
+///     fooBar:  u8,
+///     foo_bar: u8,
+/// }
+/// # }
+/// ```
+///
+/// There is also an enum with a variant for every field, named like the
+/// idiomatic field types, i.e. `PersonField::Name` or `PairField::Field0`.
+/// It can be parsed from the name of a field with `FromStr`, and
+/// `Person::field_of(PersonField::Name)` turns it into an `AnyField<Person>`.
+/// `Person::FIELD_COUNT` and `Person::field_at(index)` give positional access
+/// to the same fields, in declaration order. Structs also implement
+/// `FieldLookup` and `CompleteFieldLookup`, so dot-separated paths can be
+/// resolved at run-time with `"children.name".parse::<FieldPath<Person>>()`,
+/// or through a `Registry` of types, which also lists their `field_names`.
+/// Non-generic parents implement `HasStructInfo`, which describes their
+/// layout for `gfp_core::ffi`. The `FieldInfo` of a field whose type is known
+/// to implement `Clone` can clone it, which is what `gfp_core::migrate_fields`
+/// uses to carry data between versions of a struct.
+///
+/// Structs also get `Person::ALL_FIELDS`, the list of all of their field
+/// types in declaration order, like the lists for `ProjectAll`. So
+/// `person.project_all(Person::ALL_FIELDS)` projects to every field at once,
+/// and helpers which work on every field, like `clone_fields_into` or
+/// `SerializeFields`, don't need the fields to be listed by hand.
+///
+/// Every field type implements `NamedField`, with the name of its field, or
+/// its index for tuple structs. Chains of them display as dot-joined paths,
+/// i.e. `Person::FIELDS.name.display_path()` displays as `name`. Struct
+/// fields can also be found by name at compile time, which is what
+/// `gfp_core::chain_type!` uses to name the types of chains of fields. Every
+/// pair of distinct struct fields implements `gfp_core::Disjoint`, and so
+/// does every pair of paths through them, but union fields don't.
+///
+/// Struct fields marked with `#[field(take)]` get `take_{field}` and
+/// `clear_{field}` methods, which replace the field with its default value.
+/// Likewise `#[field(with)]` on a field, or on the parent for all fields,
+/// generates `with_{field}` methods, which replace the field and return the
+/// updated parent. These take the parent by value, so they are only available
+/// for sized parents.
+///
+/// Fields of non-generic parents can be annotated with their expected offset
+/// in bytes, i.e. `#[field(offset = 4)]`, then compilation fails if the field
+/// is placed anywhere else. This guards `#[repr(C)]` structs which are shared
+/// over FFI against layout drift.
+///
+/// Struct fields can be given a stable number with `#[field(tag = 3)]`, like
+/// the field numbers of protobuf. The tags are part of the `FieldInfo` of the
+/// fields, and `Person::field_by_tag(3)` looks a field up by its tag, so
+/// encoders and decoders can be written generically over derived structs.
+/// Each tag can only be used once per parent.
+///
+/// Fields can be given a human-readable label with
+/// `#[field(display = "Retry count")]`, for UIs which are built from the
+/// metadata of the fields. The label is part of the `FieldInfo` of the field,
+/// and of the `StructInfo` of the parent, both of which fall back to the name
+/// of the field if it doesn't have a label.
+///
+/// Fields can also carry arbitrary metadata for other frameworks, i.e.
+/// `#[field(meta(unit = "ms", sensitive))]`, which is part of the `FieldInfo`
+/// of the field, so `Person::lookup_field("age").unwrap().meta("unit")` finds
+/// the value of `unit`. The values can be strings, numbers, or bools, which
+/// are stored as strings, and a key without a value, like `sensitive`, has
+/// the value `""`.
+///
+/// `#[field(serde_names)]` on the parent of named fields makes `NamedField`
+/// and `FieldLookup` use the names that `serde` gives the fields, from
+/// `#[serde(rename = "...")]` on a field or `#[serde(rename_all = "...")]` on
+/// the parent, so paths are the same as the keys of the serialized parent.
+/// If the serialized and deserialized names differ, the serialized one is
+/// used. The field enum and `chain_type!` still use the Rust names.
+///
+/// `#[field(only(bar, val))]` on the parent only generates field types for
+/// the listed fields, or `#[field(only(0, 2))]` for tuple structs, which keeps
+/// the generated code small for large structs where only a few fields are
+/// projected to. The other fields are left out of everything that the derive
+/// generates, so the parent doesn't implement `CompleteFieldLookup`, and in
+/// `FIELDS` of tuple structs they are `()`, so the listed fields keep their
+/// index.
+///
+/// `#[field(require_repr_c)]` on the parent refuses to compile unless the
+/// parent is `#[repr(C)]`. The offsets of a `#[repr(C)]` type only depend on
+/// the order, sizes, and alignments of its fields, so they are stable across
+/// compilations and can be stored in persistent formats. For non-generic
+/// parents this is also asserted at compile time.
+///
+/// `#[field(aliases)]` on the parent generates a type alias for every field
+/// type next to the parent, i.e. `PersonName` for `person_fields::Name<Person>`
+/// and `PairField0` for `pair_fields::Field0<Pair>`. Fields marked with
+/// `#[field(flatten)]` also get an alias for chaining into their own fields,
+/// i.e. `type PersonNameThen<Then> = Chain<PersonName, Then>`.
+///
+/// Field types are only visible where their fields are, i.e. the type of a
+/// private field can only be named in the module of the parent, and the type
+/// of a `pub(crate)` field only in its crate. So code which couldn't access a
+/// field can't name its type to project to it either. `chain_type!` can only
+/// name fields which are as visible as their parent, and the other fields are
+/// left out of `FieldLookup`, so they can't be reached by name through a
+/// `FieldPath` or a `Registry` either. A parent with such fields doesn't
+/// implement `CompleteFieldLookup`.
+///
 /// For example for a struct,
 /// ```
 /// # #![feature(raw_ref_op)]
@@ -37,7 +161,15 @@ use quote::quote;
 /// # }
 /// ```
 /// will generate (excluding comments) the following. Note the use of `&raw `,
-/// this is to allow safe projection through raw pointers.
+/// this is to allow safe projection through raw pointers. The inherent
+/// `DESCRIPTOR`, `project_raw`, and `project_raw_mut` items are `const`, so
+/// they can be used to build static tables of offsets and pointers. `fields`
+/// is a `const fn` as well, even for generic parents, so chains of fields can
+/// be stored in constants. Each `DESCRIPTOR` also asserts that it agrees with
+/// the address of its field that `addr_of!` gives, which is checked at compile
+/// time whenever a field of that exact parent is projected to, so the
+/// descriptors can't drift from the layout that the compiler picked for a
+/// generic parent or a target. These assertions are left out below.
 /// ```
 /// # #![feature(raw_ref_op)]
 /// # mod test {
@@ -48,15 +180,36 @@ use quote::quote;
 /// }
 /// // This struct holds all of the field types for easy access
 /// struct PersonFields {
-///     name: Person_fields::name<Person>,
-///     age: Person_fields::age<Person>,
-///     children: Person_fields::children<Person>,
+///     name: person_fields::Name<Person>,
+///     age: person_fields::Age<Person>,
+///     children: person_fields::Children<Person>,
 /// }
 /// impl Person {
 ///     const FIELDS: PersonFields = PersonFields {
-///         name: Person_fields::name::INIT,
-///         age: Person_fields::age::INIT,
-///         children: Person_fields::children::INIT,
+///         name: person_fields::Name::INIT,
+///         age: person_fields::Age::INIT,
+///         children: person_fields::Children::INIT,
+///     };
+///
+///     // every field, in declaration order, as a list for `ProjectAll`
+///     const ALL_FIELDS: ::gfp_core::derive::Cons<
+///         person_fields::Name<Person>,
+///         ::gfp_core::derive::Cons<
+///             person_fields::Age<Person>,
+///             ::gfp_core::derive::Cons<
+///                 person_fields::Children<Person>,
+///                 ::gfp_core::derive::Nil,
+///             >,
+///         >,
+///     > = ::gfp_core::derive::Cons {
+///         value: person_fields::Name::INIT,
+///         rest: ::gfp_core::derive::Cons {
+///             value: person_fields::Age::INIT,
+///             rest: ::gfp_core::derive::Cons {
+///                 value: person_fields::Children::INIT,
+///                 rest: ::gfp_core::derive::Nil,
+///             },
+///         },
 ///     };
 ///
 ///     // get an instance of `PersonFields` easily by calling
@@ -69,93 +222,173 @@ use quote::quote;
 ///     // person.project_to(fields.age);
 ///     // person.project_to(fields.children);
 ///     // ```
-///     fn fields() -> PersonFields {
+///     const fn fields() -> PersonFields {
 ///         PersonFields {
-///             name: Person_fields::name::INIT,
-///             age: Person_fields::age::INIT,
-///             children: Person_fields::children::INIT,
+///             name: person_fields::Name::INIT,
+///             age: person_fields::Age::INIT,
+///             children: person_fields::Children::INIT,
 ///         }
 ///     }
 /// }
 /// #[allow(non_snake_case)]
-/// mod Person_fields {
-///     use super::*;
+/// mod person_fields {
 ///     // represents the `name` field of `Person`
 ///     #[allow(non_camel_case_types)]
-///     pub struct name<T>(::gfp_core::derive::Invariant<T>);
-///     impl<T> name<T> {
+///     pub(super) struct Name<__T: ?::core::marker::Sized>(
+///         ::gfp_core::derive::Invariant<__T>,
+///     );
+///     impl<__T: ?::core::marker::Sized> Name<__T> {
 ///         pub const INIT: Self = Self(::gfp_core::derive::Invariant::INIT);
 ///     }
-///     impl<T> Clone for name<T> {
+///     impl<__T: ?::core::marker::Sized> ::core::clone::Clone for Name<__T> {
 ///         fn clone(&self) -> Self {
 ///             *self
 ///         }
 ///     }
-///     impl<T> Copy for name<T> {}
-///     unsafe impl ::gfp_core::Field for name<super::Person> {
-///         type Parent = super::Person;
-///         type Type = String;
-///         #[inline]
-///         unsafe fn project_raw(&self, ptr: *const Self::Parent) -> *const Self::Type {
-///             &raw const (*ptr).name
-///         }
-///         #[inline]
-///         unsafe fn project_raw_mut(&self, ptr: *mut Self::Parent) -> *mut Self::Type {
-///             &raw mut (*ptr).name
-///         }
-///     }
+///     impl<__T: ?::core::marker::Sized> ::core::marker::Copy for Name<__T> {}
 ///     // represents the `age` field of `Person`
 ///     #[allow(non_camel_case_types)]
-///     pub struct age<T>(::gfp_core::derive::Invariant<T>);
-///     impl<T> age<T> {
+///     pub(super) struct Age<__T: ?::core::marker::Sized>(
+///         ::gfp_core::derive::Invariant<__T>,
+///     );
+///     impl<__T: ?::core::marker::Sized> Age<__T> {
 ///         pub const INIT: Self = Self(::gfp_core::derive::Invariant::INIT);
 ///     }
-///     impl<T> Clone for age<T> {
+///     impl<__T: ?::core::marker::Sized> ::core::clone::Clone for Age<__T> {
 ///         fn clone(&self) -> Self {
 ///             *self
 ///         }
 ///     }
-///     impl<T> Copy for age<T> {}
-///     unsafe impl ::gfp_core::Field for age<super::Person> {
-///         type Parent = super::Person;
-///         type Type = u16;
-///         #[inline]
-///         unsafe fn project_raw(&self, ptr: *const Self::Parent) -> *const Self::Type {
-///             &raw const (*ptr).age
-///         }
-///         #[inline]
-///         unsafe fn project_raw_mut(&self, ptr: *mut Self::Parent) -> *mut Self::Type {
-///             &raw mut (*ptr).age
-///         }
-///     }
+///     impl<__T: ?::core::marker::Sized> ::core::marker::Copy for Age<__T> {}
 ///     // represents the `children` field of `Person`
 ///     #[allow(non_camel_case_types)]
-///     pub struct children<T>(::gfp_core::derive::Invariant<T>);
-///     impl<T> children<T> {
+///     pub(super) struct Children<__T: ?::core::marker::Sized>(
+///         ::gfp_core::derive::Invariant<__T>,
+///     );
+///     impl<__T: ?::core::marker::Sized> Children<__T> {
 ///         pub const INIT: Self = Self(::gfp_core::derive::Invariant::INIT);
 ///     }
-///     impl<T> Clone for children<T> {
+///     impl<__T: ?::core::marker::Sized> ::core::clone::Clone for Children<__T> {
 ///         fn clone(&self) -> Self {
 ///             *self
 ///         }
 ///     }
-///     impl<T> Copy for children<T> {}
-///     unsafe impl ::gfp_core::Field for children<super::Person> {
-///         type Parent = super::Person;
-///         type Type = Vec<Person>;
-///         #[inline]
-///         unsafe fn project_raw(&self, ptr: *const Self::Parent) -> *const Self::Type {
-///             &raw const (*ptr).children
-///         }
-///         #[inline]
-///         unsafe fn project_raw_mut(&self, ptr: *mut Self::Parent) -> *mut Self::Type {
-///             &raw mut (*ptr).children
-///         }
+///     impl<__T: ?::core::marker::Sized> ::core::marker::Copy for Children<__T> {}
+/// }
+/// unsafe impl ::gfp_core::Field for person_fields::Name<Person> {
+///     type Parent = Person;
+///     type Type = String;
+///     #[inline]
+///     unsafe fn project_raw(&self, ptr: *const Self::Parent) -> *const Self::Type {
+///         &raw const (*ptr).name
+///     }
+///     #[inline]
+///     unsafe fn project_raw_mut(&self, ptr: *mut Self::Parent) -> *mut Self::Type {
+///         &raw mut (*ptr).name
+///     }
+/// }
+/// impl ::gfp_core::NamedField for person_fields::Name<Person> {
+///     type Name = ::gfp_core::derive::Once<&'static str>;
+///     #[inline]
+///     fn name(&self) -> Self::Name {
+///         ::gfp_core::derive::once("name")
+///     }
+/// }
+/// impl ::gfp_core::derive::FieldByName<{ ::gfp_core::derive::name_hash("name") }> for Person {
+///     type Field = person_fields::Name<Person>;
+///     const FIELD: Self::Field = person_fields::Name::INIT;
+/// }
+/// impl person_fields::Name<Person> {
+///     pub const DESCRIPTOR: ::gfp_core::FieldDescriptor =
+///         ::gfp_core::FieldDescriptor::new::<Person, String>(
+///             ::gfp_core::derive::offset_of!(Person, name)
+///         );
+///     #[inline]
+///     pub const unsafe fn project_raw(&self, ptr: *const Person) -> *const String {
+///         &raw const (*ptr).name
+///     }
+///     #[inline]
+///     pub const unsafe fn project_raw_mut(&self, ptr: *mut Person) -> *mut String {
+///         &raw mut (*ptr).name
+///     }
+/// }
+/// unsafe impl ::gfp_core::Field for person_fields::Age<Person> {
+///     type Parent = Person;
+///     type Type = u16;
+///     #[inline]
+///     unsafe fn project_raw(&self, ptr: *const Self::Parent) -> *const Self::Type {
+///         &raw const (*ptr).age
+///     }
+///     #[inline]
+///     unsafe fn project_raw_mut(&self, ptr: *mut Self::Parent) -> *mut Self::Type {
+///         &raw mut (*ptr).age
+///     }
+/// }
+/// impl ::gfp_core::NamedField for person_fields::Age<Person> {
+///     type Name = ::gfp_core::derive::Once<&'static str>;
+///     #[inline]
+///     fn name(&self) -> Self::Name {
+///         ::gfp_core::derive::once("age")
+///     }
+/// }
+/// impl ::gfp_core::derive::FieldByName<{ ::gfp_core::derive::name_hash("age") }> for Person {
+///     type Field = person_fields::Age<Person>;
+///     const FIELD: Self::Field = person_fields::Age::INIT;
+/// }
+/// impl person_fields::Age<Person> {
+///     pub const DESCRIPTOR: ::gfp_core::FieldDescriptor =
+///         ::gfp_core::FieldDescriptor::new::<Person, u16>(
+///             ::gfp_core::derive::offset_of!(Person, age)
+///         );
+///     #[inline]
+///     pub const unsafe fn project_raw(&self, ptr: *const Person) -> *const u16 {
+///         &raw const (*ptr).age
+///     }
+///     #[inline]
+///     pub const unsafe fn project_raw_mut(&self, ptr: *mut Person) -> *mut u16 {
+///         &raw mut (*ptr).age
+///     }
+/// }
+/// unsafe impl ::gfp_core::Field for person_fields::Children<Person> {
+///     type Parent = Person;
+///     type Type = Vec<Person>;
+///     #[inline]
+///     unsafe fn project_raw(&self, ptr: *const Self::Parent) -> *const Self::Type {
+///         &raw const (*ptr).children
+///     }
+///     #[inline]
+///     unsafe fn project_raw_mut(&self, ptr: *mut Self::Parent) -> *mut Self::Type {
+///         &raw mut (*ptr).children
+///     }
+/// }
+/// impl ::gfp_core::NamedField for person_fields::Children<Person> {
+///     type Name = ::gfp_core::derive::Once<&'static str>;
+///     #[inline]
+///     fn name(&self) -> Self::Name {
+///         ::gfp_core::derive::once("children")
+///     }
+/// }
+/// impl ::gfp_core::derive::FieldByName<{ ::gfp_core::derive::name_hash("children") }> for Person {
+///     type Field = person_fields::Children<Person>;
+///     const FIELD: Self::Field = person_fields::Children::INIT;
+/// }
+/// impl person_fields::Children<Person> {
+///     pub const DESCRIPTOR: ::gfp_core::FieldDescriptor =
+///         ::gfp_core::FieldDescriptor::new::<Person, Vec<Person>>(
+///             ::gfp_core::derive::offset_of!(Person, children)
+///         );
+///     #[inline]
+///     pub const unsafe fn project_raw(&self, ptr: *const Person) -> *const Vec<Person> {
+///         &raw const (*ptr).children
+///     }
+///     #[inline]
+///     pub const unsafe fn project_raw_mut(&self, ptr: *mut Person) -> *mut Vec<Person> {
+///         &raw mut (*ptr).children
 ///     }
 /// }
 /// # }
 /// ```
-#[proc_macro_derive(Field)]
+#[proc_macro_derive(Field, attributes(field))]
 pub fn derive_field(ty: TokenStream) -> TokenStream {
     let ty = syn::parse_macro_input!(ty as syn::DeriveInput);
 
@@ -210,6 +443,7 @@ fn derive_named(ty: syn::DeriveInput) -> TokenStream {
     let syn::DeriveInput {
         vis,
         ident: input_ident,
+        attrs,
         generics,
         data,
         ..
@@ -225,10 +459,28 @@ fn derive_named(ty: syn::DeriveInput) -> TokenStream {
         unreachable!()
     };
 
-    let module_name = input_ident.append("_fields");
+    let options = match attr::ParentOptions::parse(&attrs) {
+        Ok(options) => options,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let serde_names = if options.serde_names {
+        match attr::SerdeNames::parse(&attrs) {
+            Ok(serde_names) => Some(serde_names),
+            Err(err) => return err.to_compile_error().into(),
+        }
+    } else {
+        None
+    };
+
+    if let Err(err) = options.check_only(&fields.named) {
+        return err.to_compile_error().into()
+    }
+
+    let module_name = options.naming.module(&input_ident);
 
     let mut module = new_module(module_name.clone());
-    module.vis = vis;
+    module.vis = vis.clone();
 
     let contents = &mut module.content.as_mut().unwrap().1;
 
@@ -237,61 +489,264 @@ fn derive_named(ty: syn::DeriveInput) -> TokenStream {
     let mut fields_new =
         syn::punctuated::Punctuated::<_, syn::Token![,]>::new();
 
-    contents.push(item!(
-        use super::*;
-    ));
+    let mut impls = Vec::new();
+    let mut layout = Vec::new();
+    let mut names = Vec::new();
+    let mut lookup_names = Vec::new();
+    let mut idiomatic_names = Vec::new();
+    let mut visible = Vec::new();
+    let mut tags = Vec::new();
+    let mut field_attrs = Vec::new();
+    let mut markers = Vec::new();
+
+    // the unsized tail of a parent can't be a `Field::Type`, so it is skipped
+    let unsized_parent = fields
+        .named
+        .last()
+        .map_or(false, |field| is_unsized(&field.ty, &generics));
+    let sized_fields = fields.named.len() - unsized_parent as usize;
+
+    // the `#[repr(C)]` offsets depend on the fields which aren't in `only` too
+    let repr_c_layout = fields
+        .named
+        .iter()
+        .take(sized_fields)
+        .map(|field| {
+            let ident = field.ident.clone().unwrap();
+            (syn::Member::Named(ident), field.ty.clone())
+        })
+        .collect::<Vec<_>>();
 
     let (generic_header, generic, where_clause) = generics.split_for_impl();
-    for field in fields.named {
+    for field in fields.named.into_iter().take(sized_fields) {
         let ident = field.ident.unwrap();
 
+        if !options.includes(&syn::Member::Named(ident.clone())) {
+            continue
+        }
+
+        if let Err(err) = push_idiomatic_name(&mut idiomatic_names, &ident) {
+            return err.to_compile_error().into()
+        }
+
+        let marker = options.naming.marker(&ident);
+
+        let field_options = match attr::FieldOptions::parse(&field.attrs) {
+            Ok(field_options) => field_options,
+            Err(err) => return err.to_compile_error().into(),
+        };
+
+        if let Err(err) = push_tag(&mut tags, &field_options, unsized_parent) {
+            return err.to_compile_error().into()
+        }
+
+        // the name in `NamedField` and `FieldLookup`, which may be renamed
+        let name = match &serde_names {
+            Some(serde_names) => {
+                match serde_names.name(&field.attrs, &ident) {
+                    Ok(name) => name,
+                    Err(err) => return err.to_compile_error().into(),
+                }
+            },
+            None => ident.unraw().to_string(),
+        };
+
+        if lookup_names.iter().any(|(other, _)| *other == name) {
+            return syn::Error::new_spanned(
+                &ident,
+                format!("the name `{}` is used by another field", name),
+            )
+            .to_compile_error()
+            .into()
+        }
+
+        lookup_names.push((name.clone(), syn::Member::Named(ident.clone())));
+        visible.push(is_as_visible(&field.vis, &vis));
+
+        if let Some(offset) = &field_options.offset {
+            let member = syn::Member::Named(ident.clone());
+            match offset_guard(
+                &input_ident,
+                &generics,
+                unsized_parent,
+                &member,
+                offset,
+            ) {
+                Ok(guard) => impls.push(guard),
+                Err(err) => return err.to_compile_error().into(),
+            }
+        }
+
+        let marker_vis = marker_vis(&field.vis);
+
         contents.push(item!(
             #[allow(non_camel_case_types)]
-            pub struct #ident<T>(::gfp_core::derive::Invariant<T>);
+            #marker_vis struct #marker<__T: ?::core::marker::Sized>(::gfp_core::derive::Invariant<__T>);
         ));
 
         contents.push(item!(
-            impl<T> #ident<T> {
+            impl<__T: ?::core::marker::Sized> #marker<__T> {
                 pub const INIT: Self = Self(::gfp_core::derive::Invariant::INIT);
             }
         ));
 
         contents.push(item!(
-            impl<T> Clone for #ident<T> {
+            impl<__T: ?::core::marker::Sized> ::core::clone::Clone for #marker<__T> {
                 fn clone(&self) -> Self { *self }
             }
         ));
 
         contents.push(item!(
-            impl<T> Copy for #ident<T> {}
+            impl<__T: ?::core::marker::Sized> ::core::marker::Copy for #marker<__T> {}
         ));
 
+        markers.push(marker.clone());
+
         let ty = &field.ty;
+        layout.push((syn::Member::Named(ident.clone()), ty.clone()));
+        names.push((
+            ident.unraw().to_string(),
+            syn::Member::Named(ident.clone()),
+        ));
 
-        contents.push(item!(
-            unsafe impl #generic_header ::gfp_core::Field for #ident<super::#input_ident #generic> {
-                type Parent = super::#input_ident #generic;
+        if field_options.take.is_some() {
+            impls.push(take_methods(
+                &input_ident,
+                &generics,
+                &field.vis,
+                &syn::Member::Named(ident.clone()),
+                ty,
+            ));
+        }
+
+        if options.with || field_options.with.is_some() {
+            if unsized_parent {
+                return syn::Error::new_spanned(
+                    &input_ident,
+                    "`with` needs a sized parent",
+                )
+                .to_compile_error()
+                .into()
+            }
+
+            impls.push(with_method(
+                &input_ident,
+                &generics,
+                &field.vis,
+                &syn::Member::Named(ident.clone()),
+                ty,
+            ));
+        }
+
+        if options.aliases {
+            let alias = attr::alias(&input_ident, &ident.unraw().to_string());
+            let params = alias_params(&generics);
+            let field_vis = &field.vis;
+
+            impls.push(item!(
+                #field_vis type #alias <#params> = #module_name::#marker<#input_ident #generic>;
+            ));
+
+            if field_options.flatten.is_some() {
+                let then = alias.append("Then");
+
+                impls.push(item!(
+                    #field_vis type #then <#params Then> = ::gfp_core::Chain<#alias <#params>, Then>;
+                ));
+            }
+        } else if let Some(flatten) = &field_options.flatten {
+            return syn::Error::new_spanned(
+                flatten,
+                "`flatten` requires `#[field(aliases)]` on the parent",
+            )
+            .to_compile_error()
+            .into()
+        }
+
+        let layout_check = layout_check(unsized_parent);
+
+        impls.push(item!(
+            unsafe impl #generic_header ::gfp_core::Field for #module_name::#marker<#input_ident #generic> {
+                type Parent = #input_ident #generic;
                 type Type = #ty;
 
                 #[inline]
                 unsafe fn project_raw(&self, ptr: *const Self::Parent) -> *const Self::Type {
+                    #layout_check
                     ::gfp_core::ptr_project!(const ptr #ident)
                 }
 
                 #[inline]
                 unsafe fn project_raw_mut(&self, ptr: *mut Self::Parent) -> *mut Self::Type {
+                    #layout_check
+                    ::gfp_core::ptr_project!(mut ptr #ident)
+                }
+            }
+        ));
+
+        impls.push(item!(
+            impl #generic_header ::gfp_core::AutoUnpin for #module_name::#marker<#input_ident #generic> #where_clause {}
+        ));
+
+        impls.push(item!(
+            impl #generic_header ::gfp_core::NamedField for #module_name::#marker<#input_ident #generic> #where_clause {
+                type Name = ::gfp_core::derive::Once<&'static str>;
+
+                #[inline]
+                fn name(&self) -> Self::Name {
+                    ::gfp_core::derive::once(#name)
+                }
+            }
+        ));
+
+        // `chain_type!` finds fields by the names they have in Rust, but the
+        // type of a field less visible than the parent can't be exposed
+        let rust_name = ident.unraw().to_string();
+        if is_as_visible(&field.vis, &vis) {
+            impls.push(item!(
+                impl #generic_header ::gfp_core::derive::FieldByName<{ ::gfp_core::derive::name_hash(#rust_name) }> for #input_ident #generic #where_clause {
+                    type Field = #module_name::#marker<#input_ident #generic>;
+
+                    const FIELD: Self::Field = #module_name::#marker::INIT;
+                }
+            ));
+        }
+
+        // unsized parents don't have a statically known layout
+        let descriptor = if unsized_parent {
+            quote!()
+        } else {
+            checked_descriptor(
+                &input_ident,
+                &generics,
+                &syn::Member::Named(ident.clone()),
+                ty,
+            )
+        };
+
+        impls.push(item!(
+            impl #generic_header #module_name::#marker<#input_ident #generic> #where_clause {
+                #descriptor
+
+                #[inline]
+                pub const unsafe fn project_raw(&self, ptr: *const #input_ident #generic) -> *const #ty {
+                    ::gfp_core::ptr_project!(const ptr #ident)
+                }
+
+                #[inline]
+                pub const unsafe fn project_raw_mut(&self, ptr: *mut #input_ident #generic) -> *mut #ty {
                     ::gfp_core::ptr_project!(mut ptr #ident)
                 }
             }
         ));
 
         let ty = TokenStream::from(quote!(
-            #module_name::#ident<#input_ident #generic>
+            #module_name::#marker<#input_ident #generic>
         ));
         let ty = syn::parse_macro_input!(ty as syn::Type);
 
         fields_new.push(expr!(
-            #ident: #module_name::#ident::INIT
+            #ident: #module_name::#marker::INIT
         ));
 
         let item = syn::Field {
@@ -303,9 +758,58 @@ fn derive_named(ty: syn::DeriveInput) -> TokenStream {
         };
 
         fields_marker.push(item);
+        field_attrs.push(field_options);
     }
 
+    impls.extend(disjoint_impls(
+        &input_ident,
+        &generics,
+        &module_name,
+        &markers,
+    ));
+
+    if !unsized_parent {
+        impls.extend(projection_guard(
+            &input_ident,
+            &generics,
+            &module_name,
+            &markers,
+            &layout,
+        ));
+    }
+
+    if options.require_repr_c && !unsized_parent {
+        impls.extend(repr_c_guard(
+            &input_ident,
+            &generics,
+            &repr_c_layout,
+            false,
+        ));
+    }
+
+    let enum_items = if unsized_parent {
+        quote!()
+    } else {
+        let field_enum = field_enum(&input_ident, &generics, &names, false);
+        let lookup = field_lookup(
+            &input_ident,
+            &generics,
+            &lookup_names,
+            &layout,
+            &tags,
+            &field_attrs,
+            &visible,
+            options.only.is_none() && visible.iter().all(|&visible| visible),
+        );
+        let info =
+            struct_info(&input_ident, &generics, &names, &layout, &field_attrs);
+
+        quote!(#field_enum #lookup #info)
+    };
+
     let field_type_name = input_ident.append("Fields");
+    let all_fields =
+        all_fields(&input_ident, &generics, &module_name, &markers);
 
     TokenStream::from(quote! {
         struct #field_type_name #generic_header #where_clause {
@@ -317,7 +821,9 @@ fn derive_named(ty: syn::DeriveInput) -> TokenStream {
                 #fields_new
             };
 
-            fn fields() -> #field_type_name #generic {
+            #all_fields
+
+            const fn fields() -> #field_type_name #generic {
                 #field_type_name {
                     #fields_new
                 }
@@ -326,6 +832,10 @@ fn derive_named(ty: syn::DeriveInput) -> TokenStream {
 
         #[allow(non_snake_case)]
         #module
+
+        #(#impls)*
+
+        #enum_items
     })
 }
 
@@ -333,6 +843,7 @@ fn derive_unnamed(ty: syn::DeriveInput) -> TokenStream {
     let syn::DeriveInput {
         vis,
         ident: input_ident,
+        attrs,
         generics,
         data,
         ..
@@ -348,10 +859,28 @@ fn derive_unnamed(ty: syn::DeriveInput) -> TokenStream {
         unreachable!()
     };
 
-    let module_name = input_ident.append("_fields");
+    let options = match attr::ParentOptions::parse(&attrs) {
+        Ok(options) => options,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    if options.serde_names {
+        return syn::Error::new_spanned(
+            input_ident,
+            "`serde_names` needs named fields",
+        )
+        .to_compile_error()
+        .into()
+    }
+
+    if let Err(err) = options.check_only(&fields.unnamed) {
+        return err.to_compile_error().into()
+    }
+
+    let module_name = options.naming.module(&input_ident);
 
     let mut module = new_module(module_name.clone());
-    module.vis = vis;
+    module.vis = vis.clone();
 
     let contents = &mut module.content.as_mut().unwrap().1;
 
@@ -360,67 +889,234 @@ fn derive_unnamed(ty: syn::DeriveInput) -> TokenStream {
     let mut fields_new =
         syn::punctuated::Punctuated::<_, syn::Token![,]>::new();
 
-    contents.push(item!(
-        use super::*;
-    ));
+    let mut impls = Vec::new();
+    let mut layout = Vec::new();
+    let mut names = Vec::new();
+    let mut visible = Vec::new();
+    let mut tags = Vec::new();
+    let mut field_attrs = Vec::new();
+    let mut markers = Vec::new();
+
+    // the unsized tail of a parent can't be a `Field::Type`, so it is skipped
+    let unsized_parent = fields
+        .unnamed
+        .last()
+        .map_or(false, |field| is_unsized(&field.ty, &generics));
+    let sized_fields = fields.unnamed.len() - unsized_parent as usize;
+
+    // the `#[repr(C)]` offsets depend on the fields which aren't in `only` too
+    let repr_c_layout = fields
+        .unnamed
+        .iter()
+        .enumerate()
+        .take(sized_fields)
+        .map(|(i, field)| (syn::Member::Unnamed(i.into()), field.ty.clone()))
+        .collect::<Vec<_>>();
 
     let (generic_header, generic, where_clause) = generics.split_for_impl();
-    for (i, field) in fields.unnamed.iter().enumerate() {
+    for (i, field) in fields.unnamed.iter().enumerate().take(sized_fields) {
         use syn::spanned::Spanned;
-        let ident = quote::format_ident!("_{}", i, span = field.span());
+        let marker = options.naming.tuple_marker(i, field.span());
+
+        let index = syn::Member::Unnamed(syn::Index {
+            index: i as u32,
+            span:  proc_macro2::Span::call_site(),
+        });
+
+        // the fields which aren't in `only` keep their place in `FIELDS`
+        if !options.includes(&index) {
+            fields_new.push(expr!(()));
+            fields_marker.push(syn::Field {
+                attrs: Vec::new(),
+                ty: syn::parse_quote!(()),
+                ..field.clone()
+            });
+            continue
+        }
+
+        let field_options = match attr::FieldOptions::parse(&field.attrs) {
+            Ok(field_options) => field_options,
+            Err(err) => return err.to_compile_error().into(),
+        };
+
+        if let Err(err) = push_tag(&mut tags, &field_options, unsized_parent) {
+            return err.to_compile_error().into()
+        }
+
+        if let Some(offset) = &field_options.offset {
+            match offset_guard(
+                &input_ident,
+                &generics,
+                unsized_parent,
+                &index,
+                offset,
+            ) {
+                Ok(guard) => impls.push(guard),
+                Err(err) => return err.to_compile_error().into(),
+            }
+        }
+
+        let marker_vis = marker_vis(&field.vis);
 
         contents.push(item!(
             #[allow(non_camel_case_types)]
-            pub struct #ident<T>(::gfp_core::derive::Invariant<T>);
+            #marker_vis struct #marker<__T: ?::core::marker::Sized>(::gfp_core::derive::Invariant<__T>);
         ));
 
         contents.push(item!(
-            impl<T> #ident<T> {
+            impl<__T: ?::core::marker::Sized> #marker<__T> {
                 pub const INIT: Self = Self(::gfp_core::derive::Invariant::INIT);
             }
         ));
 
         contents.push(item!(
-            impl<T> Clone for #ident<T> {
+            impl<__T: ?::core::marker::Sized> ::core::clone::Clone for #marker<__T> {
                 fn clone(&self) -> Self { *self }
             }
         ));
 
         contents.push(item!(
-            impl<T> Copy for #ident<T> {}
+            impl<__T: ?::core::marker::Sized> ::core::marker::Copy for #marker<__T> {}
         ));
 
+        markers.push(marker.clone());
+
         let ty = &field.ty;
+        layout.push((index.clone(), ty.clone()));
+        names.push((i.to_string(), index.clone()));
+        visible.push(is_as_visible(&field.vis, &vis));
+
+        if field_options.take.is_some() {
+            impls.push(take_methods(
+                &input_ident,
+                &generics,
+                &field.vis,
+                &index,
+                ty,
+            ));
+        }
 
-        let index = syn::Member::Unnamed(syn::Index {
-            index: i as u32,
-            span:  proc_macro2::Span::call_site(),
-        });
+        if options.with || field_options.with.is_some() {
+            if unsized_parent {
+                return syn::Error::new_spanned(
+                    &input_ident,
+                    "`with` needs a sized parent",
+                )
+                .to_compile_error()
+                .into()
+            }
 
-        contents.push(item!(
-            unsafe impl #generic_header ::gfp_core::Field for #ident<super::#input_ident #generic> {
-                type Parent = super::#input_ident #generic;
+            impls.push(with_method(
+                &input_ident,
+                &generics,
+                &field.vis,
+                &index,
+                ty,
+            ));
+        }
+
+        if options.aliases {
+            let alias = attr::alias(&input_ident, &i.to_string());
+            let params = alias_params(&generics);
+            let field_vis = &field.vis;
+
+            impls.push(item!(
+                #field_vis type #alias <#params> = #module_name::#marker<#input_ident #generic>;
+            ));
+
+            if field_options.flatten.is_some() {
+                let then = alias.append("Then");
+
+                impls.push(item!(
+                    #field_vis type #then <#params Then> = ::gfp_core::Chain<#alias <#params>, Then>;
+                ));
+            }
+        } else if let Some(flatten) = &field_options.flatten {
+            return syn::Error::new_spanned(
+                flatten,
+                "`flatten` requires `#[field(aliases)]` on the parent",
+            )
+            .to_compile_error()
+            .into()
+        }
+
+        let layout_check = layout_check(unsized_parent);
+
+        impls.push(item!(
+            unsafe impl #generic_header ::gfp_core::Field for #module_name::#marker<#input_ident #generic> {
+                type Parent = #input_ident #generic;
                 type Type = #ty;
 
                 #[inline]
                 unsafe fn project_raw(&self, ptr: *const Self::Parent) -> *const Self::Type {
+                    #layout_check
                     &(*ptr).#index
                 }
 
                 #[inline]
                 unsafe fn project_raw_mut(&self, ptr: *mut Self::Parent) -> *mut Self::Type {
+                    #layout_check
                     &mut (*ptr).#index
                 }
             }
         ));
 
+        let name = i.to_string();
+        impls.push(item!(
+            impl #generic_header ::gfp_core::AutoUnpin for #module_name::#marker<#input_ident #generic> #where_clause {}
+        ));
+
+        impls.push(item!(
+            impl #generic_header ::gfp_core::NamedField for #module_name::#marker<#input_ident #generic> #where_clause {
+                type Name = ::gfp_core::derive::Once<&'static str>;
+
+                #[inline]
+                fn name(&self) -> Self::Name {
+                    ::gfp_core::derive::once(#name)
+                }
+            }
+        ));
+
+        if is_as_visible(&field.vis, &vis) {
+            impls.push(item!(
+                impl #generic_header ::gfp_core::derive::FieldByName<{ ::gfp_core::derive::name_hash(#name) }> for #input_ident #generic #where_clause {
+                    type Field = #module_name::#marker<#input_ident #generic>;
+
+                    const FIELD: Self::Field = #module_name::#marker::INIT;
+                }
+            ));
+        }
+
+        // unsized parents don't have a statically known layout
+        let descriptor = if unsized_parent {
+            quote!()
+        } else {
+            checked_descriptor(&input_ident, &generics, &index, ty)
+        };
+
+        impls.push(item!(
+            impl #generic_header #module_name::#marker<#input_ident #generic> #where_clause {
+                #descriptor
+
+                #[inline]
+                pub const unsafe fn project_raw(&self, ptr: *const #input_ident #generic) -> *const #ty {
+                    &raw const (*ptr).#index
+                }
+
+                #[inline]
+                pub const unsafe fn project_raw_mut(&self, ptr: *mut #input_ident #generic) -> *mut #ty {
+                    &raw mut (*ptr).#index
+                }
+            }
+        ));
+
         let ty = TokenStream::from(quote!(
-            #module_name::#ident<#input_ident #generic>
+            #module_name::#marker<#input_ident #generic>
         ));
         let ty = syn::parse_macro_input!(ty as syn::Type);
 
         fields_new.push(expr!(
-            #module_name::#ident::INIT
+            #module_name::#marker::INIT
         ));
 
         let item = syn::Field {
@@ -430,9 +1126,58 @@ fn derive_unnamed(ty: syn::DeriveInput) -> TokenStream {
         };
 
         fields_marker.push(item);
+        field_attrs.push(field_options);
     }
 
+    impls.extend(disjoint_impls(
+        &input_ident,
+        &generics,
+        &module_name,
+        &markers,
+    ));
+
+    if !unsized_parent {
+        impls.extend(projection_guard(
+            &input_ident,
+            &generics,
+            &module_name,
+            &markers,
+            &layout,
+        ));
+    }
+
+    if options.require_repr_c && !unsized_parent {
+        impls.extend(repr_c_guard(
+            &input_ident,
+            &generics,
+            &repr_c_layout,
+            false,
+        ));
+    }
+
+    let enum_items = if unsized_parent {
+        quote!()
+    } else {
+        let field_enum = field_enum(&input_ident, &generics, &names, false);
+        let lookup = field_lookup(
+            &input_ident,
+            &generics,
+            &names,
+            &layout,
+            &tags,
+            &field_attrs,
+            &visible,
+            options.only.is_none() && visible.iter().all(|&visible| visible),
+        );
+        let info =
+            struct_info(&input_ident, &generics, &names, &layout, &field_attrs);
+
+        quote!(#field_enum #lookup #info)
+    };
+
     let field_type_name = input_ident.append("Fields");
+    let all_fields =
+        all_fields(&input_ident, &generics, &module_name, &markers);
 
     TokenStream::from(quote! {
         struct #field_type_name #generic_header(#fields_marker) #where_clause;
@@ -440,13 +1185,19 @@ fn derive_unnamed(ty: syn::DeriveInput) -> TokenStream {
         impl#generic_header #input_ident #generic #where_clause {
             const FIELDS: #field_type_name #generic = #field_type_name(#fields_new);
 
-            fn fields() -> #field_type_name #generic {
+            #all_fields
+
+            const fn fields() -> #field_type_name #generic {
                 #field_type_name(#fields_new)
             }
         }
 
         #[allow(non_snake_case)]
         #module
+
+        #(#impls)*
+
+        #enum_items
     })
 }
 
@@ -454,6 +1205,7 @@ fn derive_union(ty: syn::DeriveInput) -> TokenStream {
     let syn::DeriveInput {
         vis,
         ident: input_ident,
+        attrs,
         generics,
         data,
         ..
@@ -468,10 +1220,42 @@ fn derive_union(ty: syn::DeriveInput) -> TokenStream {
         unreachable!()
     };
 
-    let module_name = input_ident.append("_fields");
+    let options = match attr::ParentOptions::parse(&attrs) {
+        Ok(options) => options,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    if options.with {
+        return syn::Error::new_spanned(
+            input_ident,
+            "unions don't support `with`",
+        )
+        .to_compile_error()
+        .into()
+    }
+
+    if options.serde_names {
+        return syn::Error::new_spanned(
+            input_ident,
+            "unions don't support `serde_names`",
+        )
+        .to_compile_error()
+        .into()
+    }
+
+    if options.only.is_some() {
+        return syn::Error::new_spanned(
+            input_ident,
+            "unions don't support `only`",
+        )
+        .to_compile_error()
+        .into()
+    }
+
+    let module_name = options.naming.module(&input_ident);
 
     let mut module = new_module(module_name.clone());
-    module.vis = vis;
+    module.vis = vis.clone();
 
     let contents = &mut module.content.as_mut().unwrap().1;
 
@@ -480,21 +1264,46 @@ fn derive_union(ty: syn::DeriveInput) -> TokenStream {
     let mut fields_new =
         syn::punctuated::Punctuated::<_, syn::Token![,]>::new();
 
-    contents.push(item!(
-        use super::*;
-    ));
+    let mut impls = Vec::new();
+    let mut layout = Vec::new();
+    let mut names = Vec::new();
+    let mut idiomatic_names = Vec::new();
+    let mut field_attrs = Vec::new();
+    let mut markers = Vec::new();
 
     let (generic_header, generic, where_clause) = generics.split_for_impl();
     for field in fields.named {
         let ident = field.ident.unwrap();
 
+        if let Err(err) = push_idiomatic_name(&mut idiomatic_names, &ident) {
+            return err.to_compile_error().into()
+        }
+
+        let marker = options.naming.marker(&ident);
+
+        let field_options = match attr::FieldOptions::parse(&field.attrs) {
+            Ok(field_options) => field_options,
+            Err(err) => return err.to_compile_error().into(),
+        };
+
+        if let Some(offset) = &field_options.offset {
+            let member = syn::Member::Named(ident.clone());
+            match offset_guard(&input_ident, &generics, false, &member, offset)
+            {
+                Ok(guard) => impls.push(guard),
+                Err(err) => return err.to_compile_error().into(),
+            }
+        }
+
+        let marker_vis = marker_vis(&field.vis);
+
         contents.push(item!(
             #[allow(non_camel_case_types)]
-            pub struct #ident<T>(::gfp_core::derive::Invariant<T>);
+            #marker_vis struct #marker<__T: ?::core::marker::Sized>(::gfp_core::derive::Invariant<__T>);
         ));
 
         contents.push(item!(
-            impl<T> #ident<T> {
+            impl<__T: ?::core::marker::Sized> #marker<__T> {
                 pub const unsafe fn init() -> Self {
                     Self(::gfp_core::derive::Invariant::INIT)
                 }
@@ -502,41 +1311,134 @@ fn derive_union(ty: syn::DeriveInput) -> TokenStream {
         ));
 
         contents.push(item!(
-            impl<T> Clone for #ident<T> {
+            impl<__T: ?::core::marker::Sized> ::core::clone::Clone for #marker<__T> {
                 fn clone(&self) -> Self { *self }
             }
         ));
 
         contents.push(item!(
-            impl<T> Copy for #ident<T> {}
+            impl<__T: ?::core::marker::Sized> ::core::marker::Copy for #marker<__T> {}
         ));
 
+        markers.push(marker.clone());
+
         let ty = &field.ty;
+        layout.push((syn::Member::Named(ident.clone()), ty.clone()));
+        names.push((
+            ident.unraw().to_string(),
+            syn::Member::Named(ident.clone()),
+        ));
 
-        contents.push(item!(
-            unsafe impl #generic_header ::gfp_core::Field for #ident<super::#input_ident #generic> {
-                type Parent = super::#input_ident #generic;
+        if let Some(tag) = &field_options.tag {
+            return syn::Error::new_spanned(tag, "unions don't support `tag`")
+                .to_compile_error()
+                .into()
+        }
+
+        if let Some(take) = &field_options.take {
+            return syn::Error::new_spanned(take, "unions don't support `take`")
+                .to_compile_error()
+                .into()
+        }
+
+        if let Some(with) = &field_options.with {
+            return syn::Error::new_spanned(with, "unions don't support `with`")
+                .to_compile_error()
+                .into()
+        }
+
+        if options.aliases {
+            let alias = attr::alias(&input_ident, &ident.unraw().to_string());
+            let params = alias_params(&generics);
+            let field_vis = &field.vis;
+
+            impls.push(item!(
+                #field_vis type #alias <#params> = #module_name::#marker<#input_ident #generic>;
+            ));
+
+            if field_options.flatten.is_some() {
+                let then = alias.append("Then");
+
+                impls.push(item!(
+                    #field_vis type #then <#params Then> = ::gfp_core::Chain<#alias <#params>, Then>;
+                ));
+            }
+        } else if let Some(flatten) = &field_options.flatten {
+            return syn::Error::new_spanned(
+                flatten,
+                "`flatten` requires `#[field(aliases)]` on the parent",
+            )
+            .to_compile_error()
+            .into()
+        }
+
+        let layout_check = layout_check(false);
+
+        impls.push(item!(
+            unsafe impl #generic_header ::gfp_core::Field for #module_name::#marker<#input_ident #generic> {
+                type Parent = #input_ident #generic;
                 type Type = #ty;
 
                 #[inline]
                 unsafe fn project_raw(&self, ptr: *const Self::Parent) -> *const Self::Type {
+                    #layout_check
                     ::gfp_core::ptr_project!(const ptr #ident)
                 }
 
                 #[inline]
                 unsafe fn project_raw_mut(&self, ptr: *mut Self::Parent) -> *mut Self::Type {
+                    #layout_check
+                    ::gfp_core::ptr_project!(mut ptr #ident)
+                }
+            }
+        ));
+
+        let name = ident.unraw().to_string();
+        impls.push(item!(
+            impl #generic_header ::gfp_core::AutoUnpin for #module_name::#marker<#input_ident #generic> #where_clause {}
+        ));
+
+        impls.push(item!(
+            impl #generic_header ::gfp_core::NamedField for #module_name::#marker<#input_ident #generic> #where_clause {
+                type Name = ::gfp_core::derive::Once<&'static str>;
+
+                #[inline]
+                fn name(&self) -> Self::Name {
+                    ::gfp_core::derive::once(#name)
+                }
+            }
+        ));
+
+        let descriptor = checked_descriptor(
+            &input_ident,
+            &generics,
+            &syn::Member::Named(ident.clone()),
+            ty,
+        );
+
+        impls.push(item!(
+            impl #generic_header #module_name::#marker<#input_ident #generic> #where_clause {
+                #descriptor
+
+                #[inline]
+                pub const unsafe fn project_raw(&self, ptr: *const #input_ident #generic) -> *const #ty {
+                    ::gfp_core::ptr_project!(const ptr #ident)
+                }
+
+                #[inline]
+                pub const unsafe fn project_raw_mut(&self, ptr: *mut #input_ident #generic) -> *mut #ty {
                     ::gfp_core::ptr_project!(mut ptr #ident)
                 }
             }
         ));
 
         let ty = TokenStream::from(quote!(
-            #module_name::#ident<#input_ident #generic>
+            #module_name::#marker<#input_ident #generic>
         ));
         let ty = syn::parse_macro_input!(ty as syn::Type);
 
         fields_new.push(expr!(
-            #ident: #module_name::#ident::init()
+            #ident: #module_name::#marker::init()
         ));
 
         let item = syn::Field {
@@ -548,8 +1450,26 @@ fn derive_union(ty: syn::DeriveInput) -> TokenStream {
         };
 
         fields_marker.push(item);
+        field_attrs.push(field_options);
+    }
+
+    impls.extend(projection_guard(
+        &input_ident,
+        &generics,
+        &module_name,
+        &markers,
+        &layout,
+    ));
+
+    if options.require_repr_c {
+        impls.extend(repr_c_guard(&input_ident, &generics, &layout, true));
     }
 
+    let field_enum = field_enum(&input_ident, &generics, &names, true);
+    let info =
+        struct_info(&input_ident, &generics, &names, &layout, &field_attrs);
+    let enum_items = quote!(#field_enum #info);
+
     let field_type_name = input_ident.append("Fields");
 
     TokenStream::from(quote! {
@@ -558,7 +1478,7 @@ fn derive_union(ty: syn::DeriveInput) -> TokenStream {
         }
 
         impl#generic_header #input_ident #generic #where_clause {
-            unsafe fn fields() -> #field_type_name #generic {
+            const unsafe fn fields() -> #field_type_name #generic {
                 #field_type_name {
                     #fields_new
                 }
@@ -567,9 +1487,764 @@ fn derive_union(ty: syn::DeriveInput) -> TokenStream {
 
         #[allow(non_snake_case)]
         #module
+
+        #(#impls)*
+
+        #enum_items
+    })
+}
+
+/// `take_*` and `clear_*` methods, which replace the field with its default
+fn take_methods(
+    input_ident: &syn::Ident,
+    generics: &syn::Generics,
+    vis: &syn::Visibility,
+    member: &syn::Member,
+    ty: &syn::Type,
+) -> syn::Item {
+    let (generic_header, generic, where_clause) = generics.split_for_impl();
+
+    let name = match member {
+        syn::Member::Named(ident) => ident.unraw().to_string(),
+        syn::Member::Unnamed(index) => index.index.to_string(),
+    };
+    let take = quote::format_ident!("take_{}", name);
+    let clear = quote::format_ident!("clear_{}", name);
+
+    syn::parse_quote! {
+        #[allow(dead_code)]
+        impl #generic_header #input_ident #generic #where_clause {
+            /// Replace the field with its default value, and return the old
+            /// value
+            #vis fn #take(&mut self) -> #ty
+            where
+                #ty: ::core::default::Default,
+            {
+                let field = ::gfp_core::ProjectTo::project_to(
+                    self,
+                    Self::FIELDS.#member,
+                );
+
+                ::core::mem::take(field)
+            }
+
+            /// Replace the field with its default value
+            #vis fn #clear(&mut self)
+            where
+                #ty: ::core::default::Default,
+            {
+                self.#take();
+            }
+        }
+    }
+}
+
+/// A `with_*` method, which replaces the field and returns the parent
+fn with_method(
+    input_ident: &syn::Ident,
+    generics: &syn::Generics,
+    vis: &syn::Visibility,
+    member: &syn::Member,
+    ty: &syn::Type,
+) -> syn::Item {
+    let (generic_header, generic, where_clause) = generics.split_for_impl();
+
+    let name = match member {
+        syn::Member::Named(ident) => ident.unraw().to_string(),
+        syn::Member::Unnamed(index) => index.index.to_string(),
+    };
+    let with = quote::format_ident!("with_{}", name);
+
+    syn::parse_quote! {
+        #[allow(dead_code)]
+        impl #generic_header #input_ident #generic #where_clause {
+            /// Replace the field with `value`, and return the updated value
+            #vis fn #with(mut self, value: #ty) -> Self {
+                let field = ::gfp_core::ProjectTo::project_to(
+                    &mut self,
+                    Self::FIELDS.#member,
+                );
+
+                *field = value;
+                self
+            }
+        }
+    }
+}
+
+/// An enum with a variant for every field, which can be parsed from the
+/// names of the fields and converted to an `AnyField`
+fn field_enum(
+    input_ident: &syn::Ident,
+    generics: &syn::Generics,
+    names: &[(String, syn::Member)],
+    is_union: bool,
+) -> proc_macro2::TokenStream {
+    let (generic_header, generic, where_clause) = generics.split_for_impl();
+    let enum_name = input_ident.append("Field");
+
+    let variants = names
+        .iter()
+        .map(|(name, _)| attr::variant(name, input_ident.span()))
+        .collect::<Vec<_>>();
+    let names_str = names.iter().map(|(name, _)| name);
+    let members = names.iter().map(|(_, member)| member);
+    let count = names.len();
+
+    let (unsafety, fields) = if is_union {
+        (quote!(unsafe), quote!(Self::fields()))
+    } else {
+        (quote!(), quote!(Self::FIELDS))
+    };
+
+    quote! {
+        #[allow(dead_code)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        enum #enum_name {
+            #(#variants,)*
+        }
+
+        #[allow(dead_code)]
+        impl #enum_name {
+            const ALL: [Self; #count] = [#(Self::#variants,)*];
+
+            fn name(self) -> &'static str {
+                match self {
+                    #(Self::#variants => #names_str,)*
+                }
+            }
+        }
+
+        impl ::core::str::FromStr for #enum_name {
+            type Err = ::gfp_core::UnknownField;
+
+            fn from_str(name: &str) -> ::core::result::Result<Self, Self::Err> {
+                #(
+                    if name == Self::#variants.name() {
+                        return ::core::result::Result::Ok(Self::#variants)
+                    }
+                )*
+
+                ::core::result::Result::Err(::gfp_core::UnknownField)
+            }
+        }
+
+        #[allow(dead_code)]
+        impl #generic_header #input_ident #generic #where_clause {
+            const FIELD_COUNT: usize = #count;
+
+            #unsafety fn field_at(index: usize) -> ::core::option::Option<::gfp_core::AnyField<Self>>
+            where
+                Self: 'static,
+            {
+                match #enum_name::ALL.get(index) {
+                    ::core::option::Option::Some(&field) => {
+                        ::core::option::Option::Some(Self::field_of(field))
+                    },
+                    ::core::option::Option::None => ::core::option::Option::None,
+                }
+            }
+
+            #unsafety fn field_of(field: #enum_name) -> ::gfp_core::AnyField<Self>
+            where
+                Self: 'static,
+            {
+                match field {
+                    #(
+                        #enum_name::#variants => ::gfp_core::AnyField::new(
+                            field.name(),
+                            #fields.#members,
+                        ),
+                    )*
+                }
+            }
+        }
+    }
+}
+
+/// A `FieldLookup` impl which resolves the fields by name, and by their tags
+fn field_lookup(
+    input_ident: &syn::Ident,
+    generics: &syn::Generics,
+    names: &[(String, syn::Member)],
+    layout: &[(syn::Member, syn::Type)],
+    tags: &[Option<u32>],
+    field_attrs: &[attr::FieldOptions],
+    visible: &[bool],
+    complete: bool,
+) -> proc_macro2::TokenStream {
+    let mut generics = generics.clone();
+    generics
+        .make_where_clause()
+        .predicates
+        .push(syn::parse_quote!(Self: 'static));
+    let (generic_header, generic, where_clause) = generics.split_for_impl();
+
+    // the fields less visible than the parent aren't listed, otherwise they
+    // could be found by name where they can't be accessed
+    let listed = (0..names.len()).filter(|&index| visible[index]);
+
+    let names_str = listed
+        .clone()
+        .map(|index| &names[index].0)
+        .collect::<Vec<_>>();
+    let fields = listed.clone().map(|index| {
+        let (name, member) = &names[index];
+        let (_, ty) = &layout[index];
+        let attrs = &field_attrs[index];
+
+        let with_tag = tags[index].map(|tag| {
+            let tag = proc_macro2::Literal::u32_unsuffixed(tag);
+            quote!(.with_tag(#tag))
+        });
+        let with_display = attrs
+            .display
+            .as_ref()
+            .map(|display| quote!(.with_display(#display)));
+        let with_meta = if attrs.meta.is_empty() {
+            quote!()
+        } else {
+            let (keys, values): (Vec<_>, Vec<_>) =
+                attrs.meta.iter().cloned().unzip();
+            quote!(.with_meta(&[#((#keys, #values),)*]))
+        };
+
+        quote! {
+            (&&&::gfp_core::derive::ParseProbe::<#ty>::NEW).with_parse(
+                (&&::gfp_core::derive::DeserializeProbe::<#ty>::NEW).with_deserialize(
+                    (&&::gfp_core::derive::CloneProbe::<#ty>::NEW).with_clone(
+                        ::gfp_core::FieldInfo::new(
+                            #name,
+                            Self::FIELDS.#member,
+                            (&&::gfp_core::derive::LookupProbe::<#ty>::NEW).lookup(),
+                        )
+                    )
+                )
+            )
+            #with_tag
+            #with_display
+            #with_meta
+        }
+    });
+
+    // without any tags the default, which finds nothing, is enough
+    let field_by_tag = if tags.iter().any(Option::is_some) {
+        let (tags, tag_names): (Vec<_>, Vec<_>) = listed
+            .filter_map(|index| {
+                let tag = proc_macro2::Literal::u32_unsuffixed(tags[index]?);
+                Some((tag, &names[index].0))
+            })
+            .unzip();
+
+        quote! {
+            fn field_by_tag(tag: u32) -> ::core::option::Option<::gfp_core::FieldInfo> {
+                match tag {
+                    #(#tags => Self::lookup_field(#tag_names),)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    // with `only` or less visible fields, some of the fields aren't listed in
+    // `field_names`
+    let complete_lookup = if complete {
+        quote! {
+            // every field is listed in `field_names`
+            unsafe impl #generic_header ::gfp_core::CompleteFieldLookup for #input_ident #generic #where_clause {}
+        }
+    } else {
+        quote!()
+    };
+
+    quote! {
+        impl #generic_header ::gfp_core::FieldLookup for #input_ident #generic #where_clause {
+            fn lookup_field(name: &str) -> ::core::option::Option<::gfp_core::FieldInfo> {
+                #[allow(unused_imports)]
+                use ::gfp_core::derive::{
+                    ViaClone as _, ViaDeserialize as _, ViaLookup as _, ViaNoClone as _,
+                    ViaNoDeserialize as _, ViaNoParse as _, ViaNone as _, ViaParse as _,
+                    ViaParseSome as _,
+                };
+
+                match name {
+                    #(#names_str => ::core::option::Option::Some(#fields),)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+
+            fn field_names() -> &'static [&'static str] {
+                &[#(#names_str,)*]
+            }
+
+            #field_by_tag
+        }
+
+        #complete_lookup
+    }
+}
+
+/// Record the tag of a field, checking that it isn't used by an earlier field
+fn push_tag(
+    tags: &mut Vec<Option<u32>>,
+    options: &attr::FieldOptions,
+    unsized_parent: bool,
+) -> syn::Result<()> {
+    let lit = match &options.tag {
+        Some(lit) => lit,
+        None => {
+            tags.push(None);
+            return Ok(())
+        },
+    };
+
+    if unsized_parent {
+        return Err(syn::Error::new_spanned(lit, "`tag` needs a sized parent"))
+    }
+
+    let tag = lit.base10_parse::<u32>()?;
+
+    if tags.contains(&Some(tag)) {
+        return Err(syn::Error::new_spanned(
+            lit,
+            format!("the tag {} is used by another field", tag),
+        ))
+    }
+
+    tags.push(Some(tag));
+    Ok(())
+}
+
+/// Record the idiomatic name of a field, checking that no earlier field has
+/// the same one, i.e. `fooBar` and `foo_bar` are both `FooBar`, which would
+/// clash in the field enum, and between the field types with idiomatic naming
+fn push_idiomatic_name(
+    idiomatic_names: &mut Vec<(String, syn::Ident)>,
+    ident: &syn::Ident,
+) -> syn::Result<()> {
+    let name = attr::variant(&ident.unraw().to_string(), ident.span());
+    let name = name.to_string();
+
+    let other = idiomatic_names.iter().find(|(other, _)| *other == name);
+    if let Some((_, other)) = other {
+        return Err(syn::Error::new_spanned(
+            ident,
+            format!(
+                "the fields `{}` and `{}` both generate the name `{}`",
+                other.unraw(),
+                ident.unraw(),
+                name,
+            ),
+        ))
+    }
+
+    idiomatic_names.push((name, ident.clone()));
+    Ok(())
+}
+
+/// A `HasStructInfo` impl which describes the layout of the parent
+fn struct_info(
+    input_ident: &syn::Ident,
+    generics: &syn::Generics,
+    names: &[(String, syn::Member)],
+    layout: &[(syn::Member, syn::Type)],
+    field_attrs: &[attr::FieldOptions],
+) -> Option<syn::Item> {
+    // generic parents don't have a single layout
+    if !generics.params.is_empty() {
+        return None
+    }
+
+    let name = input_ident.unraw().to_string();
+    let fields = names.iter().zip(layout).map(|((name, member), (_, ty))| {
+        quote! {
+            (
+                #name,
+                ::gfp_core::FieldDescriptor::new::<#input_ident, #ty>(
+                    ::gfp_core::derive::offset_of!(#input_ident, #member)
+                ),
+            )
+        }
+    });
+
+    // without any display names the default, which has none, is enough
+    let has_display = field_attrs.iter().any(|attrs| attrs.display.is_some());
+    let with_display_names = if has_display {
+        let displays = field_attrs.iter().map(|attrs| {
+            match &attrs.display {
+                Some(display) => quote!(::core::option::Option::Some(#display)),
+                None => quote!(::core::option::Option::None),
+            }
+        });
+
+        quote!(.with_display_names(&[#(#displays,)*]))
+    } else {
+        quote!()
+    };
+
+    Some(syn::parse_quote! {
+        impl ::gfp_core::HasStructInfo for #input_ident {
+            const STRUCT_INFO: ::gfp_core::StructInfo =
+                ::gfp_core::StructInfo::new::<Self>(#name, &[#(#fields,)*])
+                    #with_display_names;
+        }
     })
 }
 
+/// The generic parameters of the parent, without bounds and with a trailing
+/// comma, for use in a type alias
+fn alias_params(generics: &syn::Generics) -> proc_macro2::TokenStream {
+    let params = generics.params.iter().map(|param| {
+        match param {
+            syn::GenericParam::Type(param) => {
+                let ident = &param.ident;
+                quote!(#ident)
+            },
+            syn::GenericParam::Lifetime(param) => {
+                let lifetime = &param.lifetime;
+                quote!(#lifetime)
+            },
+            syn::GenericParam::Const(param) => {
+                let ident = &param.ident;
+                let ty = &param.ty;
+                quote!(const #ident: #ty)
+            },
+        }
+    });
+
+    quote!(#(#params,)*)
+}
+
+/// A compile time check that the fields are laid out in declaration order,
+/// following the `#[repr(C)]` algorithm
+fn repr_c_guard(
+    input_ident: &syn::Ident,
+    generics: &syn::Generics,
+    layout: &[(syn::Member, syn::Type)],
+    is_union: bool,
+) -> Option<syn::Item> {
+    // generic parents can't be checked with a free constant
+    if !generics.params.is_empty() {
+        return None
+    }
+
+    let checks = layout.iter().map(|(member, ty)| {
+        let message = format!(
+            "`{}` is not at its `#[repr(C)]` offset",
+            quote!(#input_ident.#member)
+        );
+
+        let next = if is_union {
+            quote!()
+        } else {
+            quote!(offset += ::core::mem::size_of::<#ty>();)
+        };
+
+        quote!(
+            let expected = ::gfp_core::derive::align_up(
+                offset,
+                ::core::mem::align_of::<#ty>(),
+            );
+            ::core::assert!(
+                ::gfp_core::derive::offset_of!(#input_ident, #member) == expected,
+                #message
+            );
+            offset = expected;
+            #next
+        )
+    });
+
+    Some(syn::parse_quote!(
+        #[allow(unused_assignments)]
+        const _: () = {
+            let mut offset = 0_usize;
+            #(#checks)*
+        };
+    ))
+}
+
+/// `ALL_FIELDS`, the list of every field of a struct, in declaration order
+fn all_fields(
+    input_ident: &syn::Ident,
+    generics: &syn::Generics,
+    module_name: &syn::Ident,
+    markers: &[syn::Ident],
+) -> proc_macro2::TokenStream {
+    let (_, generic, _) = generics.split_for_impl();
+
+    let mut ty = quote!(::gfp_core::derive::Nil);
+    let mut value = quote!(::gfp_core::derive::Nil);
+
+    for marker in markers.iter().rev() {
+        ty = quote!(
+            ::gfp_core::derive::Cons<#module_name::#marker<#input_ident #generic>, #ty>
+        );
+        value = quote!(
+            ::gfp_core::derive::Cons {
+                value: #module_name::#marker::INIT,
+                rest: #value,
+            }
+        );
+    }
+
+    quote! {
+        const ALL_FIELDS: #ty = #value;
+    }
+}
+
+/// The `Disjoint` proofs for the fields of a struct, each field is the root
+/// of the paths that start with it, and is a sibling of every other field
+fn disjoint_impls(
+    input_ident: &syn::Ident,
+    generics: &syn::Generics,
+    module_name: &syn::Ident,
+    markers: &[syn::Ident],
+) -> Vec<syn::Item> {
+    let (generic_header, generic, where_clause) = generics.split_for_impl();
+    let mut impls = Vec::new();
+
+    for marker in markers {
+        impls.push(syn::parse_quote!(
+            impl #generic_header ::gfp_core::derive::PathRoot for #module_name::#marker<#input_ident #generic> #where_clause {
+                type Root = Self;
+            }
+        ));
+
+        for sibling in markers.iter().filter(|&sibling| sibling != marker) {
+            impls.push(syn::parse_quote!(
+                unsafe impl #generic_header ::gfp_core::derive::Siblings<#module_name::#sibling<#input_ident #generic>> for #module_name::#marker<#input_ident #generic> #where_clause {}
+            ));
+        }
+    }
+
+    impls
+}
+
+/// The `DESCRIPTOR` of a field, which asserts that it agrees with the address
+/// of the field inside of the parent, as computed by `addr_of!`
+///
+/// The assertion is part of the constant, so it is checked for every
+/// instantiation of a generic parent that is projected through, see
+/// `layout_check`.
+fn checked_descriptor(
+    input_ident: &syn::Ident,
+    generics: &syn::Generics,
+    member: &syn::Member,
+    ty: &syn::Type,
+) -> proc_macro2::TokenStream {
+    let (_, generic, _) = generics.split_for_impl();
+
+    let message = format!(
+        "the descriptor of `{}` doesn't match the layout of the parent",
+        quote!(#input_ident.#member)
+    );
+
+    quote! {
+        pub const DESCRIPTOR: ::gfp_core::FieldDescriptor = {
+            let descriptor = ::gfp_core::FieldDescriptor::new::<#input_ident #generic, #ty>(
+                ::gfp_core::derive::offset_of!(#input_ident #generic, #member)
+            );
+
+            let parent = ::core::mem::MaybeUninit::<#input_ident #generic>::uninit();
+            let ptr = parent.as_ptr();
+
+            // Safety
+            // * taking the address of a field doesn't read from the parent
+            let actual = unsafe {
+                ::gfp_core::FieldDescriptor::from_pointers(
+                    ptr,
+                    ::core::ptr::addr_of!((*ptr).#member),
+                )
+            };
+
+            ::core::assert!(
+                actual.offset() == descriptor.offset()
+                    && actual.size() == descriptor.size(),
+                #message
+            );
+
+            descriptor
+        };
+    }
+}
+
+/// Evaluates the `DESCRIPTOR` of a field in its projections, so its layout
+/// assertions are checked for the exact parent that is projected through,
+/// even if the parent is generic
+fn layout_check(unsized_parent: bool) -> proc_macro2::TokenStream {
+    // unsized parents don't have a `DESCRIPTOR`
+    if unsized_parent {
+        quote!()
+    } else {
+        quote!(let _ = Self::DESCRIPTOR;)
+    }
+}
+
+/// A compile time check that the pointer produced by projecting to each field
+/// is at the offset in its `DESCRIPTOR`
+fn projection_guard(
+    input_ident: &syn::Ident,
+    generics: &syn::Generics,
+    module_name: &syn::Ident,
+    markers: &[syn::Ident],
+    layout: &[(syn::Member, syn::Type)],
+) -> Option<syn::Item> {
+    // generic parents can't be checked with a free constant
+    if !generics.params.is_empty() {
+        return None
+    }
+
+    let checks = markers.iter().zip(layout).map(|(marker, (member, _))| {
+        let message = format!(
+            "the projection to `{}` is not at the offset in its descriptor",
+            quote!(#input_ident.#member)
+        );
+
+        quote!(
+            // Safety
+            // * projecting a raw pointer doesn't read from the parent
+            let projected = unsafe {
+                ::gfp_core::FieldDescriptor::from_pointers(
+                    ptr,
+                    #module_name::#marker::<#input_ident>::INIT.project_raw(ptr),
+                )
+            };
+            ::core::assert!(
+                projected.offset() == #module_name::#marker::<#input_ident>::DESCRIPTOR.offset(),
+                #message
+            );
+        )
+    });
+
+    Some(syn::parse_quote!(
+        const _: () = {
+            let parent = ::core::mem::MaybeUninit::<#input_ident>::uninit();
+            let ptr = parent.as_ptr();
+            #(#checks)*
+        };
+    ))
+}
+
+/// A compile time check that `member` of the parent is at `offset` bytes
+fn offset_guard(
+    input_ident: &syn::Ident,
+    generics: &syn::Generics,
+    unsized_parent: bool,
+    member: &syn::Member,
+    offset: &syn::LitInt,
+) -> syn::Result<syn::Item> {
+    if !generics.params.is_empty() || unsized_parent {
+        return Err(syn::Error::new_spanned(
+            offset,
+            "offsets can only be checked for non-generic sized parents",
+        ))
+    }
+
+    let message = format!(
+        "`{}` is not at offset {}",
+        quote!(#input_ident.#member),
+        offset
+    );
+
+    Ok(syn::parse_quote!(
+        const _: () = ::core::assert!(
+            ::gfp_core::derive::offset_of!(#input_ident, #member) == #offset,
+            #message
+        );
+    ))
+}
+
+/// Checks if `ty` is syntactically unsized, i.e. a slice, `str`, a trait
+/// object, or a type parameter with a `?Sized` bound
+fn is_unsized(ty: &syn::Type, generics: &syn::Generics) -> bool {
+    let is_maybe_sized = |bound: &syn::TypeParamBound| {
+        if let syn::TypeParamBound::Trait(bound) = bound {
+            matches!(bound.modifier, syn::TraitBoundModifier::Maybe(_))
+        } else {
+            false
+        }
+    };
+
+    let ident = match ty {
+        syn::Type::Slice(_) | syn::Type::TraitObject(_) => return true,
+        syn::Type::Paren(ty) => return is_unsized(&ty.elem, generics),
+        syn::Type::Group(ty) => return is_unsized(&ty.elem, generics),
+        syn::Type::Path(ty) if ty.qself.is_none() => {
+            match ty.path.get_ident() {
+                Some(ident) => ident,
+                None => return false,
+            }
+        },
+        _ => return false,
+    };
+
+    if ident == "str" {
+        return true
+    }
+
+    let in_params = generics.type_params().any(|param| {
+        param.ident == *ident && param.bounds.iter().any(is_maybe_sized)
+    });
+
+    let in_where_clause = generics
+        .where_clause
+        .iter()
+        .flat_map(|where_clause| &where_clause.predicates)
+        .filter_map(|predicate| {
+            match predicate {
+                syn::WherePredicate::Type(predicate) => Some(predicate),
+                _ => None,
+            }
+        })
+        .any(|predicate| {
+            matches!(
+                &predicate.bounded_ty,
+                syn::Type::Path(bounded) if bounded.path.is_ident(ident)
+            ) && predicate.bounds.iter().any(is_maybe_sized)
+        });
+
+    in_params || in_where_clause
+}
+
+/// The visibility of the type of a field with the visibility `vis`
+///
+/// Field types live in a module inside of the module of the parent, so
+/// visibilities relative to the module of the parent are moved up by one.
+fn marker_vis(vis: &syn::Visibility) -> syn::Visibility {
+    let restricted = match vis {
+        syn::Visibility::Inherited => return syn::parse_quote!(pub(super)),
+        syn::Visibility::Restricted(restricted) => restricted,
+        _ => return vis.clone(),
+    };
+
+    let path = &restricted.path;
+    let mut segments = path.segments.iter().map(|segment| &segment.ident);
+
+    match segments.next() {
+        Some(first) if first == "crate" => vis.clone(),
+        Some(first) if first == "self" => {
+            syn::parse_quote!(pub(in super #(::#segments)*))
+        },
+        _ => syn::parse_quote!(pub(in super::#path)),
+    }
+}
+
+/// Check if a field with the visibility `field` is visible everywhere its
+/// parent with the visibility `parent` is, this is conservative so some
+/// fields which are as visible aren't detected
+fn is_as_visible(field: &syn::Visibility, parent: &syn::Visibility) -> bool {
+    match (field, parent) {
+        (syn::Visibility::Public(_), _) | (_, syn::Visibility::Inherited) => {
+            true
+        },
+        (field, parent) => {
+            quote!(#field).to_string() == quote!(#parent).to_string()
+        },
+    }
+}
+
 fn new_module(ident: syn::Ident) -> syn::ItemMod {
     syn::ItemMod {
         attrs: Vec::new(),
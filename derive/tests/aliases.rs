@@ -0,0 +1,36 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::*;
+
+#[derive(Field)]
+#[field(aliases)]
+struct Outer<T> {
+    id:    u32,
+    #[field(flatten)]
+    inner: Inner<T>,
+}
+
+#[derive(Field)]
+#[field(aliases)]
+struct Inner<T>(T, u8);
+
+fn project_value<T>(
+    outer: &Outer<T>,
+    field: OuterInnerThen<T, InnerField0<T>>,
+) -> &T {
+    outer.project_to(field)
+}
+
+#[test]
+fn aliases() {
+    let outer = Outer {
+        id:    1,
+        inner: Inner("value", 2),
+    };
+
+    let id: OuterId<&str> = Outer::fields().id;
+    let value = Outer::fields().inner.chain(Inner::fields().0);
+
+    assert_eq!(*outer.project_to(id), 1);
+    assert_eq!(*project_value(&outer, value), "value");
+}
@@ -0,0 +1,54 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::*;
+
+#[derive(Field)]
+#[repr(C)]
+struct Header {
+    #[field(offset = 0)]
+    magic:   u32,
+    #[field(offset = 4)]
+    version: u16,
+    #[field(offset = 8)]
+    length:  u64,
+}
+
+#[derive(Field)]
+#[repr(C)]
+struct Pair(#[field(offset = 0)] u8, #[field(offset = 4)] u32);
+
+#[test]
+fn offsets_match() {
+    assert_eq!(Header::fields().length.field_offset(), 8);
+    assert_eq!(Pair::fields().1.field_offset(), 4);
+}
+
+#[derive(Field)]
+#[field(require_repr_c)]
+#[repr(C)]
+struct Record {
+    tag:   u8,
+    value: u32,
+    flags: u16,
+}
+
+#[derive(Field)]
+#[field(require_repr_c)]
+#[repr(C)]
+union Word {
+    bytes: [u8; 4],
+    value: u32,
+}
+
+#[test]
+fn repr_c_offsets() {
+    let fields = Record::fields();
+
+    assert_eq!(fields.tag.field_offset(), 0);
+    assert_eq!(fields.value.field_offset(), 4);
+    assert_eq!(fields.flags.field_offset(), 8);
+
+    let fields = unsafe { Word::fields() };
+    assert_eq!(fields.bytes.field_offset(), 0);
+    assert_eq!(fields.value.field_offset(), 0);
+}
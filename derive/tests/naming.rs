@@ -0,0 +1,76 @@
+#![feature(raw_ref_op)]
+#![deny(nonstandard_style)]
+
+use gfp_core::*;
+
+#[derive(Field)]
+#[field(naming = "idiomatic")]
+struct HttpRequest {
+    method:      u8,
+    status_code: u16,
+}
+
+#[derive(Field)]
+struct Pair(u8, u32);
+
+#[derive(Field)]
+#[field(naming = "legacy")]
+struct Point {
+    x: u8,
+    y: u8,
+}
+
+// the field types don't shadow the names used by the generated code
+#[derive(Field)]
+struct Sample {
+    t:     u64,
+    clone: bool,
+    sized: u8,
+}
+
+#[test]
+fn idiomatic_names() {
+    let request = HttpRequest {
+        method:      1,
+        status_code: 200,
+    };
+
+    let status: http_request_fields::StatusCode<HttpRequest> =
+        HttpRequest::fields().status_code;
+    let method: http_request_fields::Method<HttpRequest> =
+        HttpRequest::fields().method;
+
+    assert_eq!(*request.project_to(status), 200);
+    assert_eq!(*request.project_to(method), 1);
+
+    let second: pair_fields::Field1<Pair> = Pair::fields().1;
+
+    assert_eq!(*Pair(1, 2).project_to(second), 2);
+}
+
+#[test]
+fn legacy_names() {
+    let point = Point {
+        x: 1,
+        y: 2,
+    };
+
+    let y: Point_fields::y<Point> = Point::fields().y;
+
+    assert_eq!(*point.project_to(y), 2);
+}
+
+#[test]
+fn names_used_by_generated_code() {
+    let sample = Sample {
+        t:     3,
+        clone: true,
+        sized: 1,
+    };
+
+    let t: sample_fields::T<Sample> = Sample::fields().t;
+
+    assert_eq!(*sample.project_to(t), 3);
+    assert!(*sample.project_to(Sample::fields().clone));
+    assert_eq!(*sample.project_to(Sample::fields().sized), 1);
+}
@@ -27,3 +27,19 @@ fn test() {
 
     assert_eq!(*foo.project_to(field), 2.0);
 }
+
+const FOO_TO_BAR_1: Chain<
+    foo_fields::X<Foo<Bar<f32>, i32>>,
+    bar_fields::Field1<Bar<f32>>,
+> = Chain::new(Foo::<Bar<f32>, i32>::fields().x, Bar::<f32>::fields().1);
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn const_generic_chain() {
+    let foo = Foo {
+        x: Bar(0, 2.0),
+        y: 12,
+    };
+
+    assert_eq!(*foo.project_to(FOO_TO_BAR_1), 2.0);
+}
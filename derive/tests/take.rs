@@ -0,0 +1,35 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::*;
+
+#[derive(Field)]
+struct Message {
+    id:      u32,
+    #[field(take)]
+    body:    String,
+    #[field(take)]
+    headers: Vec<(String, String)>,
+}
+
+#[derive(Field)]
+struct Batch<T>(#[field(take)] Vec<T>, usize);
+
+#[test]
+fn take_fields() {
+    let mut message = Message {
+        id:      1,
+        body:    "hello".to_string(),
+        headers: vec![("a".to_string(), "b".to_string())],
+    };
+
+    assert_eq!(message.take_body(), "hello");
+    assert_eq!(message.body, "");
+
+    message.clear_headers();
+    assert!(message.headers.is_empty());
+    assert_eq!(message.id, 1);
+
+    let mut batch = Batch(vec![1, 2, 3], 3);
+    assert_eq!(batch.take_0(), [1, 2, 3]);
+    assert!(batch.0.is_empty());
+}
@@ -0,0 +1,33 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::*;
+
+#[derive(Field, Debug, PartialEq)]
+#[field(with)]
+struct Config {
+    name:    String,
+    retries: u32,
+    verbose: bool,
+}
+
+#[derive(Field, Debug, PartialEq)]
+struct Point<T>(#[field(with)] T, T);
+
+#[test]
+fn with_fields() {
+    let config = Config {
+        name:    "default".to_string(),
+        retries: 3,
+        verbose: false,
+    };
+
+    let config = config.with_retries(5).with_verbose(true);
+
+    assert_eq!(config, Config {
+        name:    "default".to_string(),
+        retries: 5,
+        verbose: true,
+    });
+
+    assert_eq!(Point(1, 2).with_0(3), Point(3, 2));
+}